@@ -3,10 +3,17 @@
 //! Evalúa código fuente contra reglas de arquitectura específicas del framework,
 //! principios SOLID, Clean Code y mejores prácticas.
 
-use crate::ai::client::{TaskType, consultar_ia_dinamico};
+use crate::ai::client::{TaskType, consultar_ia_con_sistema};
+use crate::ai::injection::{INSTRUCCIONES_SISTEMA, delimitar};
+use crate::ai::prompts;
 use crate::ai::utils::{eliminar_bloques_codigo, extraer_codigo};
 use crate::config::SentinelConfig;
+use crate::i18n::t;
+use crate::report;
+use crate::rules;
+use crate::rules::security::{self, Severidad};
 use crate::stats::SentinelStats;
+use colored::*;
 use std::fs;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
@@ -17,6 +24,14 @@ use std::sync::{Arc, Mutex};
 /// Si encuentra problemas críticos, la IA responderá comenzando con "CRITICO",
 /// de lo contrario con "SEGURO".
 ///
+/// Antes de consultar a la IA corre el motor de reglas de seguridad local
+/// (`rules::security`): sus hallazgos se muestran y se agregan al prompt como
+/// contexto. Un hallazgo crítico rechaza el código aunque la IA responda "SEGURO".
+///
+/// Las instrucciones viajan en el prompt de sistema y el código en una sección
+/// delimitada (ver `ai::injection`). Una respuesta sin veredicto reconocible se
+/// trata como "CRITICO".
+///
 /// # Argumentos
 ///
 /// * `codigo` - Código fuente a analizar
@@ -34,7 +49,8 @@ use std::sync::{Arc, Mutex};
 ///
 /// # Efectos secundarios
 ///
-/// Crea un archivo `{file_name}.suggested` con la versión mejorada del código.
+/// Crea un archivo `{file_name}.suggested` con la versión mejorada del código
+/// y registra el veredicto en `.sentinel/reviews.jsonl` (ver `report`).
 pub fn analizar_arquitectura(
     codigo: &str,
     file_name: &str,
@@ -43,51 +59,75 @@ pub fn analizar_arquitectura(
     project_path: &Path,
     file_path: &Path,
 ) -> anyhow::Result<bool> {
-    // Convertimos el Vec<String> de reglas en una lista numerada para el prompt
-    let reglas_str = config
-        .architecture_rules
-        .iter()
-        .enumerate()
-        .map(|(i, r)| format!("{}. {}", i + 1, r))
-        .collect::<Vec<_>>()
-        .join("\n");
+    // Reglas de seguridad deterministas (sin IA)
+    let hallazgos = security::analizar(codigo, file_path, config);
+    let hallazgos_str = security::formatear_hallazgos(&hallazgos);
+    let seccion_hallazgos = if hallazgos.is_empty() {
+        String::new()
+    } else {
+        println!("\n{}\n{}", t("analisis.seguridad_local"), hallazgos_str);
+        format!(
+            "HALLAZGOS DEL ANÁLISIS ESTÁTICO LOCAL (deterministas, confírmalos y corrígelos en el código mejorado):\n\
+            {}\n\n",
+            hallazgos_str
+        )
+    };
+    let critico_local = hallazgos.iter().any(|h| h.severidad == Severidad::Critica);
+
+    // Reglas vigentes (paquete del framework + personalizaciones) como lista numerada
+    let reglas_str = rules::formatear_para_prompt(&config.reglas_efectivas());
 
     // Obtener el lenguaje dinámicamente desde la configuración
     // (detectado por IA durante la inicialización)
     let lenguaje_bloque = &config.code_language;
 
-    let prompt = format!(
-        "Actúa como un Arquitecto de Software experto en {}.\n\n\
-        CONTEXTO DEL PROYECTO:\n\
-        - Framework/Tecnología: {}\n\
-        - Archivo a analizar: {}\n\n\
-        REGLAS DE ARQUITECTURA ESPECÍFICAS:\n\
-        {}\n\n\
-        ANÁLISIS REQUERIDO:\n\
-        Analiza el código siguiente basándote ESTRICTAMENTE en las reglas de arquitectura listadas arriba.\n\
-        Considera las mejores prácticas específicas de {}.\n\n\
-        FORMATO DE RESPUESTA:\n\
-        1. Inicia con 'CRITICO' si hay fallos graves de arquitectura/seguridad, o 'SEGURO' si está bien\n\
-        2. Explica brevemente los problemas encontrados o aspectos positivos\n\
-        3. Incluye el código mejorado en un bloque ```{}\n\n\
-        CÓDIGO A ANALIZAR:\n{}",
-        config.framework,
-        config.framework,
-        file_name,
-        reglas_str,
-        config.framework,
-        lenguaje_bloque,
-        codigo
+    // Las instrucciones van en el prompt de sistema; el código, delimitado, en el mensaje
+    let sistema = format!(
+        "{}\n\n{}",
+        INSTRUCCIONES_SISTEMA,
+        prompts::renderizar(
+            "review-system",
+            project_path,
+            &[
+                ("framework", config.framework.as_str()),
+                ("language", lenguaje_bloque),
+            ],
+        )
+    );
+
+    let prompt = prompts::renderizar(
+        "review",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("file_name", file_name),
+            ("rules", &reglas_str),
+            ("findings", &seccion_hallazgos),
+            ("code", &delimitar("CODIGO", codigo)),
+        ],
     );
 
-    let respuesta = consultar_ia_dinamico(
+    let respuesta = consultar_ia_con_sistema(
+        &sistema,
         prompt,
         TaskType::Deep,
         config,
         Arc::clone(&stats),
         project_path,
     )?;
-    let es_critico = respuesta.trim().to_uppercase().starts_with("CRITICO");
+
+    // Validar el veredicto contra los hallazgos locales
+    let es_critico = match interpretar_veredicto(&respuesta) {
+        Some(false) if critico_local => {
+            println!("{}", t("analisis.seguro_con_criticos").yellow());
+            true
+        }
+        Some(critico) => critico,
+        None => {
+            println!("{}", t("analisis.veredicto_desconocido").yellow());
+            true
+        }
+    };
 
     // Actualizamos estadísticas en memoria
     {
@@ -100,6 +140,13 @@ pub fn analizar_arquitectura(
         }
         s.guardar(project_path); // Guardamos en disco de inmediato
     }
+    report::registrar_revision(
+        project_path,
+        file_path,
+        es_critico,
+        hallazgos.len(),
+        report::Origen::Monitor,
+    );
 
     // Guardamos sugerencia en el proyecto original (mismo path que el archivo)
     let sugerencia = extraer_codigo(&respuesta);
@@ -113,7 +160,40 @@ pub fn analizar_arquitectura(
     fs::write(&suggested_path, &sugerencia)?;
 
     let consejo = eliminar_bloques_codigo(&respuesta);
-    println!("\n✨ CONSEJO DE IA:\n{}", consejo);
+    println!("\n{}\n{}", t("analisis.consejo"), consejo);
 
     Ok(!es_critico)
 }
+
+/// Extrae el veredicto de la respuesta (`Some(true)` si es CRITICO)
+///
+/// Tolera formato markdown al inicio (`**CRITICO**`, `# SEGURO`). Retorna
+/// `None` si la respuesta no empieza con ninguno de los dos.
+fn interpretar_veredicto(respuesta: &str) -> Option<bool> {
+    let inicio = respuesta
+        .trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '*' | '#' | '_' | '`'))
+        .to_uppercase();
+    if inicio.starts_with("CRITICO") || inicio.starts_with("CRÍTICO") {
+        Some(true)
+    } else if inicio.starts_with("SEGURO") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpretar_veredicto() {
+        assert_eq!(
+            interpretar_veredicto("CRITICO: SQL concatenado"),
+            Some(true)
+        );
+        assert_eq!(interpretar_veredicto("**Crítico**\n..."), Some(true));
+        assert_eq!(interpretar_veredicto("  # SEGURO\nTodo bien"), Some(false));
+        assert_eq!(interpretar_veredicto("El código es SEGURO"), None);
+    }
+}
@@ -3,6 +3,7 @@
 //! Guarda respuestas de IA en disco para evitar consultas repetidas.
 //! Usa hash del prompt como identificador del caché.
 
+use crate::i18n::t;
 use colored::*;
 use std::collections::hash_map::DefaultHasher;
 use std::fs;
@@ -39,13 +40,10 @@ pub fn limpiar_cache(project_path: &Path) -> anyhow::Result<()> {
 
     if cache_dir.exists() {
         fs::remove_dir_all(&cache_dir)?;
-        println!("{}", "   🗑️  Caché limpiado exitosamente.".green());
-        println!(
-            "{}",
-            "   💡 El caché se regenerará automáticamente en las próximas consultas.".dimmed()
-        );
+        println!("{}", t("cache.limpiado").green());
+        println!("{}", t("cache.se_regenerara").dimmed());
     } else {
-        println!("{}", "   ℹ️  No hay caché para limpiar.".yellow());
+        println!("{}", t("cache.vacio").yellow());
     }
 
     Ok(())
@@ -12,7 +12,10 @@
 //! Incluye sistema de fallback automático entre modelos.
 
 use crate::ai::cache::{guardar_en_cache, intentar_leer_cache};
+use crate::ai::injection::INSTRUCCIONES_SISTEMA;
 use crate::config::{AIConfig, AIProvider, SentinelConfig};
+use crate::i18n::{self, t, tf};
+use crate::secrets;
 use crate::stats::SentinelStats;
 use colored::*;
 use reqwest::blocking::Client;
@@ -20,6 +23,45 @@ use serde_json::json;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+/// Rol de un mensaje dentro de una conversación con la IA
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rol {
+    /// Instrucciones (se envían como prompt de sistema del proveedor)
+    Sistema,
+    Usuario,
+    Asistente,
+}
+
+/// Mensaje de una conversación de varios turnos
+#[derive(Debug, Clone)]
+pub struct Mensaje {
+    pub rol: Rol,
+    pub contenido: String,
+}
+
+impl Mensaje {
+    pub fn sistema(contenido: impl Into<String>) -> Self {
+        Self {
+            rol: Rol::Sistema,
+            contenido: contenido.into(),
+        }
+    }
+
+    pub fn usuario(contenido: impl Into<String>) -> Self {
+        Self {
+            rol: Rol::Usuario,
+            contenido: contenido.into(),
+        }
+    }
+
+    pub fn asistente(contenido: impl Into<String>) -> Self {
+        Self {
+            rol: Rol::Asistente,
+            contenido: contenido.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum TaskType {
     Light, // Commits, docs
@@ -28,36 +70,63 @@ pub enum TaskType {
 
 /// Punto de entrada inteligente con Fallback y Caché
 pub fn consultar_ia_dinamico(
+    prompt: String,
+    task: TaskType,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: &Path,
+) -> anyhow::Result<String> {
+    consultar_ia_con_sistema(
+        INSTRUCCIONES_SISTEMA,
+        prompt,
+        task,
+        config,
+        stats,
+        project_path,
+    )
+}
+
+/// Igual que `consultar_ia_dinamico`, con instrucciones propias en el prompt de sistema
+///
+/// `sistema` lleva las instrucciones de la tarea y `prompt` solo el contexto
+/// y el contenido delimitado (ver `injection::delimitar`).
+pub fn consultar_ia_con_sistema(
+    sistema: &str,
     prompt: String,
     _task: TaskType,
     config: &SentinelConfig,
     stats: Arc<Mutex<SentinelStats>>,
     project_path: &Path,
 ) -> anyhow::Result<String> {
+    // 0. Ocultar secretos: ni el proveedor ni la caché reciben los valores reales
+    let prompt = secrets::redactar_para_ia(&prompt, &config.secret_allowlist);
+    // El idioma de respuesta forma parte de la clave: cambiar `language` no reutiliza respuestas
+    let clave_cache = format!("{}\n\n{}\n\n{}", sistema, i18n::idioma().codigo(), prompt);
+
     // 1. Intentar Caché
-    if config.use_cache {
-        if let Some(res) = intentar_leer_cache(&prompt, project_path) {
-            println!("{}", "   ♻️  Usando respuesta de caché...".dimmed());
-            return Ok(res);
-        }
+    if config.use_cache
+        && let Some(res) = intentar_leer_cache(&clave_cache, project_path)
+    {
+        println!("{}", t("ia.usando_cache").dimmed());
+        return Ok(res);
     }
 
     // 2. Intentar ejecución con Fallback
-    let resultado =
-        consultar_ia_con_fallback(prompt.clone(), &config.ai_configs, Arc::clone(&stats));
+    let mensajes = [Mensaje::sistema(sistema), Mensaje::usuario(prompt)];
+    let resultado = consultar_ia_con_fallback(&mensajes, &config.ai_configs, Arc::clone(&stats));
 
     // 3. Guardar en Caché si tuvo éxito
-    if let Ok(ref res) = resultado {
-        if config.use_cache {
-            let _ = guardar_en_cache(&prompt, res, project_path);
-        }
+    if let Ok(ref res) = resultado
+        && config.use_cache
+    {
+        let _ = guardar_en_cache(&clave_cache, res, project_path);
     }
 
     resultado
 }
 
 pub fn consultar_ia_con_fallback(
-    prompt: String,
+    mensajes: &[Mensaje],
     configs: &[AIConfig],
     stats: Arc<Mutex<SentinelStats>>,
 ) -> anyhow::Result<String> {
@@ -67,29 +136,40 @@ pub fn consultar_ia_con_fallback(
         ));
     }
 
-    let mut last_error = anyhow::anyhow!("Error desconocido");
+    let mut last_error = anyhow::anyhow!("{}", t("ia.error_desconocido"));
 
     for (i, config) in configs.iter().enumerate() {
         if i > 0 {
             println!(
-                "\n⚠️  El modelo '{}' falló. Intentando con el siguiente configurado: '{}'...",
-                configs[i - 1].name,
-                config.name
+                "\n{}",
+                tf(
+                    "ia.modelo_fallo",
+                    &[
+                        ("anterior", &configs[i - 1].name),
+                        ("siguiente", &config.name)
+                    ]
+                )
             );
         }
 
-        match consultar_ia(prompt.clone(), config.clone(), Arc::clone(&stats)) {
+        match consultar_ia_conversacion(mensajes, config.clone(), Arc::clone(&stats)) {
             Ok(res) => {
                 if i > 0 {
                     println!(
-                        "   ✅ El modelo '{}' respondió correctamente.\n",
-                        config.name
+                        "{}\n",
+                        tf("ia.modelo_respondio", &[("modelo", &config.name)])
                     );
                 }
                 return Ok(res);
             }
             Err(e) => {
-                println!("   ❌ Error en '{}': {}", config.name, e);
+                println!(
+                    "{}",
+                    tf(
+                        "ia.error_modelo",
+                        &[("modelo", &config.name), ("error", &e.to_string())]
+                    )
+                );
                 last_error = e;
             }
         }
@@ -101,22 +181,95 @@ pub fn consultar_ia_con_fallback(
     ))
 }
 
+/// Variante de `consultar_ia_con_fallback` para conversaciones de varios turnos
+///
+/// Como `consultar_ia_dinamico`, redacta los secretos de cada mensaje antes de
+/// enviarlo (sin caché: cada turno depende de los anteriores).
+pub fn consultar_conversacion_con_fallback(
+    mensajes: &[Mensaje],
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+) -> anyhow::Result<String> {
+    let mut last_error = anyhow::anyhow!(
+        "No hay configuraciones de IA disponibles. Reinicia Sentinel para configurar una."
+    );
+    let mut redactados = Vec::with_capacity(mensajes.len());
+    let mut cantidad = 0;
+    for mensaje in mensajes {
+        let (contenido, n) = secrets::redactar(&mensaje.contenido, &config.secret_allowlist);
+        redactados.push(Mensaje {
+            rol: mensaje.rol,
+            contenido,
+        });
+        cantidad += n;
+    }
+    secrets::avisar_redaccion(cantidad);
+
+    for ai_config in &config.ai_configs {
+        match consultar_ia_conversacion(&redactados, ai_config.clone(), Arc::clone(&stats)) {
+            Ok(res) => return Ok(res),
+            Err(e) => {
+                println!(
+                    "{}",
+                    tf(
+                        "ia.error_modelo",
+                        &[("modelo", &ai_config.name), ("error", &e.to_string())]
+                    )
+                );
+                last_error = e;
+            }
+        }
+    }
+
+    Err(last_error)
+}
+
 pub fn consultar_ia(
     prompt: String,
     config: AIConfig,
     stats: Arc<Mutex<SentinelStats>>,
+) -> anyhow::Result<String> {
+    consultar_ia_conversacion(&[Mensaje::usuario(prompt)], config, stats)
+}
+
+/// Consulta a la IA enviando el historial completo de una conversación
+///
+/// Los mensajes se envían en orden y deben alternar usuario/asistente,
+/// empezando y terminando por un mensaje del usuario. Los mensajes de
+/// `Rol::Sistema` se envían como prompt de sistema del proveedor; si no hay
+/// ninguno se usa `INSTRUCCIONES_SISTEMA`. Al final se agrega el idioma en que
+/// debe responder (`i18n::instruccion_respuesta`).
+pub fn consultar_ia_conversacion(
+    mensajes: &[Mensaje],
+    config: AIConfig,
+    stats: Arc<Mutex<SentinelStats>>,
 ) -> anyhow::Result<String> {
     let client = Client::new();
-    let prompt_len = prompt.len();
+    let prompt_len: usize = mensajes.iter().map(|m| m.contenido.len()).sum();
+
+    let (sistema, turnos): (Vec<Mensaje>, Vec<Mensaje>) = mensajes
+        .iter()
+        .cloned()
+        .partition(|m| m.rol == Rol::Sistema);
+    let sistema = if sistema.is_empty() {
+        INSTRUCCIONES_SISTEMA.to_string()
+    } else {
+        sistema
+            .iter()
+            .map(|m| m.contenido.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    };
+    let sistema = format!("{}\n\n{}", sistema, i18n::instruccion_respuesta());
 
     let resultado = match config.provider {
-        AIProvider::Claude => consultar_claude(&client, prompt, &config),
-        AIProvider::Gemini => consultar_gemini(&client, prompt, &config),
+        AIProvider::Claude => consultar_claude(&client, &sistema, &turnos, &config),
+        AIProvider::Gemini => consultar_gemini(&client, &sistema, &turnos, &config),
         AIProvider::OpenAI
         | AIProvider::Groq
         | AIProvider::Ollama
         | AIProvider::Kimi
-        | AIProvider::DeepSeek => consultar_openai_compatible(&client, prompt, &config),
+        | AIProvider::DeepSeek => consultar_openai_compatible(&client, &sistema, &turnos, &config),
     };
 
     if let Ok(ref res) = resultado {
@@ -132,7 +285,27 @@ pub fn consultar_ia(
     resultado
 }
 
-fn consultar_claude(client: &Client, prompt: String, config: &AIConfig) -> anyhow::Result<String> {
+/// Convierte el historial al formato `messages` de Claude y OpenAI
+fn mensajes_json(mensajes: &[Mensaje]) -> Vec<serde_json::Value> {
+    mensajes
+        .iter()
+        .map(|m| {
+            let rol = match m.rol {
+                Rol::Sistema => "system",
+                Rol::Usuario => "user",
+                Rol::Asistente => "assistant",
+            };
+            json!({"role": rol, "content": m.contenido})
+        })
+        .collect()
+}
+
+fn consultar_claude(
+    client: &Client,
+    sistema: &str,
+    mensajes: &[Mensaje],
+    config: &AIConfig,
+) -> anyhow::Result<String> {
     let url = format!("{}/v1/messages", config.api_url.trim_end_matches('/'));
     let response = client
         .post(&url)
@@ -142,7 +315,8 @@ fn consultar_claude(client: &Client, prompt: String, config: &AIConfig) -> anyho
         .json(&json!({
             "model": config.model,
             "max_tokens": 1500,
-            "messages": [{"role": "user", "content": prompt}]
+            "system": sistema,
+            "messages": mensajes_json(mensajes)
         }))
         .send()?;
 
@@ -151,7 +325,12 @@ fn consultar_claude(client: &Client, prompt: String, config: &AIConfig) -> anyho
     })
 }
 
-fn consultar_gemini(client: &Client, prompt: String, config: &AIConfig) -> anyhow::Result<String> {
+fn consultar_gemini(
+    client: &Client,
+    sistema: &str,
+    mensajes: &[Mensaje],
+    config: &AIConfig,
+) -> anyhow::Result<String> {
     let url = format!(
         "{}/v1beta/models/{}:generateContent?key={}",
         config.api_url.trim_end_matches('/'),
@@ -159,13 +338,24 @@ fn consultar_gemini(client: &Client, prompt: String, config: &AIConfig) -> anyho
         config.api_key
     );
 
+    // Gemini usa el rol "model" para las respuestas del asistente
+    let contents: Vec<serde_json::Value> = mensajes
+        .iter()
+        .map(|m| {
+            let rol = match m.rol {
+                Rol::Sistema | Rol::Usuario => "user",
+                Rol::Asistente => "model",
+            };
+            json!({"role": rol, "parts": [{ "text": m.contenido }]})
+        })
+        .collect();
+
     let response = client
         .post(&url)
         .header("content-type", "application/json")
         .json(&json!({
-            "contents": [{
-                "parts": [{ "text": prompt }]
-            }]
+            "systemInstruction": { "parts": [{ "text": sistema }] },
+            "contents": contents
         }))
         .send()?;
 
@@ -178,7 +368,8 @@ fn consultar_gemini(client: &Client, prompt: String, config: &AIConfig) -> anyho
 
 fn consultar_openai_compatible(
     client: &Client,
-    prompt: String,
+    sistema: &str,
+    mensajes: &[Mensaje],
     config: &AIConfig,
 ) -> anyhow::Result<String> {
     let url = format!("{}/chat/completions", config.api_url.trim_end_matches('/'));
@@ -189,13 +380,13 @@ fn consultar_openai_compatible(
         request = request.header("authorization", format!("Bearer {}", config.api_key));
     }
 
+    let mut messages = vec![json!({"role": "system", "content": sistema})];
+    messages.extend(mensajes_json(mensajes));
+
     let response = request
         .json(&json!({
             "model": config.model,
-            "messages": [
-                {"role": "system", "content": "Eres un Arquitecto de Software Senior."},
-                {"role": "user", "content": prompt}
-            ],
+            "messages": messages,
             "temperature": 0.1
         }))
         .send()?;
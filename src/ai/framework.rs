@@ -3,20 +3,57 @@
 //! Analiza archivos del proyecto para identificar el framework principal,
 //! lenguaje de programación, patrones de arquitectura y configuraciones.
 
-use crate::ai::client::consultar_ia;
+use crate::ai::client::{Mensaje, consultar_ia_conversacion};
+use crate::ai::injection::{self, INSTRUCCIONES_SISTEMA, delimitar};
+use crate::ai::prompts;
 use crate::config::{FrameworkDetection, SentinelConfig};
+use crate::i18n::{t, tf};
+use crate::secrets;
 use crate::stats::SentinelStats;
 use colored::*;
 use std::fs;
-use std::path::Path;
+use std::io::Read;
+use std::path::{Component, Path};
 use std::sync::{Arc, Mutex};
 
+/// Máximo de turnos en los que la IA puede pedir archivos antes de responder
+const MAX_TURNOS_LECTURA: usize = 3;
+
+/// Máximo de archivos que se entregan por turno
+const MAX_ARCHIVOS_POR_TURNO: usize = 3;
+
+/// Tamaño máximo (en bytes) que se envía de cada archivo solicitado
+const MAX_BYTES_ARCHIVO: usize = 16 * 1024;
+
+/// Nombres de archivo que nunca se envían a la IA (secretos y credenciales)
+const ARCHIVOS_PROHIBIDOS: &[&str] = &[
+    ".sentinelrc.toml",
+    ".npmrc",
+    ".pypirc",
+    ".netrc",
+    ".git-credentials",
+    "id_rsa",
+    "id_ed25519",
+    "credentials.json",
+    "secrets.json",
+    "secrets.yaml",
+    "secrets.yml",
+];
+
+/// Extensiones de archivos de claves y certificados
+const EXTENSIONES_PROHIBIDAS: &[&str] = &["pem", "key", "p12", "pfx", "crt", "keystore", "jks"];
+
 /// Detecta el framework y sus reglas usando IA analizando los archivos del proyecto
+///
+/// Solo se invoca cuando `detector::detectar_framework_local` no es concluyente
+/// o cuando `force_ai_detection` está activo. Retorna `Err` si la respuesta no
+/// es un JSON válido para que el llamador decida el fallback.
 pub fn detectar_framework_con_ia(
     project_path: &Path,
     config: &SentinelConfig,
+    candidatos: &[String],
 ) -> anyhow::Result<FrameworkDetection> {
-    println!("{}", "🤖 Detectando framework con IA...".magenta());
+    println!("{}", t("framework.detectando").magenta());
 
     let archivos = SentinelConfig::listar_archivos_raiz(project_path);
     let archivos_str = archivos.join("\n");
@@ -24,6 +61,14 @@ pub fn detectar_framework_con_ia(
     // Leer automáticamente archivos clave para mejorar la detección
     let mut contenido_extra = String::new();
 
+    // Candidatos de la detección heurística (proyecto ambiguo)
+    if !candidatos.is_empty() {
+        contenido_extra.push_str(&format!(
+            "\n\nCANDIDATOS DETECTADOS LOCALMENTE (elige el que gobierna la arquitectura): {}",
+            candidatos.join(", ")
+        ));
+    }
+
     // Intentar leer package.json (proyectos JS/TS)
     if let Ok(package_json) = fs::read_to_string(project_path.join("package.json")) {
         let primeras_lineas: String = package_json
@@ -33,7 +78,7 @@ pub fn detectar_framework_con_ia(
             .join("\n");
         contenido_extra.push_str(&format!(
             "\n\nCONTENIDO DE package.json (primeras 50 líneas):\n{}",
-            primeras_lineas
+            delimitar("package.json", &primeras_lineas)
         ));
     }
 
@@ -42,7 +87,7 @@ pub fn detectar_framework_con_ia(
         let primeras_lineas: String = requirements.lines().take(30).collect::<Vec<_>>().join("\n");
         contenido_extra.push_str(&format!(
             "\n\nCONTENIDO DE requirements.txt:\n{}",
-            primeras_lineas
+            delimitar("requirements.txt", &primeras_lineas)
         ));
     }
 
@@ -55,94 +100,230 @@ pub fn detectar_framework_con_ia(
             .join("\n");
         contenido_extra.push_str(&format!(
             "\n\nCONTENIDO DE composer.json:\n{}",
-            primeras_lineas
+            delimitar("composer.json", &primeras_lineas)
         ));
     }
 
+    // Instrucciones en el prompt de sistema; archivos del proyecto, delimitados, en el mensaje
+    let sistema = format!(
+        "{}\n\n{}",
+        INSTRUCCIONES_SISTEMA,
+        prompts::renderizar(
+            "framework-detection",
+            project_path,
+            &[
+                ("max_files", &MAX_ARCHIVOS_POR_TURNO.to_string()),
+                ("max_turns", &MAX_TURNOS_LECTURA.to_string()),
+            ],
+        )
+    );
+
     let prompt_inicial = format!(
-        "Eres un Experto en Arquitectura de Software. Tu tarea es identificar el \"Framework de Alto Nivel\" \
-        que gobierna la arquitectura del proyecto.\n\n\
-        CONTEXTO:\n\
-        Archivos raíz: {}\
-        {}\n\n\
-        INSTRUCCIONES CRÍTICAS DE DIFERENCIACIÓN:\n\
-        1. Framework vs Lenguaje: No respondas con el nombre del lenguaje (ej. TypeScript, Python). \
-        Identifica el framework que dicta la estructura (ej. React, FastAPI, NestJS).\n\
-        2. Jerarquía de Decisión:\n\
-           - Si detectas 'react', el framework es \"React\" (aunque use Vite o Next, prioriza el ecosistema).\n\
-           - Si detectas '@nestjs/core', el framework es \"NestJS\", no \"Node.js\".\n\
-           - Si detectas 'actix-web' o 'axum' en un Cargo.toml, el framework es el nombre del crate.\n\
-        3. Precisión en Monorepos: Si ves múltiples configuraciones, identifica la que define la ejecución principal.\n\n\
-        RESPONDE EXCLUSIVAMENTE EN JSON:\n\
-        {{\n\
-          \"framework\": \"Nombre específico del framework (ej. React, Django, Axum)\",\n\
-          \"code_language\": \"Lenguaje base (ej. typescript, rust, python)\",\n\
-          \"rules\": [\"4 principios técnicos clave\"],\n\
-          \"extensions\": [\"ts\", \"tsx\", \"js\", etc],\n\
-          \"parent_patterns\": [\"sufijos de arquitectura\"],\n\
-          \"test_patterns\": [\"rutas de tests con {{name}}\"]\n\
-        }}\n\n\
-        IMPORTANTE: Si no hay un framework claro, identifica la librería de entrada (entry-point) principal. \
-        Prohibido responder con nombres genéricos como \"JavaScript/TypeScript\".",
-        archivos_str, contenido_extra
+        "CONTEXTO:\n\
+        Archivos raíz:\n{}\
+        {}",
+        delimitar("ARCHIVOS RAIZ", &archivos_str),
+        contenido_extra
     );
 
-    // Primera consulta
-    let respuesta = consultar_ia(
-        prompt_inicial,
-        config.ai_configs[0].clone(),
-        Arc::new(Mutex::new(SentinelStats::default())),
-    )?;
-
-    // Si la IA pide leer un archivo
-    if respuesta.trim().starts_with("LEER:") {
-        let archivo = respuesta.trim().replace("LEER:", "").trim().to_string();
-        let archivo_path = project_path.join(&archivo);
-
-        println!("   📄 IA solicita leer: {}", archivo.cyan());
-
-        if let Ok(contenido) = fs::read_to_string(&archivo_path) {
-            // Limitar contenido a primeras 100 líneas para no saturar
-            let contenido_limitado: String =
-                contenido.lines().take(100).collect::<Vec<_>>().join("\n");
-
-            let prompt_con_contenido = format!(
-                "Eres un Experto en Arquitectura de Software. Identifica el \"Framework de Alto Nivel\" del proyecto.\n\n\
-                CONTEXTO:\n\
-                Archivos raíz: {}\n\n\
-                Contenido de '{}':\n{}\n\n\
-                INSTRUCCIONES CRÍTICAS:\n\
-                1. Framework vs Lenguaje: Identifica el framework que dicta la arquitectura, NO el lenguaje.\n\
-                2. Jerarquía:\n\
-                   - 'react' en dependencies → Framework: \"React\"\n\
-                   - '@nestjs/core' → Framework: \"NestJS\"\n\
-                   - 'Django' → Framework: \"Django\"\n\
-                3. Prohibido responder con nombres genéricos como \"TypeScript\" o \"JavaScript\".\n\n\
-                RESPONDE EN JSON:\n\
-                {{\n\
-                  \"framework\": \"Nombre específico del framework\",\n\
-                  \"code_language\": \"lenguaje base\",\n\
-                  \"rules\": [\"4 principios clave\"],\n\
-                  \"extensions\": [\"extensiones\"],\n\
-                  \"parent_patterns\": [\"sufijos o []\"],\n\
-                  \"test_patterns\": [\"rutas con {{{{name}}}}\"]\n\
-                }}\n\n\
-                IMPORTANTE: SOLO JSON, sin texto adicional.",
-                archivos_str, archivo, contenido_limitado
-            );
+    // Aún no hay configuración del proyecto: se redacta sin allowlist
+    let mut conversacion = vec![
+        Mensaje::sistema(sistema),
+        Mensaje::usuario(secrets::redactar_para_ia(&prompt_inicial, &[])),
+    ];
+    let stats = Arc::new(Mutex::new(SentinelStats::default()));
 
-            let respuesta_final = consultar_ia(
-                prompt_con_contenido,
-                config.ai_configs[0].clone(),
-                Arc::new(Mutex::new(SentinelStats::default())),
-            )?;
+    for turno in 0..=MAX_TURNOS_LECTURA {
+        let respuesta = consultar_ia_conversacion(
+            &conversacion,
+            config.ai_configs[0].clone(),
+            Arc::clone(&stats),
+        )?;
 
-            return parsear_deteccion_framework(&respuesta_final);
+        let solicitudes = extraer_solicitudes_lectura(&respuesta);
+        if solicitudes.is_empty() {
+            return parsear_deteccion_framework(&respuesta);
+        }
+        if turno == MAX_TURNOS_LECTURA {
+            return Err(anyhow::anyhow!(
+                "la IA siguió pidiendo archivos tras {} turnos de lectura",
+                MAX_TURNOS_LECTURA
+            ));
+        }
+
+        let mut entregados = String::new();
+        for archivo in solicitudes.iter().take(MAX_ARCHIVOS_POR_TURNO) {
+            match leer_archivo_seguro(project_path, archivo) {
+                Ok(contenido) => {
+                    println!(
+                        "{}",
+                        tf(
+                            "framework.solicita_leer",
+                            &[("archivo", &archivo.cyan().to_string())]
+                        )
+                    );
+                    if let Some((linea, _)) = injection::buscar_marcadores(&contenido).first() {
+                        println!(
+                            "{}",
+                            tf(
+                                "framework.posible_inyeccion",
+                                &[
+                                    ("archivo", &archivo.yellow().to_string()),
+                                    ("linea", &linea.to_string())
+                                ]
+                            )
+                        );
+                    }
+                    entregados.push_str(&format!(
+                        "\n\nCONTENIDO DE '{}':\n{}",
+                        archivo,
+                        delimitar(&format!("ARCHIVO {}", archivo), &contenido)
+                    ));
+                }
+                Err(motivo) => {
+                    println!(
+                        "{}",
+                        tf(
+                            "framework.lectura_rechazada",
+                            &[
+                                ("archivo", &archivo.yellow().to_string()),
+                                ("motivo", &motivo)
+                            ]
+                        )
+                    );
+                    entregados.push_str(&format!("\n\n'{}' NO DISPONIBLE: {}", archivo, motivo));
+                }
+            }
+        }
+        if solicitudes.len() > MAX_ARCHIVOS_POR_TURNO {
+            entregados.push_str(&format!(
+                "\n\nSolo se entregan {} archivos por turno; el resto fue omitido.",
+                MAX_ARCHIVOS_POR_TURNO
+            ));
         }
+
+        let restantes = MAX_TURNOS_LECTURA - turno - 1;
+        let cierre = if restantes == 0 {
+            "Ya no puedes pedir más archivos. RESPONDE SOLO CON EL JSON.".to_string()
+        } else {
+            format!(
+                "Puedes pedir más archivos en {} turno(s) más con LEER:, o responder con el JSON.",
+                restantes
+            )
+        };
+
+        conversacion.push(Mensaje::asistente(respuesta));
+        conversacion.push(Mensaje::usuario(secrets::redactar_para_ia(
+            &format!("{}\n\n{}", entregados.trim_start(), cierre),
+            &[],
+        )));
+    }
+
+    unreachable!("el bucle siempre retorna en el último turno")
+}
+
+/// Extrae las rutas pedidas por la IA con líneas `LEER: <ruta>`
+///
+/// Solo se consideran solicitudes si la respuesta no contiene ya un JSON
+/// (la IA a veces acompaña el JSON final con texto que menciona `LEER:`).
+fn extraer_solicitudes_lectura(respuesta: &str) -> Vec<String> {
+    if respuesta.contains('{') && respuesta.contains('}') {
+        return vec![];
     }
 
-    // Parsear respuesta JSON
-    parsear_deteccion_framework(&respuesta)
+    let mut rutas: Vec<String> = Vec::new();
+    for linea in respuesta.lines() {
+        let linea = linea.trim().trim_start_matches(['-', '*', ' ', '`']);
+        if let Some(ruta) = linea.strip_prefix("LEER:") {
+            let ruta = ruta.trim().trim_matches(['`', '"', '\'']).to_string();
+            if !ruta.is_empty() && !rutas.contains(&ruta) {
+                rutas.push(ruta);
+            }
+        }
+    }
+    rutas
+}
+
+/// Lee un archivo pedido por la IA aplicando las restricciones de seguridad
+///
+/// Rechaza rutas absolutas o que salgan de `project_path` (incluido vía
+/// symlinks), archivos de secretos (`.env*`, claves, `.sentinelrc.toml`) y
+/// contenido no UTF-8. El contenido se recorta a `MAX_BYTES_ARCHIVO`.
+///
+/// # Retorna
+/// El contenido (posiblemente recortado) o el motivo del rechazo.
+fn leer_archivo_seguro(project_path: &Path, ruta: &str) -> Result<String, String> {
+    let relativa = Path::new(ruta);
+    if relativa
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        return Err(t("framework.motivo_fuera_del_proyecto"));
+    }
+
+    let nombre = relativa
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let extension = relativa
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let en_directorio_privado = relativa
+        .components()
+        .any(|c| matches!(c.as_os_str().to_str(), Some(".git" | ".ssh" | ".sentinel")));
+    if nombre.starts_with(".env")
+        || ARCHIVOS_PROHIBIDOS.contains(&nombre.as_str())
+        || EXTENSIONES_PROHIBIDAS.contains(&extension.as_str())
+        || en_directorio_privado
+    {
+        return Err(t("framework.motivo_archivo_sensible"));
+    }
+
+    let raiz = project_path
+        .canonicalize()
+        .map_err(|_| t("framework.motivo_proyecto_inaccesible"))?;
+    let completa = raiz
+        .join(relativa)
+        .canonicalize()
+        .map_err(|_| t("framework.motivo_no_existe"))?;
+    if !completa.starts_with(&raiz) {
+        return Err(t("framework.motivo_fuera_del_proyecto"));
+    }
+    if !completa.is_file() {
+        return Err(t("framework.motivo_no_es_archivo"));
+    }
+
+    let mut bytes = Vec::new();
+    fs::File::open(&completa)
+        .and_then(|f| f.take(MAX_BYTES_ARCHIVO as u64 + 1).read_to_end(&mut bytes))
+        .map_err(|e| {
+            tf(
+                "framework.motivo_error_lectura",
+                &[("error", &e.to_string())],
+            )
+        })?;
+
+    let recortado = bytes.len() > MAX_BYTES_ARCHIVO;
+    bytes.truncate(MAX_BYTES_ARCHIVO);
+    let mut contenido = match String::from_utf8(bytes) {
+        Ok(texto) => texto,
+        // El corte pudo partir un carácter multibyte: descartar el resto incompleto
+        Err(e) if recortado && e.utf8_error().error_len().is_none() => {
+            let valido = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valido);
+            String::from_utf8(bytes).unwrap_or_default()
+        }
+        Err(_) => return Err("archivo binario".to_string()),
+    };
+
+    if recortado {
+        contenido.push_str(&format!("\n[... recortado a {} bytes]", MAX_BYTES_ARCHIVO));
+    }
+    Ok(contenido)
 }
 
 /// Parsea la respuesta JSON de la IA con la detección del framework
@@ -160,29 +341,127 @@ fn parsear_deteccion_framework(respuesta: &str) -> anyhow::Result<FrameworkDetec
 
     match serde_json::from_str::<FrameworkDetection>(json_str) {
         Ok(deteccion) => {
-            println!("   ✅ Framework detectado: {}", deteccion.framework.green());
-            Ok(deteccion)
-        }
-        Err(e) => {
-            // Fallback si falla el parsing
+            validar_deteccion(&deteccion)?;
             println!(
-                "   ⚠️  Error al parsear respuesta de IA: {}",
-                e.to_string().yellow()
+                "{}",
+                tf(
+                    "framework.detectado",
+                    &[("framework", &deteccion.framework.green().to_string())]
+                )
             );
-            println!("   ℹ️  Usando configuración genérica. Edita .sentinelrc.toml después.");
-            Ok(FrameworkDetection {
-                framework: "Generic".to_string(),
-                rules: vec![
-                    "Clean Code principles".to_string(),
-                    "SOLID design patterns".to_string(),
-                    "Code maintainability".to_string(),
-                    "Comprehensive testing".to_string(),
-                ],
-                extensions: vec!["js".to_string(), "ts".to_string()],
-                code_language: "typescript".to_string(),
-                parent_patterns: vec![],
-                test_patterns: vec!["{name}.test.ts".to_string(), "{name}.spec.ts".to_string()],
-            })
+            Ok(deteccion)
         }
+        Err(e) => Err(anyhow::anyhow!(
+            "respuesta de IA no válida ({}): {}",
+            e,
+            json_str.chars().take(200).collect::<String>()
+        )),
+    }
+}
+
+/// Rechaza detecciones cuyas rutas o extensiones saldrían del proyecto
+///
+/// Los patrones de tests y de archivos padre se combinan con rutas del
+/// proyecto: una respuesta manipulada no debe poder apuntar fuera de él.
+fn validar_deteccion(deteccion: &FrameworkDetection) -> anyhow::Result<()> {
+    if let Some(ext) = deteccion
+        .extensions
+        .iter()
+        .find(|e| e.is_empty() || !e.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        return Err(anyhow::anyhow!(
+            "extensión no válida en la respuesta: {:?}",
+            ext
+        ));
+    }
+
+    let fuera_del_proyecto = |patron: &String| {
+        let ruta = Path::new(patron);
+        ruta.is_absolute()
+            || patron.starts_with('/')
+            || patron.starts_with('\\')
+            || ruta.components().any(|c| c == Component::ParentDir)
+    };
+    if let Some(patron) = deteccion
+        .test_patterns
+        .iter()
+        .chain(&deteccion.parent_patterns)
+        .find(|p| fuera_del_proyecto(p))
+    {
+        return Err(anyhow::anyhow!(
+            "patrón fuera del proyecto en la respuesta: {}",
+            patron
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_extraer_solicitudes_lectura() {
+        let respuesta = "LEER: package.json\n- LEER: `apps/web/package.json`\nLEER: package.json";
+        assert_eq!(
+            extraer_solicitudes_lectura(respuesta),
+            vec!["package.json", "apps/web/package.json"]
+        );
+
+        // Una respuesta con JSON es la respuesta final aunque mencione LEER:
+        assert!(extraer_solicitudes_lectura("LEER: x\n{\"framework\": \"React\"}").is_empty());
+    }
+
+    #[test]
+    fn test_leer_archivo_seguro_rechaza_rutas_y_secretos() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(".env.local"), "API_KEY=123").unwrap();
+        fs::write(root.join(".sentinelrc.toml"), "api_key = 'x'").unwrap();
+        fs::write(root.join("server.key"), "-----BEGIN").unwrap();
+        fs::write(root.join("main.go"), "package main").unwrap();
+
+        assert!(leer_archivo_seguro(root, "../etc/passwd").is_err());
+        assert!(leer_archivo_seguro(root, "/etc/passwd").is_err());
+        assert_eq!(
+            leer_archivo_seguro(root, ".env.local"),
+            Err(t("framework.motivo_archivo_sensible"))
+        );
+        assert!(leer_archivo_seguro(root, ".sentinelrc.toml").is_err());
+        assert!(leer_archivo_seguro(root, "server.key").is_err());
+        assert!(leer_archivo_seguro(root, "no-existe.go").is_err());
+        assert_eq!(
+            leer_archivo_seguro(root, "./main.go").unwrap(),
+            "package main"
+        );
+    }
+
+    #[test]
+    fn test_parsear_deteccion_rechaza_rutas_fuera_del_proyecto() {
+        let respuesta = |test_pattern: &str| {
+            format!(
+                "{{\"framework\": \"React\", \"code_language\": \"typescript\", \"rules\": [], \
+                \"extensions\": [\"tsx\"], \"parent_patterns\": [], \"test_patterns\": [\"{}\"]}}",
+                test_pattern
+            )
+        };
+        assert!(parsear_deteccion_framework(&respuesta("src/{name}.test.tsx")).is_ok());
+        assert!(parsear_deteccion_framework(&respuesta("../../{name}.test.tsx")).is_err());
+        assert!(parsear_deteccion_framework(&respuesta("/etc/{name}")).is_err());
+    }
+
+    #[test]
+    fn test_leer_archivo_seguro_recorta_contenido() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("grande.txt"),
+            "ñ".repeat(MAX_BYTES_ARCHIVO),
+        )
+        .unwrap();
+
+        let contenido = leer_archivo_seguro(temp_dir.path(), "grande.txt").unwrap();
+        assert!(contenido.ends_with(&format!("[... recortado a {} bytes]", MAX_BYTES_ARCHIVO)));
+        assert!(contenido.len() < MAX_BYTES_ARCHIVO + 50);
     }
 }
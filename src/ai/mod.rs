@@ -6,12 +6,17 @@
 //! - Análisis de arquitectura de código
 //! - Detección y validación de frameworks de testing
 //! - Sistema de caché para optimizar consultas
+//! - Defensa contra inyección de prompts en el contenido enviado
+//! - Plantillas de prompts personalizables por proyecto
 
 pub mod analysis;
 pub mod cache;
 pub mod client;
 pub mod framework;
+pub mod injection;
 pub mod models;
+pub mod prompts;
+pub mod scaffold;
 pub mod testing;
 pub mod utils;
 
@@ -21,4 +26,5 @@ pub use cache::limpiar_cache;
 pub use client::{TaskType, consultar_ia_dinamico};
 pub use framework::detectar_framework_con_ia;
 pub use models::obtener_modelos_disponibles;
+pub use scaffold::generar_test_inicial;
 pub use testing::{TestingStatus, detectar_testing_framework, obtener_sugerencias_complementarias};
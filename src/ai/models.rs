@@ -1,4 +1,5 @@
 use crate::config::AIProvider;
+use crate::i18n::tf;
 use reqwest::blocking::Client;
 
 /// Obtiene la lista de modelos disponibles para el proveedor configurado
@@ -13,7 +14,7 @@ pub fn obtener_modelos_disponibles(
     match provider {
         AIProvider::Claude => {
             let response = client
-                .get(&format!("{}/v1/models", url))
+                .get(format!("{}/v1/models", url))
                 .header("x-api-key", api_key)
                 .header("anthropic-version", "2023-06-01")
                 .send()?;
@@ -21,7 +22,7 @@ pub fn obtener_modelos_disponibles(
             let json: serde_json::Value = response.json()?;
             let models = json["data"]
                 .as_array()
-                .ok_or_else(|| anyhow::anyhow!("Respuesta de Claude inválida"))?
+                .ok_or_else(|| respuesta_invalida("Claude"))?
                 .iter()
                 .filter_map(|m| m["id"].as_str().map(|s| s.to_string()))
                 .collect();
@@ -29,13 +30,13 @@ pub fn obtener_modelos_disponibles(
         }
         AIProvider::Gemini => {
             let response = client
-                .get(&format!("{}/v1beta/models?key={}", url, api_key))
+                .get(format!("{}/v1beta/models?key={}", url, api_key))
                 .send()?;
 
             let json: serde_json::Value = response.json()?;
             let models = json["models"]
                 .as_array()
-                .ok_or_else(|| anyhow::anyhow!("Respuesta de Gemini inválida"))?
+                .ok_or_else(|| respuesta_invalida("Gemini"))?
                 .iter()
                 .filter_map(|m| {
                     m["name"]
@@ -50,7 +51,7 @@ pub fn obtener_modelos_disponibles(
         | AIProvider::Ollama
         | AIProvider::Kimi
         | AIProvider::DeepSeek => {
-            let mut request = client.get(&format!("{}/models", url));
+            let mut request = client.get(format!("{}/models", url));
             if !api_key.is_empty() {
                 request = request.header("authorization", format!("Bearer {}", api_key));
             }
@@ -59,7 +60,7 @@ pub fn obtener_modelos_disponibles(
             let json: serde_json::Value = response.json()?;
             let models = json["data"]
                 .as_array()
-                .ok_or_else(|| anyhow::anyhow!("Respuesta de API compatible inválida"))?
+                .ok_or_else(|| respuesta_invalida("OpenAI-compatible"))?
                 .iter()
                 .filter_map(|m| m["id"].as_str().map(|s| s.to_string()))
                 .collect();
@@ -67,3 +68,11 @@ pub fn obtener_modelos_disponibles(
         }
     }
 }
+
+/// Error de una respuesta de la lista de modelos con formato inesperado
+fn respuesta_invalida(proveedor: &str) -> anyhow::Error {
+    anyhow::anyhow!(
+        "{}",
+        tf("ia.respuesta_modelos_invalida", &[("proveedor", proveedor)])
+    )
+}
@@ -0,0 +1,158 @@
+//! # Plantillas de prompts
+//!
+//! Cada prompt que Sentinel envía a la IA se genera a partir de una plantilla
+//! con nombre. Las plantillas por defecto son archivos de texto en
+//! `src/ai/prompts/templates/` embebidos en el binario con `include_str!`;
+//! cada proyecto puede reemplazar cualquiera de ellas con un archivo del mismo
+//! nombre en `.sentinel/prompts/` (ej: `.sentinel/prompts/review.txt`).
+//!
+//! Las variables se escriben entre llaves (`{framework}`, `{rules}`,
+//! `{file_name}`, `{code}`, `{diff}`, `{test_output}`...). Las llaves que no
+//! corresponden a una variable conocida se dejan tal cual, así que los
+//! ejemplos JSON de las plantillas no necesitan escaparse.
+//!
+//! Las instrucciones de defensa contra inyección (`injection::INSTRUCCIONES_SISTEMA`)
+//! y el delimitado del contenido no confiable no forman parte de las
+//! plantillas: una personalización no puede desactivarlos.
+
+use std::fs;
+use std::path::Path;
+
+/// Directorio de plantillas propias del proyecto (relativo a la raíz)
+pub const DIRECTORIO_PLANTILLAS: &str = ".sentinel/prompts";
+
+/// Plantillas integradas: (nombre, contenido)
+const PLANTILLAS: &[(&str, &str)] = &[
+    ("review-system", include_str!("templates/review-system.txt")),
+    ("review", include_str!("templates/review.txt")),
+    ("pr-review", include_str!("templates/pr-review.txt")),
+    (
+        "commit-message",
+        include_str!("templates/commit-message.txt"),
+    ),
+    ("docs", include_str!("templates/docs.txt")),
+    ("docs-overview", include_str!("templates/docs-overview.txt")),
+    ("doc-comments", include_str!("templates/doc-comments.txt")),
+    (
+        "test-diagnosis",
+        include_str!("templates/test-diagnosis.txt"),
+    ),
+    ("test-fix", include_str!("templates/test-fix.txt")),
+    ("test-scaffold", include_str!("templates/test-scaffold.txt")),
+    ("daily-report", include_str!("templates/daily-report.txt")),
+    ("changelog", include_str!("templates/changelog.txt")),
+    (
+        "framework-detection",
+        include_str!("templates/framework-detection.txt"),
+    ),
+    (
+        "testing-detection",
+        include_str!("templates/testing-detection.txt"),
+    ),
+    (
+        "testing-suggestions",
+        include_str!("templates/testing-suggestions.txt"),
+    ),
+];
+
+/// Retorna el texto de una plantilla: la del proyecto si existe, si no la integrada
+///
+/// # Panics
+/// Si `nombre` no es una plantilla integrada (error de programación).
+pub fn plantilla(nombre: &str, project_path: &Path) -> String {
+    let integrada = PLANTILLAS
+        .iter()
+        .find(|(n, _)| *n == nombre)
+        .map(|(_, contenido)| *contenido)
+        .unwrap_or_else(|| panic!("plantilla de prompt desconocida: {}", nombre));
+
+    let propia = project_path
+        .join(DIRECTORIO_PLANTILLAS)
+        .join(format!("{}.txt", nombre));
+    match fs::read_to_string(&propia) {
+        Ok(contenido) if !contenido.trim().is_empty() => contenido.trim_end().to_string(),
+        _ => integrada.trim_end().to_string(),
+    }
+}
+
+/// Genera un prompt a partir de una plantilla y sus variables
+///
+/// # Ejemplos
+/// ```ignore
+/// let prompt = renderizar("docs", project_path, &[("framework", "NestJS"), ("file_name", "users.service.ts"), ("code", codigo)]);
+/// ```
+pub fn renderizar(nombre: &str, project_path: &Path, variables: &[(&str, &str)]) -> String {
+    sustituir(&plantilla(nombre, project_path), variables)
+}
+
+/// Reemplaza `{variable}` por su valor en una sola pasada
+///
+/// Los valores insertados no se vuelven a procesar: un código que contenga
+/// `{rules}` no se expande.
+pub fn sustituir(plantilla: &str, variables: &[(&str, &str)]) -> String {
+    let mut resultado = String::with_capacity(plantilla.len());
+    let mut resto = plantilla;
+
+    while let Some(inicio) = resto.find('{') {
+        resultado.push_str(&resto[..inicio]);
+        let candidato = &resto[inicio + 1..];
+        let reemplazo = candidato.find('}').and_then(|fin| {
+            let nombre = &candidato[..fin];
+            variables
+                .iter()
+                .find(|(n, _)| *n == nombre)
+                .map(|(_, valor)| (*valor, fin))
+        });
+        match reemplazo {
+            Some((valor, fin)) => {
+                resultado.push_str(valor);
+                resto = &candidato[fin + 1..];
+            }
+            None => {
+                resultado.push('{');
+                resto = candidato;
+            }
+        }
+    }
+    resultado.push_str(resto);
+    resultado
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sustituir_variables() {
+        let texto = sustituir(
+            "Archivo {file_name}:\n{code}\nJSON: {\"a\": 1} y {name}",
+            &[
+                ("file_name", "app.ts"),
+                ("code", "const x = '{file_name}';"),
+            ],
+        );
+        assert_eq!(
+            texto,
+            "Archivo app.ts:\nconst x = '{file_name}';\nJSON: {\"a\": 1} y {name}"
+        );
+    }
+
+    #[test]
+    fn test_plantilla_propia_reemplaza_la_integrada() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        assert!(plantilla("commit-message", root).contains("Conventional Commits"));
+
+        fs::create_dir_all(root.join(DIRECTORIO_PLANTILLAS)).unwrap();
+        fs::write(
+            root.join(DIRECTORIO_PLANTILLAS).join("commit-message.txt"),
+            "Commit en inglés para {file_name}\n",
+        )
+        .unwrap();
+        assert_eq!(
+            renderizar("commit-message", root, &[("file_name", "app.ts")]),
+            "Commit en inglés para app.ts"
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! Generación de tests iniciales con IA
+//!
+//! Cuando un archivo modificado no tiene tests, genera un archivo de test base
+//! con el framework de testing detectado para que el flujo de commit pueda
+//! continuar con una línea base que pasa.
+
+use crate::ai::client::{TaskType, consultar_ia_dinamico};
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::ai::utils::extraer_codigo;
+use crate::config::SentinelConfig;
+use crate::i18n::t;
+use crate::stats::SentinelStats;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Genera el contenido de un test inicial para un módulo
+///
+/// # Argumentos
+///
+/// * `codigo` - Código fuente del módulo a testear
+/// * `source_rel` - Ruta del archivo fuente relativa a la raíz del paquete
+/// * `test_rel` - Ruta donde se creará el test, relativa a la misma raíz
+/// * `config` - Configuración (framework, lenguaje y `testing_framework`)
+/// * `stats` - Estadísticas compartidas del proyecto
+/// * `project_path` - Ruta del proyecto monitoreado (caché de IA)
+///
+/// # Retorna
+///
+/// El contenido del archivo de test (sin bloques markdown).
+pub fn generar_test_inicial(
+    codigo: &str,
+    source_rel: &str,
+    test_rel: &str,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: &Path,
+) -> anyhow::Result<String> {
+    let testing_framework = config
+        .testing_framework
+        .as_deref()
+        .unwrap_or("el framework de testing estándar del stack");
+
+    let prompt = prompts::renderizar(
+        "test-scaffold",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("testing_framework", testing_framework),
+            ("language", &config.code_language),
+            ("file_name", source_rel),
+            ("test_path", test_rel),
+            ("code", &delimitar("CODIGO", codigo)),
+        ],
+    );
+
+    let respuesta = consultar_ia_dinamico(prompt, TaskType::Deep, config, stats, project_path)?;
+    let contenido = extraer_codigo(&respuesta);
+
+    if contenido.trim().is_empty() {
+        return Err(anyhow::anyhow!("{}", t("ia.sin_codigo_test")));
+    }
+    Ok(contenido)
+}
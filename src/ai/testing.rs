@@ -5,7 +5,11 @@
 //! en el framework principal detectado.
 
 use crate::ai::client::consultar_ia;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
 use crate::config::SentinelConfig;
+use crate::i18n::{t, tf};
+use crate::secrets;
 use crate::stats::SentinelStats;
 use colored::*;
 use serde::{Deserialize, Serialize};
@@ -51,7 +55,7 @@ pub fn detectar_testing_framework(
     project_path: &Path,
     config: &SentinelConfig,
 ) -> anyhow::Result<TestingFrameworkInfo> {
-    println!("{}", "🧪 Detectando frameworks de testing...".cyan());
+    println!("{}", t("testing.detectando").cyan());
 
     // 1. Análisis estático rápido: detectar qué archivos de config/deps existen
     let analisis_estatico = analizar_archivos_proyecto(project_path);
@@ -150,73 +154,31 @@ fn consultar_ia_para_testing_dinamico(
 ) -> anyhow::Result<TestingFrameworkInfo> {
     let archivos_raiz = SentinelConfig::listar_archivos_raiz(project_path);
 
-    let prompt = format!(
-        "Eres un experto en Testing de Software y arquitectura. Analiza este proyecto y determina su estrategia de testing.\n\n\
-        CONTEXTO DEL PROYECTO:\n\
-        - Framework principal: {}\n\
-        - Lenguaje: {}\n\
-        - Gestor de paquetes: {}\n\
-        - Archivos raíz: {}\n\n\
-        ARCHIVOS DE CONFIGURACIÓN DE TESTING ENCONTRADOS:\n\
-        {}\n\n\
-        CONTENIDO DE ARCHIVOS DE DEPENDENCIAS:\n\
-        {}\n\n\
-        TAREAS:\n\
-        1. DETECTAR frameworks de testing instalados/configurados (analiza las dependencias)\n\
-        2. DETERMINAR el estado:\n\
-           - \"valid\": Configuración completa y funcional\n\
-           - \"incomplete\": Framework detectado pero falta configuración\n\
-           - \"missing\": No hay frameworks de testing\n\
-         3. Si falta testing o está incompleto, SUGERIR frameworks apropiados:\n\
-            - Considera el framework principal ({})\n\
-            - Prioriza estándares de la industria actuales (2024-2025)\n\
-            - Incluye frameworks para unit testing, integration testing y E2E\n\
-            - Genera comandos de instalación correctos para el gestor: {}\n\
-         4. PRIORIZA sugerencias: 1 (alta/recomendado), 2 (alternativa), 3 (adicional)\n\n\
-         IMPORTANTE SOBRE COMANDOS:\n\
-         - Para npm: 'npm install --save-dev <package>'\n\
-         - Para yarn: 'yarn add --dev <package>'\n\
-         - Para pnpm: 'pnpm add -D <package>'\n\
-         - Para pip: 'pip install <package>'\n\
-         - Para composer: 'composer require --dev <package>'\n\
-         - Para cargo: 'cargo add --dev <package>' o manual en Cargo.toml\n\
-         - Para go: 'go get <package>' o manual en go.mod\n\n\
-         RESPONDE SOLO CON JSON VÁLIDO:\n\
-         {{\n\
-           \"testing_framework\": \"Framework principal o null\",\n\
-           \"additional_frameworks\": [\"otros frameworks\"],\n\
-           \"config_files\": [\"archivos de config encontrados\"],\n\
-           \"status\": \"valid\"|\"incomplete\"|\"missing\",\n\
-           \"suggestions\": [\n\
-             {{\n\
-               \"framework\": \"nombre del framework\",\n\
-               \"reason\": \"por qué es apropiado para este proyecto\",\n\
-               \"install_command\": \"comando completo de instalación\",\n\
-               \"priority\": 1-3\n\
-             }}\n\
-           ]\n\
-         }}\n\n\
-         Responde ÚNICAMENTE con el JSON, sin explicaciones adicionales.",
-        config.framework,
-        config.code_language,
-        config.manager,
-        archivos_raiz.join(", "),
-        if analisis.archivos_config.is_empty() {
-            "Ninguno".to_string()
-        } else {
-            analisis.archivos_config.join(", ")
-        },
-        if analisis.contenido_deps.is_empty() {
-            "No se encontraron archivos de dependencias".to_string()
-        } else {
-            analisis.contenido_deps.clone()
-        },
-        config.framework,
-        config.manager
+    let archivos_config = if analisis.archivos_config.is_empty() {
+        "Ninguno".to_string()
+    } else {
+        analisis.archivos_config.join(", ")
+    };
+    let dependencias = if analisis.contenido_deps.is_empty() {
+        "No se encontraron archivos de dependencias".to_string()
+    } else {
+        delimitar("DEPENDENCIAS", &analisis.contenido_deps)
+    };
+    let prompt = prompts::renderizar(
+        "testing-detection",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("language", &config.code_language),
+            ("manager", &config.manager),
+            ("root_files", &archivos_raiz.join(", ")),
+            ("config_files", &archivos_config),
+            ("dependencies", &dependencias),
+        ],
     );
 
     let respuesta = consultar_ia(
-        prompt,
+        secrets::redactar_para_ia(&prompt, &config.secret_allowlist),
         config.ai_configs[0].clone(),
         Arc::new(Mutex::new(SentinelStats::default())),
     )?;
@@ -238,17 +200,23 @@ fn parsear_testing_info(respuesta: &str) -> anyhow::Result<TestingFrameworkInfo>
 
     match serde_json::from_str::<TestingFrameworkInfo>(json_str) {
         Ok(info) => {
-            println!("   ✅ Análisis de testing completado");
+            println!("{}", t("testing.analisis_completado"));
             Ok(info)
         }
         Err(e) => {
             println!(
-                "   ⚠️  Error al parsear respuesta: {}",
-                e.to_string().yellow()
+                "{}",
+                tf(
+                    "testing.error_parsear",
+                    &[("error", &e.to_string().yellow().to_string())]
+                )
             );
             println!(
-                "   Respuesta recibida: {}",
-                json_str.chars().take(200).collect::<String>()
+                "{}",
+                tf(
+                    "testing.respuesta_recibida",
+                    &[("respuesta", &json_str.chars().take(200).collect::<String>())]
+                )
             );
             // Fallback básico
             Ok(TestingFrameworkInfo {
@@ -270,52 +238,29 @@ pub fn obtener_sugerencias_complementarias(
     config: &SentinelConfig,
     testing_actual: &str,
 ) -> anyhow::Result<Vec<TestingSuggestion>> {
-    println!("\n{}", "🔍 Analizando frameworks complementarios...".cyan());
+    println!("\n{}", t("testing.analizando_complementarios").cyan());
 
     let analisis = analizar_archivos_proyecto(project_path);
 
-    let prompt = format!(
-        "Eres un experto en Testing de Software. El proyecto YA TIENE testing configurado.\n\n\
-        CONTEXTO DEL PROYECTO:\n\
-        - Framework principal: {}\n\
-        - Lenguaje: {}\n\
-        - Gestor de paquetes: {}\n\
-        - Testing actual: {}\n\n\
-        DEPENDENCIAS ACTUALES:\n\
-        {}\n\n\
-        TAREA:\n\
-        Sugiere frameworks de testing COMPLEMENTARIOS que añadan valor al stack actual:\n\
-        1. Si solo tiene unit testing (ej: Jest), sugiere E2E (Cypress, Playwright)\n\
-        2. Si falta coverage, sugiere herramientas de cobertura\n\
-        3. Si es backend, sugiere testing de integración o carga\n\
-        4. NO repitas frameworks que ya están instalados\n\
-        5. Prioriza por utilidad: 1 (muy recomendado), 2 (útil), 3 (opcional)\n\n\
-        RESPONDE SOLO CON JSON:\n\
-        {{\n\
-          \"suggestions\": [\n\
-            {{\n\
-              \"framework\": \"nombre\",\n\
-              \"reason\": \"qué añade al stack actual de testing\",\n\
-              \"install_command\": \"comando completo con gestor {}\",\n\
-              \"priority\": 1-3\n\
-            }}\n\
-          ]\n\
-        }}\n\n\
-        Si no hay sugerencias útiles, retorna array vacío. Responde SOLO JSON.",
-        config.framework,
-        config.code_language,
-        config.manager,
-        testing_actual,
-        if analisis.contenido_deps.is_empty() {
-            "No disponible".to_string()
-        } else {
-            analisis.contenido_deps
-        },
-        config.manager
+    let dependencias = if analisis.contenido_deps.is_empty() {
+        "No disponible".to_string()
+    } else {
+        delimitar("DEPENDENCIAS", &analisis.contenido_deps)
+    };
+    let prompt = prompts::renderizar(
+        "testing-suggestions",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("language", &config.code_language),
+            ("manager", &config.manager),
+            ("testing_framework", testing_actual),
+            ("dependencies", &dependencias),
+        ],
     );
 
     let respuesta = consultar_ia(
-        prompt,
+        secrets::redactar_para_ia(&prompt, &config.secret_allowlist),
         config.ai_configs[0].clone(),
         Arc::new(Mutex::new(SentinelStats::default())),
     )?;
@@ -338,11 +283,11 @@ pub fn obtener_sugerencias_complementarias(
 
     match serde_json::from_str::<SugerenciasComplementarias>(json_str) {
         Ok(result) => {
-            println!("   ✅ Análisis completado");
+            println!("{}", t("testing.completado"));
             Ok(result.suggestions)
         }
         Err(_) => {
-            println!("   ⚠️  No se encontraron sugerencias complementarias");
+            println!("{}", t("testing.sin_sugerencias"));
             Ok(vec![])
         }
     }
@@ -350,42 +295,63 @@ pub fn obtener_sugerencias_complementarias(
 
 /// Muestra un resumen colorido del análisis de testing
 fn mostrar_resumen_testing(info: &TestingFrameworkInfo) {
-    println!("\n{}", "═══ ANÁLISIS DE TESTING ═══".bold().cyan());
+    println!("\n{}", t("testing.resumen_titulo").bold().cyan());
 
     match info.status {
         TestingStatus::Valid => {
-            println!("   {} Testing configurado correctamente", "✅".green());
+            println!("   {} {}", "✅".green(), t("testing.configurado"));
             if let Some(main) = &info.testing_framework {
-                println!("   📦 Framework principal: {}", main.green().bold());
+                println!(
+                    "{}",
+                    tf(
+                        "testing.framework_principal",
+                        &[("framework", &main.green().bold().to_string())]
+                    )
+                );
             }
             if !info.additional_frameworks.is_empty() {
                 println!(
-                    "   🔧 Frameworks adicionales: {}",
-                    info.additional_frameworks.join(", ").cyan()
+                    "{}",
+                    tf(
+                        "testing.frameworks_adicionales",
+                        &[(
+                            "frameworks",
+                            &info.additional_frameworks.join(", ").cyan().to_string()
+                        )]
+                    )
                 );
             }
             if !info.config_files.is_empty() {
-                println!("   📄 Configuración encontrada:");
+                println!("{}", t("testing.config_encontrada"));
                 for file in &info.config_files {
                     println!("      • {}", file.yellow());
                 }
             }
         }
         TestingStatus::Incomplete => {
-            println!("   {} Configuración de testing incompleta", "⚠️".yellow());
+            println!("   {} {}", "⚠️".yellow(), t("testing.incompleto"));
             if let Some(main) = &info.testing_framework {
-                println!("   📦 Framework detectado: {}", main.yellow());
+                println!(
+                    "{}",
+                    tf(
+                        "testing.framework_detectado",
+                        &[("framework", &main.yellow().to_string())]
+                    )
+                );
             }
-            println!("   💡 Recomendación: Completar configuración o instalar herramientas");
+            println!("{}", t("testing.recomendacion_completar"));
         }
         TestingStatus::Missing => {
-            println!("   {} No se detectaron frameworks de testing", "❌".red());
-            println!("   💡 Se recomienda configurar testing para el proyecto");
+            println!("   {} {}", "❌".red(), t("testing.sin_frameworks"));
+            println!("{}", t("testing.recomendacion_configurar"));
         }
     }
 
     if !info.suggestions.is_empty() {
-        println!("\n   {}", "SUGERENCIAS DE INSTALACIÓN:".bold().yellow());
+        println!(
+            "\n   {}",
+            t("testing.sugerencias_instalacion").bold().yellow()
+        );
         for (i, suggestion) in info.suggestions.iter().enumerate() {
             let priority_icon = match suggestion.priority {
                 1 => "🔥",
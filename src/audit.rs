@@ -0,0 +1,860 @@
+//! # Auditoría de dependencias sin conexión
+//!
+//! `sentinel audit` lee los lockfiles del proyecto (npm, pnpm, yarn, Cargo, Go,
+//! Poetry/pip y Composer) y compara las versiones instaladas con una base de
+//! advisories en formato OSV guardada en disco. No usa la red: la base se
+//! descarga aparte (por ejemplo los `all.zip` de cada ecosistema publicados por
+//! OSV) y se descomprime en `.sentinel/osv`.
+//!
+//! Complementa la lectura de archivos de dependencias que hace
+//! `ai::testing` para la detección de frameworks, pero con versiones exactas.
+
+use crate::i18n::{t, tf};
+use colored::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directorio por defecto de la base OSV (relativo al proyecto)
+const DIRECTORIO_OSV: &str = ".sentinel/osv";
+
+/// Variable de entorno con una base OSV compartida entre proyectos
+const VARIABLE_OSV: &str = "SENTINEL_OSV_DB";
+
+/// Lector de un lockfile: contenido y origen → dependencias
+type LectorLockfile = fn(&str, &str) -> Vec<Dependencia>;
+
+/// Lockfiles soportados y su lector
+const LOCKFILES: &[(&str, LectorLockfile)] = &[
+    ("package-lock.json", parsear_package_lock),
+    ("pnpm-lock.yaml", parsear_pnpm_lock),
+    ("yarn.lock", parsear_yarn_lock),
+    ("Cargo.lock", parsear_cargo_lock),
+    ("go.sum", parsear_go_sum),
+    ("poetry.lock", parsear_poetry_lock),
+    ("requirements.txt", parsear_requirements),
+    ("composer.lock", parsear_composer_lock),
+];
+
+/// `true` si el nombre de archivo es un lockfile soportado
+pub fn es_lockfile(nombre: &str) -> bool {
+    LOCKFILES.iter().any(|(archivo, _)| *archivo == nombre)
+}
+
+/// Dependencia resuelta en un lockfile
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependencia {
+    /// Ecosistema con el nombre que usa OSV (`npm`, `crates.io`, `Go`, `PyPI`, `Packagist`)
+    pub ecosistema: &'static str,
+    pub nombre: String,
+    pub version: String,
+    /// Lockfile de origen, relativo al proyecto
+    pub origen: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severidad {
+    Desconocida,
+    Baja,
+    Media,
+    Alta,
+    Critica,
+}
+
+impl Severidad {
+    fn desde_texto(texto: &str) -> Self {
+        match texto.to_uppercase().as_str() {
+            "CRITICAL" => Self::Critica,
+            "HIGH" => Self::Alta,
+            "MODERATE" | "MEDIUM" => Self::Media,
+            "LOW" => Self::Baja,
+            _ => Self::Desconocida,
+        }
+    }
+
+    /// Rangos de CVSS v3 cuando el advisory solo trae una puntuación numérica
+    fn desde_puntuacion(puntuacion: f64) -> Self {
+        match puntuacion {
+            p if p >= 9.0 => Self::Critica,
+            p if p >= 7.0 => Self::Alta,
+            p if p >= 4.0 => Self::Media,
+            p if p > 0.0 => Self::Baja,
+            _ => Self::Desconocida,
+        }
+    }
+
+    fn etiqueta(&self) -> ColoredString {
+        match self {
+            Self::Critica => "CRÍTICA".red().bold(),
+            Self::Alta => "ALTA".red(),
+            Self::Media => "MEDIA".yellow(),
+            Self::Baja => "BAJA".green(),
+            Self::Desconocida => "DESCONOCIDA".dimmed(),
+        }
+    }
+}
+
+/// Advisory OSV reducido a lo que necesita la auditoría
+#[derive(Debug, Clone)]
+struct Advisory {
+    id: String,
+    resumen: String,
+    severidad: Severidad,
+    afectados: Vec<PaqueteAfectado>,
+}
+
+#[derive(Debug, Clone)]
+struct PaqueteAfectado {
+    ecosistema: String,
+    nombre: String,
+    versiones: Vec<String>,
+    /// Eventos de cada rango SEMVER/ECOSYSTEM en orden
+    rangos: Vec<Vec<Evento>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Evento {
+    Introducida(String),
+    Corregida(String),
+    UltimaAfectada(String),
+}
+
+/// Vulnerabilidad encontrada en una dependencia
+#[derive(Debug, Clone)]
+pub struct Vulnerabilidad {
+    pub dependencia: Dependencia,
+    pub id: String,
+    pub resumen: String,
+    pub severidad: Severidad,
+    pub corregida_en: Vec<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Lockfiles
+// ---------------------------------------------------------------------------
+
+/// Lee todas las dependencias de los lockfiles presentes en `dir`
+///
+/// `prefijo` se antepone al nombre del lockfile en `origen` (paquetes de workspace).
+pub fn leer_lockfiles(dir: &Path, prefijo: &str) -> Vec<Dependencia> {
+    let mut dependencias = Vec::new();
+    for (archivo, lector) in LOCKFILES {
+        if let Ok(contenido) = fs::read_to_string(dir.join(archivo)) {
+            let origen = format!("{}{}", prefijo, archivo);
+            dependencias.extend(lector(&contenido, &origen));
+        }
+    }
+    dependencias.sort_by(|a, b| {
+        (a.ecosistema, &a.nombre, &a.version).cmp(&(b.ecosistema, &b.nombre, &b.version))
+    });
+    dependencias.dedup_by(|a, b| {
+        a.ecosistema == b.ecosistema && a.nombre == b.nombre && a.version == b.version
+    });
+    dependencias
+}
+
+fn dependencia(ecosistema: &'static str, nombre: &str, version: &str, origen: &str) -> Dependencia {
+    Dependencia {
+        ecosistema,
+        nombre: nombre.to_string(),
+        version: version.to_string(),
+        origen: origen.to_string(),
+    }
+}
+
+/// `package-lock.json` v1 (`dependencies` anidadas) y v2/v3 (`packages`)
+fn parsear_package_lock(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(contenido) else {
+        return vec![];
+    };
+    let mut resultado = Vec::new();
+
+    if let Some(paquetes) = json["packages"].as_object() {
+        for (ruta, datos) in paquetes {
+            // La raíz ("") y los paquetes del workspace son el propio proyecto;
+            // sus enlaces en node_modules tampoco tienen versión publicada
+            let Some(nombre) = ruta
+                .rsplit("node_modules/")
+                .next()
+                .filter(|_| ruta.contains("node_modules/"))
+            else {
+                continue;
+            };
+            if datos["link"].as_bool() == Some(true) {
+                continue;
+            }
+            if let Some(version) = datos["version"].as_str() {
+                resultado.push(dependencia("npm", nombre, version, origen));
+            }
+        }
+    } else if let Some(dependencias) = json["dependencies"].as_object() {
+        fn recorrer(
+            mapa: &serde_json::Map<String, serde_json::Value>,
+            origen: &str,
+            resultado: &mut Vec<Dependencia>,
+        ) {
+            for (nombre, datos) in mapa {
+                if let Some(version) = datos["version"].as_str() {
+                    resultado.push(dependencia("npm", nombre, version, origen));
+                }
+                if let Some(anidadas) = datos["dependencies"].as_object() {
+                    recorrer(anidadas, origen, resultado);
+                }
+            }
+        }
+        recorrer(dependencias, origen, &mut resultado);
+    }
+    resultado
+}
+
+/// Separa `nombre@version` respetando los scopes (`@scope/nombre@1.0.0`)
+fn separar_arroba(especificacion: &str) -> Option<(&str, &str)> {
+    let pos = especificacion.get(1..)?.rfind('@')? + 1;
+    Some((&especificacion[..pos], &especificacion[pos + 1..]))
+}
+
+/// `pnpm-lock.yaml`: claves de la sección `packages:` en los formatos
+/// v5 (`/nombre/1.0.0`), v6 (`/nombre@1.0.0`) y v9 (`nombre@1.0.0`)
+fn parsear_pnpm_lock(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    let mut resultado = Vec::new();
+    let mut en_paquetes = false;
+
+    for linea in contenido.lines() {
+        if !linea.starts_with(' ') && !linea.is_empty() {
+            en_paquetes = linea.trim_end() == "packages:";
+            continue;
+        }
+        let Some(clave) = linea
+            .strip_prefix("  ")
+            .filter(|c| !c.starts_with(' '))
+            .and_then(|c| c.trim_end().strip_suffix(':'))
+        else {
+            continue;
+        };
+        if !en_paquetes {
+            continue;
+        }
+
+        let clave = clave.trim_matches(['\'', '"']);
+        // Quitar sufijos de peers: `(react@18.2.0)` en v6+ y `_react@18.2.0` en v5
+        let clave = clave.split('(').next().unwrap_or(clave);
+        let sin_barra = clave.strip_prefix('/').unwrap_or(clave);
+
+        let par = match separar_arroba(sin_barra) {
+            Some(par) if !par.1.contains('/') => Some(par),
+            _ => sin_barra.rsplit_once('/'),
+        };
+        if let Some((nombre, version)) = par {
+            let version = version.split('_').next().unwrap_or(version);
+            if !nombre.is_empty() && version.starts_with(|c: char| c.is_ascii_digit()) {
+                resultado.push(dependencia("npm", nombre, version, origen));
+            }
+        }
+    }
+    resultado
+}
+
+/// `yarn.lock` v1 (`version "1.0.0"`) y Berry (`version: 1.0.0`)
+fn parsear_yarn_lock(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    let mut resultado = Vec::new();
+    let mut nombre_actual: Option<String> = None;
+
+    for linea in contenido.lines() {
+        if !linea.starts_with(' ') && linea.trim_end().ends_with(':') && !linea.starts_with('#') {
+            // Cabecera: `"lodash@^4.17.0", lodash@^4.17.21:` o `"lodash@npm:^4.17.21":`
+            let primera = linea
+                .trim_end_matches(':')
+                .split(',')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .trim_matches('"');
+            nombre_actual = separar_arroba(primera).map(|(n, _)| n.to_string());
+        } else if let Some(resto) = linea.trim_start().strip_prefix("version")
+            && linea.starts_with("  ")
+            && !linea.starts_with("   ")
+            && let Some(nombre) = nombre_actual.take()
+        {
+            let version = resto.trim_start_matches(':').trim().trim_matches('"');
+            if !version.is_empty() && !version.starts_with("0.0.0-use.local") {
+                resultado.push(dependencia("npm", &nombre, version, origen));
+            }
+        }
+    }
+    resultado
+}
+
+/// `[[package]]` con `name` y `version` (Cargo.lock y poetry.lock)
+fn paquetes_toml(contenido: &str) -> Vec<(String, String)> {
+    let Ok(valor) = contenido.parse::<toml::Value>() else {
+        return vec![];
+    };
+    valor
+        .get("package")
+        .and_then(|p| p.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|p| {
+            Some((
+                p.get("name")?.as_str()?.to_string(),
+                p.get("version")?.as_str()?.to_string(),
+            ))
+        })
+        .collect()
+}
+
+fn parsear_cargo_lock(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    paquetes_toml(contenido)
+        .iter()
+        .map(|(n, v)| dependencia("crates.io", n, v, origen))
+        .collect()
+}
+
+fn parsear_poetry_lock(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    paquetes_toml(contenido)
+        .iter()
+        .map(|(n, v)| dependencia("PyPI", n, v, origen))
+        .collect()
+}
+
+/// `go.sum`: `modulo v1.2.3 h1:...` y `modulo v1.2.3/go.mod h1:...`
+fn parsear_go_sum(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    contenido
+        .lines()
+        .filter_map(|linea| {
+            let mut campos = linea.split_whitespace();
+            let modulo = campos.next()?;
+            let version = campos.next()?.trim_end_matches("/go.mod");
+            let version = version
+                .trim_start_matches('v')
+                .trim_end_matches("+incompatible");
+            Some(dependencia("Go", modulo, version, origen))
+        })
+        .collect()
+}
+
+/// `requirements.txt`: solo versiones fijadas con `==`
+fn parsear_requirements(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    contenido
+        .lines()
+        .filter_map(|linea| {
+            let linea = linea.split('#').next()?.split(';').next()?.trim();
+            let (nombre, version) = linea.split_once("==")?;
+            let nombre = nombre.split('[').next()?.trim();
+            let version = version.split(',').next()?.trim();
+            (!nombre.is_empty() && !version.is_empty())
+                .then(|| dependencia("PyPI", nombre, version, origen))
+        })
+        .collect()
+}
+
+/// `composer.lock`: `packages` y `packages-dev`
+fn parsear_composer_lock(contenido: &str, origen: &str) -> Vec<Dependencia> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(contenido) else {
+        return vec![];
+    };
+    ["packages", "packages-dev"]
+        .iter()
+        .flat_map(|seccion| json[seccion].as_array().cloned().unwrap_or_default())
+        .filter_map(|p| {
+            let nombre = p["name"].as_str()?;
+            let version = p["version"].as_str()?.trim_start_matches('v');
+            Some(dependencia("Packagist", nombre, version, origen))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Base OSV
+// ---------------------------------------------------------------------------
+
+/// Nombre normalizado para comparar (PyPI no distingue `-`, `_`, `.` ni mayúsculas)
+fn normalizar_nombre(ecosistema: &str, nombre: &str) -> String {
+    if ecosistema == "PyPI" {
+        nombre.to_lowercase().replace(['_', '.'], "-")
+    } else {
+        nombre.to_string()
+    }
+}
+
+fn parsear_advisory(json: &serde_json::Value) -> Option<Advisory> {
+    let id = json["id"].as_str()?.to_string();
+    let resumen = json["summary"]
+        .as_str()
+        .or_else(|| json["details"].as_str())
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let severidad = json["database_specific"]["severity"]
+        .as_str()
+        .map(Severidad::desde_texto)
+        .filter(|s| *s != Severidad::Desconocida)
+        .or_else(|| {
+            json["severity"]
+                .as_array()?
+                .iter()
+                .filter_map(|s| s["score"].as_str()?.parse::<f64>().ok())
+                .map(Severidad::desde_puntuacion)
+                .max()
+        })
+        .unwrap_or(Severidad::Desconocida);
+
+    let afectados = json["affected"]
+        .as_array()?
+        .iter()
+        .filter_map(|a| {
+            let ecosistema = a["package"]["ecosystem"].as_str()?.to_string();
+            let nombre = a["package"]["name"].as_str()?.to_string();
+            let versiones = a["versions"]
+                .as_array()
+                .map(|v| {
+                    v.iter()
+                        .filter_map(|x| x.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let rangos = a["ranges"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|r| matches!(r["type"].as_str(), Some("SEMVER" | "ECOSYSTEM")))
+                .map(|r| {
+                    r["events"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|e| {
+                            let texto = |campo: &str| e[campo].as_str().map(String::from);
+                            texto("introduced")
+                                .map(Evento::Introducida)
+                                .or_else(|| texto("fixed").map(Evento::Corregida))
+                                .or_else(|| texto("last_affected").map(Evento::UltimaAfectada))
+                        })
+                        .collect()
+                })
+                .collect();
+            Some(PaqueteAfectado {
+                ecosistema,
+                nombre,
+                versiones,
+                rangos,
+            })
+        })
+        .collect();
+
+    Some(Advisory {
+        id,
+        resumen,
+        severidad,
+        afectados,
+    })
+}
+
+/// Carga recursivamente los `.json` de la base OSV, indexados por ecosistema y paquete
+fn cargar_base(dir: &Path) -> HashMap<(String, String), Vec<Advisory>> {
+    fn recorrer(dir: &Path, indice: &mut HashMap<(String, String), Vec<Advisory>>) {
+        let Ok(entradas) = fs::read_dir(dir) else {
+            return;
+        };
+        for entrada in entradas.flatten() {
+            let ruta = entrada.path();
+            if ruta.is_dir() {
+                recorrer(&ruta, indice);
+                continue;
+            }
+            if ruta.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(advisory) = fs::read_to_string(&ruta)
+                .ok()
+                .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+                .and_then(|j| parsear_advisory(&j))
+            else {
+                continue;
+            };
+            for afectado in &advisory.afectados {
+                let clave = (
+                    afectado.ecosistema.clone(),
+                    normalizar_nombre(&afectado.ecosistema, &afectado.nombre),
+                );
+                let lista = indice.entry(clave).or_default();
+                if !lista.iter().any(|a| a.id == advisory.id) {
+                    lista.push(advisory.clone());
+                }
+            }
+        }
+    }
+
+    let mut indice = HashMap::new();
+    recorrer(dir, &mut indice);
+    indice
+}
+
+// ---------------------------------------------------------------------------
+// Versiones
+// ---------------------------------------------------------------------------
+
+/// Compara versiones estilo semver (`v` inicial, prerelease y metadatos de build)
+///
+/// Es una aproximación que también sirve para PyPI y Packagist en los casos
+/// habituales: segmentos numéricos comparados como números y una versión con
+/// prerelease (`-beta`, `rc1`) anterior a la versión final.
+fn comparar_versiones(a: &str, b: &str) -> Ordering {
+    fn partes(version: &str) -> (Vec<u64>, Option<String>) {
+        let version = version.trim().trim_start_matches('v');
+        let version = version.split('+').next().unwrap_or(version);
+        // El prerelease empieza en el primer carácter que no es dígito ni punto
+        let corte = version
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(version.len());
+        let numeros = version[..corte]
+            .split('.')
+            .filter(|p| !p.is_empty())
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let pre = version[corte..].trim_start_matches(['-', '.']);
+        (numeros, (!pre.is_empty()).then(|| pre.to_string()))
+    }
+
+    let (num_a, pre_a) = partes(a);
+    let (num_b, pre_b) = partes(b);
+    let largo = num_a.len().max(num_b.len());
+    for i in 0..largo {
+        let orden = num_a.get(i).unwrap_or(&0).cmp(num_b.get(i).unwrap_or(&0));
+        if orden != Ordering::Equal {
+            return orden;
+        }
+    }
+    match (pre_a, pre_b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => x.cmp(&y),
+    }
+}
+
+/// Indica si una versión cae dentro de un rango OSV
+///
+/// Los eventos se evalúan en orden de versión: el último evento que no supera
+/// la versión decide si está afectada.
+fn en_rango(version: &str, eventos: &[Evento]) -> bool {
+    let mut ordenados: Vec<&Evento> = eventos.iter().collect();
+    let valor = |e: &Evento| match e {
+        Evento::Introducida(v) | Evento::Corregida(v) | Evento::UltimaAfectada(v) => v.clone(),
+    };
+    ordenados.sort_by(|a, b| comparar_versiones(&valor(a), &valor(b)));
+
+    let mut afectada = false;
+    for evento in ordenados {
+        match evento {
+            Evento::Introducida(v)
+                if v == "0" || comparar_versiones(v, version) != Ordering::Greater =>
+            {
+                afectada = true
+            }
+            Evento::Corregida(v) if comparar_versiones(v, version) != Ordering::Greater => {
+                afectada = false
+            }
+            Evento::UltimaAfectada(v) if comparar_versiones(v, version) == Ordering::Less => {
+                afectada = false
+            }
+            _ => {}
+        }
+    }
+    afectada
+}
+
+/// Busca vulnerabilidades para una lista de dependencias
+fn auditar(
+    dependencias: &[Dependencia],
+    base: &HashMap<(String, String), Vec<Advisory>>,
+) -> Vec<Vulnerabilidad> {
+    let mut resultado = Vec::new();
+    for dep in dependencias {
+        let clave = (
+            dep.ecosistema.to_string(),
+            normalizar_nombre(dep.ecosistema, &dep.nombre),
+        );
+        for advisory in base.get(&clave).into_iter().flatten() {
+            let afectados: Vec<&PaqueteAfectado> = advisory
+                .afectados
+                .iter()
+                .filter(|a| {
+                    a.ecosistema == dep.ecosistema
+                        && normalizar_nombre(&a.ecosistema, &a.nombre) == clave.1
+                })
+                .collect();
+            let vulnerable = afectados.iter().any(|a| {
+                a.versiones.iter().any(|v| v == &dep.version)
+                    || a.rangos.iter().any(|r| en_rango(&dep.version, r))
+            });
+            if !vulnerable {
+                continue;
+            }
+
+            let mut corregida_en: Vec<String> = afectados
+                .iter()
+                .flat_map(|a| a.rangos.iter().flatten())
+                .filter_map(|e| match e {
+                    Evento::Corregida(v) => Some(v.clone()),
+                    _ => None,
+                })
+                .collect();
+            corregida_en.sort_by(|a, b| comparar_versiones(a, b));
+            corregida_en.dedup();
+
+            resultado.push(Vulnerabilidad {
+                dependencia: dep.clone(),
+                id: advisory.id.clone(),
+                resumen: advisory.resumen.clone(),
+                severidad: advisory.severidad,
+                corregida_en,
+            });
+        }
+    }
+    resultado.sort_by(|a, b| {
+        b.severidad
+            .cmp(&a.severidad)
+            .then(a.dependencia.nombre.cmp(&b.dependencia.nombre))
+    });
+    resultado
+}
+
+// ---------------------------------------------------------------------------
+// Comando
+// ---------------------------------------------------------------------------
+
+/// Ruta de la base OSV: `--db`, luego `SENTINEL_OSV_DB`, luego `.sentinel/osv`
+fn resolver_base(project_path: &Path, db: Option<&Path>) -> PathBuf {
+    db.map(Path::to_path_buf)
+        .or_else(|| std::env::var_os(VARIABLE_OSV).map(PathBuf::from))
+        .unwrap_or_else(|| project_path.join(DIRECTORIO_OSV))
+}
+
+/// Ejecuta `sentinel audit` e imprime el reporte
+///
+/// Se auditan los lockfiles de la raíz y de cada paquete del workspace
+/// declarado en `.sentinelrc.toml`.
+///
+/// # Retorna
+/// Código de salida: 0 sin vulnerabilidades, 1 si hay alguna, 2 si falta la base.
+pub fn ejecutar(project_path: &Path, db: Option<&Path>) -> i32 {
+    let base_dir = resolver_base(project_path, db);
+    if !base_dir.is_dir() {
+        eprintln!(
+            "{}",
+            tf(
+                "audit.sin_base",
+                &[("ruta", &base_dir.display().to_string())]
+            )
+            .red()
+            .bold()
+        );
+        eprintln!(
+            "{}",
+            tf(
+                "audit.como_descargar",
+                &[("directorio", DIRECTORIO_OSV), ("variable", VARIABLE_OSV)]
+            )
+        );
+        return 2;
+    }
+
+    let mut dependencias = leer_lockfiles(project_path, "");
+    if let Some(config) = crate::config::SentinelConfig::load(project_path) {
+        for paquete in &config.packages {
+            let prefijo = format!("{}/", paquete.path.trim_end_matches('/'));
+            dependencias.extend(leer_lockfiles(&project_path.join(&paquete.path), &prefijo));
+        }
+    }
+    if dependencias.is_empty() {
+        println!("{}", t("audit.sin_lockfiles").yellow());
+        return 0;
+    }
+
+    println!(
+        "{}",
+        tf(
+            "audit.auditando",
+            &[
+                ("n", &dependencias.len().to_string()),
+                ("ruta", &base_dir.display().to_string())
+            ]
+        )
+        .bright_cyan()
+    );
+    let base = cargar_base(&base_dir);
+    let vulnerabilidades = auditar(&dependencias, &base);
+
+    if vulnerabilidades.is_empty() {
+        println!("{}", t("audit.sin_vulnerabilidades").green().bold());
+        return 0;
+    }
+
+    println!();
+    for v in &vulnerabilidades {
+        println!(
+            "{} {} {} {}",
+            v.severidad.etiqueta(),
+            format!("{}@{}", v.dependencia.nombre, v.dependencia.version).bold(),
+            format!("({}, {})", v.dependencia.ecosistema, v.dependencia.origen).dimmed(),
+            v.id.cyan()
+        );
+        if !v.resumen.is_empty() {
+            println!("   {}", v.resumen);
+        }
+        if v.corregida_en.is_empty() {
+            println!("   {}", t("audit.sin_correccion").yellow());
+        } else {
+            println!(
+                "   {}",
+                tf(
+                    "audit.corregida_en",
+                    &[("versiones", &v.corregida_en.join(", ").green().to_string())]
+                )
+            );
+        }
+    }
+
+    let paquetes: std::collections::HashSet<_> = vulnerabilidades
+        .iter()
+        .map(|v| (&v.dependencia.nombre, &v.dependencia.version))
+        .collect();
+    println!(
+        "\n{}",
+        tf(
+            "audit.resumen",
+            &[
+                ("vulnerabilidades", &vulnerabilidades.len().to_string()),
+                ("paquetes", &paquetes.len().to_string()),
+                ("total", &dependencias.len().to_string())
+            ]
+        )
+        .red()
+        .bold()
+    );
+    1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn nombres(deps: &[Dependencia]) -> Vec<String> {
+        deps.iter()
+            .map(|d| format!("{}@{}", d.nombre, d.version))
+            .collect()
+    }
+
+    #[test]
+    fn test_parsear_lockfiles_js() {
+        let npm = r#"{"lockfileVersion": 3, "packages": {
+            "": {"name": "app"},
+            "node_modules/lodash": {"version": "4.17.20"},
+            "node_modules/a/node_modules/@babel/core": {"version": "7.0.0"},
+            "node_modules/mi-lib": {"resolved": "packages/mi-lib", "link": true}
+        }}"#;
+        assert_eq!(
+            nombres(&parsear_package_lock(npm, "package-lock.json")),
+            vec!["@babel/core@7.0.0", "lodash@4.17.20"]
+        );
+
+        let pnpm = "lockfileVersion: '9.0'\n\nimporters:\n  .:\n    dependencies: {}\n\npackages:\n\n  '@babel/core@7.24.0':\n    resolution: {}\n  lodash@4.17.21:\n    resolution: {}\n  /react-dom@18.2.0(react@18.2.0):\n    resolution: {}\n  /minimist/1.2.5:\n    resolution: {}\n";
+        assert_eq!(
+            nombres(&parsear_pnpm_lock(pnpm, "pnpm-lock.yaml")),
+            vec![
+                "@babel/core@7.24.0",
+                "lodash@4.17.21",
+                "react-dom@18.2.0",
+                "minimist@1.2.5"
+            ]
+        );
+
+        let yarn = "# yarn lockfile v1\n\n\"@types/node@^20.0.0\", \"@types/node@^20.1.0\":\n  version \"20.11.5\"\n  resolved \"https://...\"\n\nlodash@npm:^4.17.21:\n  version: 4.17.21\n  dependencies:\n    foo: 1.0.0\n";
+        assert_eq!(
+            nombres(&parsear_yarn_lock(yarn, "yarn.lock")),
+            vec!["@types/node@20.11.5", "lodash@4.17.21"]
+        );
+    }
+
+    #[test]
+    fn test_parsear_lockfiles_otros() {
+        let cargo = "[[package]]\nname = \"serde\"\nversion = \"1.0.100\"\n";
+        assert_eq!(
+            nombres(&parsear_cargo_lock(cargo, "Cargo.lock")),
+            vec!["serde@1.0.100"]
+        );
+
+        let go = "golang.org/x/net v0.7.0 h1:abc=\ngolang.org/x/net v0.7.0/go.mod h1:def=\n";
+        assert_eq!(
+            nombres(&parsear_go_sum(go, "go.sum")),
+            vec!["golang.org/x/net@0.7.0", "golang.org/x/net@0.7.0"]
+        );
+
+        let req =
+            "# deps\nDjango==3.2.0\nrequests[socks]==2.25.0 ; python_version > '3'\nflask>=2.0\n";
+        assert_eq!(
+            nombres(&parsear_requirements(req, "requirements.txt")),
+            vec!["Django@3.2.0", "requests@2.25.0"]
+        );
+
+        let composer = r#"{"packages": [{"name": "guzzlehttp/guzzle", "version": "v7.4.0"}], "packages-dev": []}"#;
+        assert_eq!(
+            nombres(&parsear_composer_lock(composer, "composer.lock")),
+            vec!["guzzlehttp/guzzle@7.4.0"]
+        );
+    }
+
+    #[test]
+    fn test_rangos_y_versiones() {
+        assert_eq!(comparar_versiones("1.10.0", "1.9.9"), Ordering::Greater);
+        assert_eq!(comparar_versiones("v2.0.0", "2.0"), Ordering::Equal);
+        assert_eq!(comparar_versiones("1.0.0-beta.1", "1.0.0"), Ordering::Less);
+
+        let eventos = vec![
+            Evento::Introducida("0".to_string()),
+            Evento::Corregida("4.17.21".to_string()),
+        ];
+        assert!(en_rango("4.17.20", &eventos));
+        assert!(!en_rango("4.17.21", &eventos));
+
+        let ultima = vec![
+            Evento::Introducida("1.0.0".to_string()),
+            Evento::UltimaAfectada("1.2.0".to_string()),
+        ];
+        assert!(!en_rango("0.9.0", &ultima));
+        assert!(en_rango("1.2.0", &ultima));
+        assert!(!en_rango("1.2.1", &ultima));
+    }
+
+    #[test]
+    fn test_auditar_con_base_osv() {
+        let temp_dir = TempDir::new().unwrap();
+        let osv = temp_dir.path().join("npm");
+        fs::create_dir_all(&osv).unwrap();
+        fs::write(
+            osv.join("GHSA-35jh-r3h4-6jhm.json"),
+            r#"{"id": "GHSA-35jh-r3h4-6jhm", "summary": "Command Injection in lodash",
+                "database_specific": {"severity": "HIGH"},
+                "affected": [{"package": {"ecosystem": "npm", "name": "lodash"},
+                    "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}, {"fixed": "4.17.21"}]}]}]}"#,
+        )
+        .unwrap();
+
+        let base = cargar_base(temp_dir.path());
+        let deps = vec![
+            dependencia("npm", "lodash", "4.17.20", "package-lock.json"),
+            dependencia("npm", "lodash", "4.17.21", "package-lock.json"),
+        ];
+        let vulnerabilidades = auditar(&deps, &base);
+        assert_eq!(vulnerabilidades.len(), 1);
+        assert_eq!(vulnerabilidades[0].dependencia.version, "4.17.20");
+        assert_eq!(vulnerabilidades[0].severidad, Severidad::Alta);
+        assert_eq!(vulnerabilidades[0].corregida_en, vec!["4.17.21"]);
+    }
+}
@@ -0,0 +1,559 @@
+//! # CHANGELOG a partir de Conventional Commits
+//!
+//! `sentinel changelog` lee los commits entre dos refs (por defecto desde el
+//! último tag hasta `HEAD`), los agrupa en las secciones de
+//! [Keep a Changelog](https://keepachangelog.com) según su tipo y scope, marca
+//! los cambios incompatibles (`!` o el pie `BREAKING CHANGE:`) y antepone una
+//! nueva versión al `CHANGELOG.md` del proyecto.
+//!
+//! Los tipos sin impacto para el usuario (`docs`, `test`, `chore`, `ci`,
+//! `build`, `style`) se omiten salvo que rompan compatibilidad, y los commits
+//! que no siguen Conventional Commits solo se cuentan. Con `--ai` la IA reescribe
+//! las descripciones; la estructura y la clasificación las decide Sentinel.
+
+use crate::ai;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::config::SentinelConfig;
+use crate::docs::fecha_iso8601;
+use crate::i18n::{self, t, tf};
+use crate::stats::SentinelStats;
+use colored::*;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Archivo de changelog (relativo a la raíz)
+const ARCHIVO_CHANGELOG: &str = "CHANGELOG.md";
+
+/// Encabezado de un `CHANGELOG.md` nuevo
+const ENCABEZADO: &str = "# Changelog\n\n\
+All notable changes to this project will be documented in this file.\n\n\
+The format is based on [Keep a Changelog](https://keepachangelog.com/en/1.1.0/),\n\
+and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0.html).\n";
+
+/// Opciones de `sentinel changelog`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpcionesChangelog {
+    /// Ref inicial (exclusiva); sin definir, el último tag
+    pub desde: Option<String>,
+    /// Ref final (inclusiva); sin definir, `HEAD`
+    pub hasta: Option<String>,
+    /// Versión de la nueva sección; sin definir se calcula desde el último tag
+    pub version: Option<String>,
+    /// Pulir las descripciones con IA
+    pub ia: bool,
+    /// Mostrar la sección sin escribir el archivo
+    pub dry_run: bool,
+}
+
+/// Secciones de Keep a Changelog, en el orden en que se escriben
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Seccion {
+    Added,
+    Changed,
+    Deprecated,
+    Removed,
+    Fixed,
+    Security,
+}
+
+impl Seccion {
+    const TODAS: [Seccion; 6] = [
+        Seccion::Added,
+        Seccion::Changed,
+        Seccion::Deprecated,
+        Seccion::Removed,
+        Seccion::Fixed,
+        Seccion::Security,
+    ];
+
+    /// Nombre canónico de Keep a Changelog; no se traduce para que el archivo
+    /// sea legible por las herramientas que lo procesan
+    fn titulo(&self) -> &'static str {
+        match self {
+            Seccion::Added => "Added",
+            Seccion::Changed => "Changed",
+            Seccion::Deprecated => "Deprecated",
+            Seccion::Removed => "Removed",
+            Seccion::Fixed => "Fixed",
+            Seccion::Security => "Security",
+        }
+    }
+}
+
+/// Encabezado de un commit según Conventional Commits
+#[derive(Debug, PartialEq)]
+struct Convencional {
+    tipo: String,
+    scope: Option<String>,
+    rompe: bool,
+    descripcion: String,
+}
+
+/// Entrada de la nueva versión
+#[derive(Debug, PartialEq)]
+struct Entrada {
+    seccion: Seccion,
+    scope: Option<String>,
+    descripcion: String,
+    hash: String,
+    rompe: bool,
+    /// Texto del pie `BREAKING CHANGE:`
+    nota_ruptura: Option<String>,
+}
+
+/// `tipo(scope)!: descripción`
+fn parsear_convencional(asunto: &str) -> Option<Convencional> {
+    let (encabezado, descripcion) = asunto.split_once(':')?;
+    let descripcion = descripcion.trim();
+    let (encabezado, rompe) = match encabezado.strip_suffix('!') {
+        Some(e) => (e, true),
+        None => (encabezado, false),
+    };
+    let (tipo, scope) = match encabezado.split_once('(') {
+        Some((tipo, resto)) => (tipo, Some(resto.strip_suffix(')')?.trim().to_string())),
+        None => (encabezado, None),
+    };
+    if tipo.is_empty() || !tipo.chars().all(|c| c.is_ascii_alphabetic()) || descripcion.is_empty() {
+        return None;
+    }
+    Some(Convencional {
+        tipo: tipo.to_lowercase(),
+        scope: scope.filter(|s| !s.is_empty()),
+        rompe,
+        descripcion: descripcion.to_string(),
+    })
+}
+
+/// Texto del pie `BREAKING CHANGE:` (o `BREAKING-CHANGE:`) del cuerpo
+fn nota_ruptura(cuerpo: &str) -> Option<String> {
+    let inicio = ["BREAKING CHANGE:", "BREAKING-CHANGE:"]
+        .iter()
+        .find_map(|p| cuerpo.find(p).map(|i| i + p.len()))?;
+    // El pie llega hasta la línea en blanco siguiente
+    let nota = cuerpo[inicio..]
+        .split("\n\n")
+        .next()
+        .unwrap_or("")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(nota)
+}
+
+/// Sección de un commit; `None` si no es relevante para el usuario
+fn clasificar(commit: &Convencional, rompe: bool) -> Option<Seccion> {
+    let descripcion = commit.descripcion.to_lowercase();
+    let scope = commit.scope.as_deref().unwrap_or("");
+    let seccion = match commit.tipo.as_str() {
+        "security" | "sec" => Seccion::Security,
+        "fix" if scope == "security" || scope == "seguridad" => Seccion::Security,
+        "fix" => Seccion::Fixed,
+        "feat" => Seccion::Added,
+        "perf" | "refactor" | "revert" => Seccion::Changed,
+        _ if rompe => Seccion::Changed,
+        _ => return None,
+    };
+    if seccion == Seccion::Security || seccion == Seccion::Fixed {
+        return Some(seccion);
+    }
+    if ["remove", "drop", "elimina", "quita"]
+        .iter()
+        .any(|p| descripcion.starts_with(p))
+    {
+        Some(Seccion::Removed)
+    } else if ["deprecate", "depreca", "marca como obsolet"]
+        .iter()
+        .any(|p| descripcion.starts_with(p))
+    {
+        Some(Seccion::Deprecated)
+    } else {
+        Some(seccion)
+    }
+}
+
+/// Interpreta la salida de `git log` con el formato `%x1e%h%x1f%s%x1f%b`
+///
+/// # Retorna
+/// Las entradas (de la más antigua a la más reciente) y la cantidad de
+/// commits que no siguen Conventional Commits.
+fn interpretar_log(salida: &str) -> (Vec<Entrada>, usize) {
+    let mut entradas = Vec::new();
+    let mut no_convencionales = 0;
+    for bloque in salida.split('\x1e').filter(|b| !b.trim().is_empty()) {
+        let mut campos = bloque.splitn(3, '\x1f');
+        let (Some(hash), Some(asunto)) = (campos.next(), campos.next()) else {
+            continue;
+        };
+        let cuerpo = campos.next().unwrap_or("");
+        let Some(commit) = parsear_convencional(asunto.trim()) else {
+            no_convencionales += 1;
+            continue;
+        };
+        let nota = nota_ruptura(cuerpo);
+        let rompe = commit.rompe || nota.is_some();
+        if let Some(seccion) = clasificar(&commit, rompe) {
+            entradas.push(Entrada {
+                seccion,
+                scope: commit.scope,
+                descripcion: commit.descripcion,
+                hash: hash.trim().to_string(),
+                rompe,
+                nota_ruptura: nota.filter(|n| !n.is_empty()),
+            });
+        }
+    }
+    // git log lista primero los más recientes
+    entradas.reverse();
+    (entradas, no_convencionales)
+}
+
+/// Siguiente versión según SemVer a partir del último tag
+///
+/// Un cambio incompatible sube la mayor (la menor en `0.x`), una
+/// funcionalidad nueva la menor y el resto el parche. Retorna `None` si el tag
+/// no es una versión `X.Y.Z` (con o sin `v`).
+fn siguiente_version(tag: &str, entradas: &[Entrada]) -> Option<String> {
+    let numeros: Vec<u64> = tag
+        .trim_start_matches(['v', 'V'])
+        .split(['-', '+'])
+        .next()?
+        .split('.')
+        .map(|n| n.parse().ok())
+        .collect::<Option<_>>()?;
+    let [mayor, menor, parche] = numeros[..] else {
+        return None;
+    };
+    let rompe = entradas.iter().any(|e| e.rompe);
+    let agrega = entradas.iter().any(|e| e.seccion == Seccion::Added);
+    Some(match (rompe, agrega) {
+        (true, _) if mayor > 0 => format!("{}.0.0", mayor + 1),
+        (true, _) | (false, true) => format!("{}.{}.0", mayor, menor + 1),
+        _ => format!("{}.{}.{}", mayor, menor, parche + 1),
+    })
+}
+
+/// Markdown de la nueva versión: secciones en orden y, dentro, agrupado por scope
+fn componer_seccion(version: &str, fecha: &str, entradas: &[Entrada]) -> String {
+    let encabezado = if version.eq_ignore_ascii_case("unreleased") {
+        "## [Unreleased]".to_string()
+    } else {
+        format!("## [{}] - {}", version, fecha)
+    };
+    let mut md = format!("{}\n", encabezado);
+    for seccion in Seccion::TODAS {
+        let mut de_seccion: Vec<&Entrada> =
+            entradas.iter().filter(|e| e.seccion == seccion).collect();
+        if de_seccion.is_empty() {
+            continue;
+        }
+        // Estable: conserva el orden cronológico dentro de cada scope
+        de_seccion.sort_by_key(|e| e.scope.clone());
+        md.push_str(&format!("\n### {}\n\n", seccion.titulo()));
+        for e in de_seccion {
+            let ruptura = if e.rompe { "⚠️ **BREAKING:** " } else { "" };
+            let scope = e
+                .scope
+                .as_ref()
+                .map(|s| format!("**{}:** ", s))
+                .unwrap_or_default();
+            md.push_str(&format!(
+                "- {}{}{} ({})\n",
+                ruptura, scope, e.descripcion, e.hash
+            ));
+            if let Some(nota) = &e.nota_ruptura {
+                md.push_str(&format!("  - {}\n", nota));
+            }
+        }
+    }
+    md
+}
+
+/// Antepone la nueva versión a las existentes (tras el título y la introducción)
+fn insertar_en_changelog(existente: Option<&str>, seccion: &str) -> String {
+    let Some(existente) = existente.filter(|e| !e.trim().is_empty()) else {
+        return format!("{}\n{}", ENCABEZADO, seccion);
+    };
+    let posicion = if existente.starts_with("## ") {
+        Some(0)
+    } else {
+        existente.find("\n## ").map(|i| i + 1)
+    };
+    match posicion {
+        Some(i) => {
+            // Respetar el separador entre versiones si el archivo lo usa
+            let separador = if existente[i..].contains("\n---\n") {
+                "\n---\n\n"
+            } else {
+                "\n"
+            };
+            format!(
+                "{}{}{}{}",
+                &existente[..i],
+                seccion,
+                separador,
+                &existente[i..]
+            )
+        }
+        None => format!("{}\n\n{}", existente.trim_end(), seccion),
+    }
+}
+
+/// Ejecuta git en el proyecto; `None` si falla
+fn git(project_path: &Path, args: &[&str]) -> Option<String> {
+    let salida = Command::new("git")
+        .args(args)
+        .current_dir(project_path)
+        .output()
+        .ok()?;
+    salida
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&salida.stdout).to_string())
+}
+
+/// Reescribe las descripciones con IA; si la respuesta no es válida las deja igual
+fn pulir_con_ia(
+    entradas: &mut [Entrada],
+    project_path: &Path,
+    config: &SentinelConfig,
+) -> anyhow::Result<()> {
+    let stats = Arc::new(Mutex::new(SentinelStats::cargar(project_path)));
+    // Solo la descripción: el scope se escribe aparte en `componer_seccion`
+    let descripciones: Vec<&str> = entradas.iter().map(|e| e.descripcion.as_str()).collect();
+    let prompt = prompts::renderizar(
+        "changelog",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            (
+                "entries",
+                &delimitar("ENTRADAS", &serde_json::to_string_pretty(&descripciones)?),
+            ),
+        ],
+    );
+    let respuesta =
+        ai::consultar_ia_dinamico(prompt, ai::TaskType::Light, config, stats, project_path)?;
+
+    let pulidas = match (respuesta.find('['), respuesta.rfind(']')) {
+        (Some(inicio), Some(fin)) if fin > inicio => {
+            serde_json::from_str::<Vec<String>>(&respuesta[inicio..=fin]).ok()
+        }
+        _ => None,
+    };
+    match pulidas {
+        Some(pulidas) if pulidas.len() == entradas.len() => {
+            for (entrada, texto) in entradas.iter_mut().zip(pulidas) {
+                let texto = texto.trim();
+                if !texto.is_empty() && !texto.contains('\n') {
+                    entrada.descripcion = texto.to_string();
+                }
+            }
+            Ok(())
+        }
+        _ => anyhow::bail!("{}", t("changelog.respuesta_invalida")),
+    }
+}
+
+/// `sentinel changelog`: antepone una nueva versión a `CHANGELOG.md`
+///
+/// # Retorna
+/// Código de salida: 0 si se generó la versión (o no hay cambios que
+/// registrar), 1 si `--ai` falló y se usaron las descripciones originales, 2
+/// si no se pudo generar.
+pub fn ejecutar(project_path: &Path, opciones: &OpcionesChangelog) -> i32 {
+    let config = SentinelConfig::load(project_path);
+    if let Some(config) = &config {
+        i18n::establecer(config.language.as_deref());
+    }
+
+    let hasta = opciones.hasta.as_deref().unwrap_or("HEAD");
+    let ultimo_tag = git(project_path, &["describe", "--tags", "--abbrev=0", hasta])
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let desde = opciones.desde.clone().or_else(|| ultimo_tag.clone());
+    let rango = match &desde {
+        Some(desde) => format!("{}..{}", desde, hasta),
+        None => hasta.to_string(),
+    };
+    println!(
+        "{}",
+        tf("changelog.leyendo", &[("rango", &rango)])
+            .bright_cyan()
+            .bold()
+    );
+
+    let Some(salida) = git(
+        project_path,
+        &[
+            "log",
+            "--no-merges",
+            "--pretty=format:%x1e%h%x1f%s%x1f%b",
+            &rango,
+        ],
+    ) else {
+        eprintln!("{}", tf("changelog.error_git", &[("rango", &rango)]).red());
+        return 2;
+    };
+    let (mut entradas, no_convencionales) = interpretar_log(&salida);
+    if no_convencionales > 0 {
+        println!(
+            "{}",
+            tf(
+                "changelog.no_convencionales",
+                &[("n", &no_convencionales.to_string())]
+            )
+            .yellow()
+        );
+    }
+    if entradas.is_empty() {
+        println!("{}", t("changelog.sin_cambios").yellow());
+        return 0;
+    }
+
+    let mut codigo = 0;
+    if opciones.ia {
+        let Some(config) = &config else {
+            eprintln!("{}", t("changelog.sin_config").red().bold());
+            return 2;
+        };
+        if let Err(e) = pulir_con_ia(&mut entradas, project_path, config) {
+            println!(
+                "{}",
+                tf("changelog.error_ia", &[("error", &e.to_string())]).yellow()
+            );
+            codigo = 1;
+        }
+    }
+
+    let version = opciones
+        .version
+        .clone()
+        .or_else(|| {
+            ultimo_tag
+                .as_deref()
+                .and_then(|tag| siguiente_version(tag, &entradas))
+        })
+        .unwrap_or_else(|| "Unreleased".to_string());
+    let ahora = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let seccion = componer_seccion(&version, &fecha_iso8601(ahora)[..10], &entradas);
+
+    if opciones.dry_run {
+        println!("\n{}", seccion);
+        return codigo;
+    }
+
+    let ruta = project_path.join(ARCHIVO_CHANGELOG);
+    let existente = fs::read_to_string(&ruta).ok();
+    let encabezado = seccion.lines().next().unwrap_or("");
+    if let Some(existente) = &existente
+        && existente
+            .lines()
+            .any(|l| l.split(" - ").next() == encabezado.split(" - ").next())
+    {
+        eprintln!(
+            "{}",
+            tf("changelog.version_existente", &[("version", &version)]).red()
+        );
+        return 2;
+    }
+    if let Err(e) = fs::write(&ruta, insertar_en_changelog(existente.as_deref(), &seccion)) {
+        eprintln!(
+            "{}",
+            tf("changelog.error", &[("error", &e.to_string())]).red()
+        );
+        return 2;
+    }
+    println!(
+        "{}",
+        tf(
+            "changelog.escrito",
+            &[
+                ("version", &version),
+                ("n", &entradas.len().to_string()),
+                ("archivo", ARCHIVO_CHANGELOG)
+            ]
+        )
+        .green()
+    );
+    codigo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agrupa_commits_y_detecta_rupturas() {
+        let salida = "\x1ea1\x1ffeat(api)!: quita el endpoint /v1\x1f\
+            \x1ea2\x1ffix: corrige el login\x1fBREAKING CHANGE: el token\nahora expira\n\nRefs #3\
+            \x1ea3\x1fdocs: readme\x1f\
+            \x1ea4\x1factualiza cosas\x1f\
+            \x1ea5\x1ffeat(auth): agrega OAuth\x1f\
+            \x1ea6\x1ffix(security): escapa HTML\x1f";
+        let (entradas, no_convencionales) = interpretar_log(salida);
+        assert_eq!(no_convencionales, 1);
+        let resumen: Vec<(&str, Seccion, bool)> = entradas
+            .iter()
+            .map(|e| (e.hash.as_str(), e.seccion, e.rompe))
+            .collect();
+        assert_eq!(
+            resumen,
+            vec![
+                ("a6", Seccion::Security, false),
+                ("a5", Seccion::Added, false),
+                ("a2", Seccion::Fixed, true),
+                ("a1", Seccion::Removed, true),
+            ]
+        );
+        assert_eq!(
+            entradas[2].nota_ruptura.as_deref(),
+            Some("el token ahora expira")
+        );
+
+        assert_eq!(
+            siguiente_version("v1.4.2", &entradas).as_deref(),
+            Some("2.0.0")
+        );
+        assert_eq!(
+            siguiente_version("0.3.1", &entradas).as_deref(),
+            Some("0.4.0")
+        );
+        assert_eq!(
+            siguiente_version("v1.4.2", &entradas[..1]).as_deref(),
+            Some("1.4.3")
+        );
+        assert_eq!(siguiente_version("release-7", &entradas), None);
+    }
+
+    #[test]
+    fn test_antepone_version_respetando_separadores() {
+        let seccion = "## [1.1.0] - 2026-10-18\n\n### Added\n\n- x (a1)\n";
+        let existente =
+            "# Changelog\n\nIntro.\n\n## [1.0.0] - 2026-01-01\n\n- y\n\n---\n\n## [0.9.0]\n";
+        assert_eq!(
+            insertar_en_changelog(Some(existente), seccion),
+            "# Changelog\n\nIntro.\n\n## [1.1.0] - 2026-10-18\n\n### Added\n\n- x (a1)\n\n---\n\n## [1.0.0] - 2026-01-01\n\n- y\n\n---\n\n## [0.9.0]\n"
+        );
+        assert!(insertar_en_changelog(None, seccion).starts_with("# Changelog\n"));
+    }
+
+    #[test]
+    fn test_secciones_con_nombres_canonicos() {
+        let (entradas, _) = interpretar_log(
+            "\x1ea1\x1ffeat(api)!: quita el endpoint /v1\x1f\x1ea2\x1ffix: corrige el login\x1f",
+        );
+        assert_eq!(
+            componer_seccion("2.0.0", "2026-10-18", &entradas),
+            "## [2.0.0] - 2026-10-18\n\n### Removed\n\n\
+            - ⚠️ **BREAKING:** **api:** quita el endpoint /v1 (a1)\n\n\
+            ### Fixed\n\n- corrige el login (a2)\n"
+        );
+    }
+}
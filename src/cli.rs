@@ -0,0 +1,282 @@
+//! # Línea de comandos
+//!
+//! Sin argumentos Sentinel inicia el monitor interactivo. Los subcomandos
+//! ejecutan una tarea puntual sobre el proyecto y terminan con un código de
+//! salida apto para scripts y CI.
+
+use crate::changelog::OpcionesChangelog;
+use crate::i18n::{t, tf};
+use crate::pr::OpcionesRevision;
+use crate::report::OpcionesReporte;
+use std::path::PathBuf;
+
+/// Tarea pedida por la línea de comandos
+#[derive(Debug, PartialEq)]
+pub enum Comando {
+    /// Monitor interactivo (comportamiento por defecto)
+    Monitor,
+    /// `sentinel audit`: vulnerabilidades de dependencias contra una base OSV local
+    Audit {
+        proyecto: PathBuf,
+        db: Option<PathBuf>,
+    },
+    /// `sentinel docs build`: documentación de todo el proyecto, índice y arquitectura
+    DocsBuild {
+        proyecto: PathBuf,
+        mermaid: bool,
+    },
+    /// `sentinel report`: commits y revisiones de un periodo
+    Report {
+        proyecto: PathBuf,
+        opciones: OpcionesReporte,
+    },
+    /// `sentinel changelog`: nueva versión de `CHANGELOG.md` desde Conventional Commits
+    Changelog {
+        proyecto: PathBuf,
+        opciones: OpcionesChangelog,
+    },
+    /// `sentinel review`: revisión de PR sobre el diff de la rama contra una base
+    Review {
+        proyecto: PathBuf,
+        opciones: OpcionesRevision,
+    },
+    Ayuda,
+}
+
+/// Interpreta los argumentos (sin el nombre del ejecutable)
+pub fn parsear(args: &[String]) -> Result<Comando, String> {
+    let Some(subcomando) = args.first() else {
+        return Ok(Comando::Monitor);
+    };
+
+    match subcomando.as_str() {
+        "-h" | "--help" | "help" => Ok(Comando::Ayuda),
+        "audit" => {
+            let mut proyecto = PathBuf::from(".");
+            let mut db = None;
+            let mut resto = args[1..].iter();
+            while let Some(arg) = resto.next() {
+                let mut valor = |flag: &str| {
+                    resto
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| tf("cli.requiere_valor", &[("flag", flag)]))
+                };
+                match arg.as_str() {
+                    "--path" | "-p" => proyecto = PathBuf::from(valor(arg)?),
+                    "--db" => db = Some(PathBuf::from(valor(arg)?)),
+                    otro => {
+                        return Err(tf(
+                            "cli.argumento_desconocido",
+                            &[("subcomando", "audit"), ("argumento", otro)],
+                        ));
+                    }
+                }
+            }
+            Ok(Comando::Audit { proyecto, db })
+        }
+        "docs" if args.get(1).map(String::as_str) == Some("build") => {
+            let mut proyecto = PathBuf::from(".");
+            let mut mermaid = false;
+            let mut resto = args[2..].iter();
+            while let Some(arg) = resto.next() {
+                match arg.as_str() {
+                    "--path" | "-p" => {
+                        proyecto = PathBuf::from(
+                            resto
+                                .next()
+                                .ok_or_else(|| tf("cli.requiere_valor", &[("flag", arg)]))?,
+                        )
+                    }
+                    "--mermaid" => mermaid = true,
+                    otro => {
+                        return Err(tf(
+                            "cli.argumento_desconocido",
+                            &[("subcomando", "docs build"), ("argumento", otro)],
+                        ));
+                    }
+                }
+            }
+            Ok(Comando::DocsBuild { proyecto, mermaid })
+        }
+        "report" => {
+            let mut proyecto = PathBuf::from(".");
+            let mut opciones = OpcionesReporte::default();
+            let mut resto = args[1..].iter();
+            while let Some(arg) = resto.next() {
+                let mut valor = |flag: &str| {
+                    resto
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| tf("cli.requiere_valor", &[("flag", flag)]))
+                };
+                match arg.as_str() {
+                    "--path" | "-p" => proyecto = PathBuf::from(valor(arg)?),
+                    "--since" => opciones.since = Some(valor(arg)?),
+                    "--until" => opciones.until = Some(valor(arg)?),
+                    "--author" => opciones.author = Some(valor(arg)?),
+                    "--branch" => opciones.branch = Some(valor(arg)?),
+                    otro => {
+                        return Err(tf(
+                            "cli.argumento_desconocido",
+                            &[("subcomando", "report"), ("argumento", otro)],
+                        ));
+                    }
+                }
+            }
+            Ok(Comando::Report { proyecto, opciones })
+        }
+        "changelog" => {
+            let mut proyecto = PathBuf::from(".");
+            let mut opciones = OpcionesChangelog::default();
+            let mut resto = args[1..].iter();
+            while let Some(arg) = resto.next() {
+                let mut valor = |flag: &str| {
+                    resto
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| tf("cli.requiere_valor", &[("flag", flag)]))
+                };
+                match arg.as_str() {
+                    "--path" | "-p" => proyecto = PathBuf::from(valor(arg)?),
+                    "--from" => opciones.desde = Some(valor(arg)?),
+                    "--to" => opciones.hasta = Some(valor(arg)?),
+                    "--version" => opciones.version = Some(valor(arg)?),
+                    "--ai" => opciones.ia = true,
+                    "--dry-run" => opciones.dry_run = true,
+                    otro => {
+                        return Err(tf(
+                            "cli.argumento_desconocido",
+                            &[("subcomando", "changelog"), ("argumento", otro)],
+                        ));
+                    }
+                }
+            }
+            Ok(Comando::Changelog { proyecto, opciones })
+        }
+        "review" => {
+            let mut proyecto = PathBuf::from(".");
+            let mut base = None;
+            let mut head = None;
+            let mut salida = None;
+            let mut resto = args[1..].iter();
+            while let Some(arg) = resto.next() {
+                let mut valor = |flag: &str| {
+                    resto
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| tf("cli.requiere_valor", &[("flag", flag)]))
+                };
+                match arg.as_str() {
+                    "--path" | "-p" => proyecto = PathBuf::from(valor(arg)?),
+                    "--base" => base = Some(valor(arg)?),
+                    "--head" => head = Some(valor(arg)?),
+                    "--output" | "-o" => salida = Some(PathBuf::from(valor(arg)?)),
+                    otro => {
+                        return Err(tf(
+                            "cli.argumento_desconocido",
+                            &[("subcomando", "review"), ("argumento", otro)],
+                        ));
+                    }
+                }
+            }
+            let base = base.ok_or_else(|| {
+                tf(
+                    "cli.falta_argumento",
+                    &[("subcomando", "review"), ("flag", "--base")],
+                )
+            })?;
+            Ok(Comando::Review {
+                proyecto,
+                opciones: OpcionesRevision { base, head, salida },
+            })
+        }
+        otro => Err(tf("cli.subcomando_desconocido", &[("subcomando", otro)])),
+    }
+}
+
+/// Muestra la ayuda de la línea de comandos
+pub fn mostrar_uso() {
+    println!("{}", t("cli.uso"));
+    println!();
+    println!("{}", t("cli.sin_subcomando"));
+    println!();
+    println!("{}", t("cli.subcomandos"));
+    println!("{}", t("cli.ayuda_audit"));
+    println!("{}", t("cli.ayuda_docs"));
+    println!("{}", t("cli.ayuda_report"));
+    println!("{}", t("cli.ayuda_changelog"));
+    println!("{}", t("cli.ayuda_review"));
+    println!("{}", t("cli.ayuda_help"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(lista: &[&str]) -> Vec<String> {
+        lista.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parsear_subcomandos() {
+        assert_eq!(parsear(&[]), Ok(Comando::Monitor));
+        assert_eq!(
+            parsear(&args(&["audit", "--db", "/tmp/osv"])),
+            Ok(Comando::Audit {
+                proyecto: PathBuf::from("."),
+                db: Some(PathBuf::from("/tmp/osv")),
+            })
+        );
+        assert!(parsear(&args(&["audit", "--db"])).is_err());
+        assert_eq!(
+            parsear(&args(&["docs", "build", "--mermaid", "-p", "app"])),
+            Ok(Comando::DocsBuild {
+                proyecto: PathBuf::from("app"),
+                mermaid: true,
+            })
+        );
+        assert_eq!(
+            parsear(&args(&[
+                "report",
+                "--since",
+                "1 week ago",
+                "--author",
+                "ana"
+            ])),
+            Ok(Comando::Report {
+                proyecto: PathBuf::from("."),
+                opciones: OpcionesReporte {
+                    since: Some("1 week ago".to_string()),
+                    author: Some("ana".to_string()),
+                    ..Default::default()
+                },
+            })
+        );
+        assert_eq!(
+            parsear(&args(&["changelog", "--from", "v1.0.0", "--ai"])),
+            Ok(Comando::Changelog {
+                proyecto: PathBuf::from("."),
+                opciones: OpcionesChangelog {
+                    desde: Some("v1.0.0".to_string()),
+                    ia: true,
+                    ..Default::default()
+                },
+            })
+        );
+        assert_eq!(
+            parsear(&args(&["review", "--base", "main"])),
+            Ok(Comando::Review {
+                proyecto: PathBuf::from("."),
+                opciones: OpcionesRevision {
+                    base: "main".to_string(),
+                    head: None,
+                    salida: None,
+                },
+            })
+        );
+        assert!(parsear(&args(&["review"])).is_err());
+        assert!(parsear(&args(&["docs"])).is_err());
+        assert!(parsear(&args(&["deploy"])).is_err());
+    }
+}
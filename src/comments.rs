@@ -0,0 +1,566 @@
+//! # Comentarios de documentación en el código
+//!
+//! Detecta los símbolos exportados del archivo modificado que no tienen
+//! comentario de documentación (JSDoc/TSDoc, docstrings de Python, GoDoc,
+//! rustdoc, PHPDoc) y pide a la IA solo el texto de cada comentario. Sentinel
+//! los inserta en su lugar y muestra el resultado como diff para que el usuario
+//! lo acepte o lo rechace: el resto del archivo nunca se modifica.
+
+use crate::ai;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::config::SentinelConfig;
+use crate::diff;
+use crate::i18n::{es_afirmativo, t, tf};
+use crate::stats::SentinelStats;
+use colored::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Símbolos por consulta (el resto se propone en el siguiente cambio)
+const MAX_SIMBOLOS: usize = 20;
+
+/// Líneas máximas de la firma de una función de Python
+const MAX_LINEAS_FIRMA: usize = 20;
+
+/// Convención de comentarios de documentación del lenguaje
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Estilo {
+    JsDoc,
+    Docstring,
+    GoDoc,
+    RustDoc,
+    PhpDoc,
+}
+
+impl Estilo {
+    /// Estilo según `code_language`; si no se reconoce, según la extensión
+    fn para(code_language: &str, path: &Path) -> Option<Self> {
+        let por_nombre = |nombre: &str| match nombre {
+            "typescript" | "javascript" | "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => {
+                Some(Estilo::JsDoc)
+            }
+            "python" | "py" => Some(Estilo::Docstring),
+            "go" | "golang" => Some(Estilo::GoDoc),
+            "rust" | "rs" => Some(Estilo::RustDoc),
+            "php" => Some(Estilo::PhpDoc),
+            _ => None,
+        };
+        por_nombre(&code_language.trim().to_lowercase()).or_else(|| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .and_then(por_nombre)
+        })
+    }
+
+    fn nombre(&self) -> &'static str {
+        match self {
+            Estilo::JsDoc => "JSDoc/TSDoc (/** ... */)",
+            Estilo::Docstring => "docstrings de Python (PEP 257, \"\"\"...\"\"\")",
+            Estilo::GoDoc => "GoDoc (// Nombre ...)",
+            Estilo::RustDoc => "rustdoc (///)",
+            Estilo::PhpDoc => "PHPDoc (/** ... */)",
+        }
+    }
+
+    /// Verifica que el texto propuesto sea solo un comentario de documentación
+    fn comentario_valido(&self, comentario: &str) -> bool {
+        let lineas: Vec<&str> = comentario.lines().map(str::trim).collect();
+        let (Some(primera), Some(ultima)) = (lineas.first(), lineas.last()) else {
+            return false;
+        };
+        match self {
+            Estilo::JsDoc | Estilo::PhpDoc => {
+                primera.starts_with("/**")
+                    && ultima.ends_with("*/")
+                    && lineas.iter().enumerate().all(|(i, l)| {
+                        let cierre = l.find("*/");
+                        (i == 0 || l.starts_with('*'))
+                            && (cierre.is_none() || (i == lineas.len() - 1 && l.ends_with("*/")))
+                    })
+            }
+            Estilo::GoDoc => lineas.iter().all(|l| l.starts_with("//")),
+            Estilo::RustDoc => lineas.iter().all(|l| l.starts_with("///")),
+            Estilo::Docstring => {
+                let texto = comentario.trim();
+                texto.len() >= 6
+                    && texto.starts_with("\"\"\"")
+                    && texto.ends_with("\"\"\"")
+                    && texto[3..texto.len() - 3].find("\"\"\"").is_none()
+            }
+        }
+    }
+}
+
+/// Símbolo exportado sin comentario de documentación
+#[derive(Debug, PartialEq)]
+struct SimboloSinDoc {
+    nombre: String,
+    /// Línea de la declaración (0-indexada)
+    linea: usize,
+    /// Línea antes de la cual se inserta el comentario (0-indexada)
+    insertar_en: usize,
+    /// Indentación del comentario
+    indentacion: String,
+}
+
+/// Comentario propuesto por la IA
+#[derive(Debug, Deserialize)]
+struct Propuesta {
+    simbolo: String,
+    comentario: String,
+}
+
+/// Propone comentarios de documentación para los símbolos exportados sin documentar
+///
+/// Se muestra el diff con los comentarios insertados y solo se escribe el
+/// archivo si el usuario acepta.
+///
+/// # Retorna
+/// `true` si se aplicaron los comentarios.
+pub fn proponer_comentarios(
+    file_path: &Path,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: &Path,
+    leer_respuesta: &dyn Fn() -> Option<String>,
+) -> anyhow::Result<bool> {
+    let Some(estilo) = Estilo::para(&config.code_language, file_path) else {
+        return Ok(false);
+    };
+    let codigo = fs::read_to_string(file_path)?;
+    let mut simbolos = buscar_sin_documentar(&codigo, estilo);
+    if simbolos.is_empty() {
+        return Ok(false);
+    }
+    simbolos.truncate(MAX_SIMBOLOS);
+
+    let file_name = file_path.file_name().unwrap().to_str().unwrap();
+    println!(
+        "{}",
+        tf(
+            "comentarios.sin_documentar",
+            &[
+                ("n", &simbolos.len().to_string()),
+                ("archivo", &file_name.cyan().to_string())
+            ]
+        )
+        .magenta()
+    );
+
+    let lista = simbolos
+        .iter()
+        .map(|s| format!("- {} (línea {})", s.nombre, s.linea + 1))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = prompts::renderizar(
+        "doc-comments",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("language", &config.code_language),
+            ("style", estilo.nombre()),
+            ("file_name", file_name),
+            ("symbols", &lista),
+            ("code", &delimitar("CODIGO", &codigo)),
+        ],
+    );
+    let respuesta =
+        ai::consultar_ia_dinamico(prompt, ai::TaskType::Light, config, stats, project_path)?;
+
+    let propuestas = interpretar_propuestas(&respuesta);
+    let (nuevo, insertados) = insertar_comentarios(&codigo, &simbolos, &propuestas, estilo);
+    if insertados == 0 {
+        println!("{}", t("comentarios.sin_propuestas").yellow());
+        return Ok(false);
+    }
+
+    let ruta = file_path
+        .strip_prefix(project_path)
+        .unwrap_or(file_path)
+        .to_string_lossy()
+        .to_string();
+    println!();
+    diff::mostrar_diff(&diff::diff_unificado(&codigo, &nuevo, &ruta));
+    print!(
+        "\n{}",
+        tf(
+            "comentarios.preguntar_aplicar",
+            &[("n", &insertados.to_string())]
+        )
+    );
+    io::stdout().flush()?;
+    if !leer_respuesta().is_some_and(|r| es_afirmativo(&r)) {
+        println!("{}", t("comentarios.descartados"));
+        return Ok(false);
+    }
+
+    // El archivo pudo cambiar mientras se esperaba la respuesta
+    if fs::read_to_string(file_path)? != codigo {
+        println!("{}", t("comentarios.archivo_cambio").yellow());
+        return Ok(false);
+    }
+    fs::write(file_path, nuevo)?;
+    println!("{}", t("comentarios.aplicados").green());
+    Ok(true)
+}
+
+/// Extrae la lista JSON `[{"simbolo": ..., "comentario": ...}]` de la respuesta
+fn interpretar_propuestas(respuesta: &str) -> HashMap<String, String> {
+    let json = match (respuesta.find('['), respuesta.rfind(']')) {
+        (Some(inicio), Some(fin)) if fin > inicio => &respuesta[inicio..=fin],
+        _ => return HashMap::new(),
+    };
+    serde_json::from_str::<Vec<Propuesta>>(json)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| (p.simbolo, p.comentario))
+        .collect()
+}
+
+/// Inserta los comentarios válidos sobre (o, en Python, dentro de) cada símbolo
+///
+/// # Retorna
+/// El nuevo contenido y la cantidad de comentarios insertados.
+fn insertar_comentarios(
+    codigo: &str,
+    simbolos: &[SimboloSinDoc],
+    propuestas: &HashMap<String, String>,
+    estilo: Estilo,
+) -> (String, usize) {
+    let mut lineas: Vec<String> = codigo.lines().map(String::from).collect();
+    let mut inserciones: Vec<(usize, Vec<String>)> = simbolos
+        .iter()
+        .filter_map(|simbolo| {
+            let comentario = propuestas.get(&simbolo.nombre)?;
+            if !estilo.comentario_valido(comentario) {
+                return None;
+            }
+            let bloque = comentario
+                .trim()
+                .lines()
+                .enumerate()
+                .map(|(i, linea)| {
+                    let linea = linea.trim();
+                    let extra = if i > 0
+                        && matches!(estilo, Estilo::JsDoc | Estilo::PhpDoc)
+                        && linea.starts_with('*')
+                    {
+                        " "
+                    } else {
+                        ""
+                    };
+                    if linea.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{}{}{}", simbolo.indentacion, extra, linea)
+                    }
+                })
+                .collect();
+            Some((simbolo.insertar_en, bloque))
+        })
+        .collect();
+
+    // De abajo hacia arriba para no desplazar las posiciones pendientes
+    inserciones.sort_by_key(|(posicion, _)| std::cmp::Reverse(*posicion));
+    let insertados = inserciones.len();
+    for (posicion, bloque) in inserciones {
+        lineas.splice(posicion..posicion, bloque);
+    }
+
+    let mut nuevo = lineas.join("\n");
+    if codigo.ends_with('\n') {
+        nuevo.push('\n');
+    }
+    (nuevo, insertados)
+}
+
+fn indentacion_de(linea: &str) -> &str {
+    &linea[..linea.len() - linea.trim_start().len()]
+}
+
+/// Primer identificador de un texto (letras, dígitos, `_` y `$`)
+fn identificador(texto: &str) -> Option<String> {
+    let nombre: String = texto
+        .trim_start()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+        .collect();
+    (!nombre.is_empty()).then_some(nombre)
+}
+
+/// Quita el primer prefijo que coincida (repetidamente) y retorna el resto
+fn sin_prefijos<'a>(mut texto: &'a str, prefijos: &[&str]) -> &'a str {
+    while let Some(prefijo) = prefijos.iter().find(|p| texto.starts_with(*p)) {
+        texto = texto[prefijo.len()..].trim_start();
+    }
+    texto
+}
+
+/// Primera línea del bloque de decoradores/atributos que precede a una declaración
+///
+/// Solo cuenta las líneas con la misma indentación que la declaración, para
+/// no confundir los decoradores de los miembros de una clase anterior.
+fn inicio_decoradores(lineas: &[&str], i: usize, marcador: &str) -> usize {
+    let indentacion = indentacion_de(lineas[i]);
+    let mut inicio = i;
+    let mut j = i;
+    while j > 0 {
+        let previa = lineas[j - 1];
+        if previa.trim().is_empty() {
+            break;
+        }
+        j -= 1;
+        let propia = previa.strip_prefix(indentacion).unwrap_or("");
+        if propia.starts_with(marcador) {
+            inicio = j;
+        } else if !(propia.starts_with(char::is_whitespace)
+            || propia.starts_with(')')
+            || propia.starts_with('}')
+            || propia.starts_with(']'))
+        {
+            break;
+        }
+    }
+    inicio
+}
+
+/// `true` si la línea anterior a `inicio` cierra o es un comentario de documentación
+fn documentado_antes(lineas: &[&str], inicio: usize, estilo: Estilo) -> bool {
+    let Some(previa) = inicio.checked_sub(1).map(|j| lineas[j].trim()) else {
+        return false;
+    };
+    match estilo {
+        Estilo::JsDoc | Estilo::PhpDoc => previa.ends_with("*/"),
+        Estilo::GoDoc => previa.starts_with("//"),
+        Estilo::RustDoc => previa.starts_with("///") || previa.starts_with("#[doc"),
+        Estilo::Docstring => false,
+    }
+}
+
+/// Símbolos exportados sin comentario de documentación
+fn buscar_sin_documentar(codigo: &str, estilo: Estilo) -> Vec<SimboloSinDoc> {
+    let lineas: Vec<&str> = codigo.lines().collect();
+    let mut simbolos = Vec::new();
+
+    for (i, linea) in lineas.iter().enumerate() {
+        let nivel_superior = !linea.starts_with(char::is_whitespace);
+        let texto = linea.trim();
+
+        let nombre = match estilo {
+            Estilo::JsDoc if nivel_superior && texto.starts_with("export ") => {
+                let resto = sin_prefijos(
+                    &texto["export ".len()..],
+                    &["default ", "declare ", "abstract ", "async "],
+                );
+                let resto = sin_prefijos(
+                    resto,
+                    &[
+                        "function* ",
+                        "function ",
+                        "class ",
+                        "const ",
+                        "let ",
+                        "var ",
+                        "interface ",
+                        "type ",
+                        "enum ",
+                    ],
+                );
+                // `export { a }`, `export * from` y `export default {` no declaran nombre
+                (resto.len() < texto.len() - "export ".len())
+                    .then(|| identificador(resto))
+                    .flatten()
+            }
+            Estilo::Docstring if nivel_superior => {
+                let resto = sin_prefijos(texto, &["async "]);
+                resto
+                    .strip_prefix("def ")
+                    .or_else(|| resto.strip_prefix("class "))
+                    .and_then(identificador)
+                    .filter(|n| !n.starts_with('_'))
+            }
+            Estilo::GoDoc if nivel_superior => {
+                let resto = texto
+                    .strip_prefix("func ")
+                    .map(|r| match r.strip_prefix('(') {
+                        // Método: saltar el receptor
+                        Some(receptor) => receptor.split_once(')').map_or("", |(_, r)| r),
+                        None => r,
+                    })
+                    .or_else(|| texto.strip_prefix("type "));
+                resto
+                    .and_then(identificador)
+                    .filter(|n| n.starts_with(|c: char| c.is_uppercase()))
+            }
+            Estilo::RustDoc if texto.starts_with("pub ") => {
+                let resto =
+                    sin_prefijos(&texto["pub ".len()..], &["async ", "const fn ", "unsafe "]);
+                let resto = sin_prefijos(
+                    resto,
+                    &[
+                        "fn ", "struct ", "enum ", "trait ", "type ", "const ", "static ",
+                    ],
+                );
+                (resto.len() < texto.len() - "pub ".len())
+                    .then(|| identificador(resto))
+                    .flatten()
+            }
+            Estilo::PhpDoc => {
+                let resto = sin_prefijos(texto, &["final ", "abstract ", "readonly "]);
+                if let Some(metodo) = resto
+                    .strip_prefix("public ")
+                    .map(|r| sin_prefijos(r, &["static "]))
+                    .and_then(|r| r.strip_prefix("function "))
+                {
+                    identificador(metodo).filter(|n| !n.starts_with("__"))
+                } else if nivel_superior {
+                    ["function ", "class ", "interface ", "trait ", "enum "]
+                        .iter()
+                        .find_map(|k| resto.strip_prefix(k))
+                        .and_then(identificador)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+        let Some(nombre) = nombre else {
+            continue;
+        };
+
+        if estilo == Estilo::Docstring {
+            if let Some((insertar_en, indentacion)) = posicion_docstring(&lineas, i) {
+                simbolos.push(SimboloSinDoc {
+                    nombre,
+                    linea: i,
+                    insertar_en,
+                    indentacion,
+                });
+            }
+            continue;
+        }
+
+        let marcador = match estilo {
+            Estilo::JsDoc => "@",
+            _ => "#[",
+        };
+        let inicio = inicio_decoradores(&lineas, i, marcador);
+        if !documentado_antes(&lineas, inicio, estilo) {
+            simbolos.push(SimboloSinDoc {
+                nombre,
+                linea: i,
+                insertar_en: inicio,
+                indentacion: indentacion_de(linea).to_string(),
+            });
+        }
+    }
+    simbolos
+}
+
+/// Dónde va el docstring de una función o clase de Python sin documentar
+///
+/// # Retorna
+/// `None` si ya tiene docstring o si la definición ocupa una sola línea.
+fn posicion_docstring(lineas: &[&str], i: usize) -> Option<(usize, String)> {
+    // La firma termina cuando se cierran los paréntesis abiertos
+    let mut profundidad = 0i32;
+    let mut fin = None;
+    for (j, linea) in lineas.iter().enumerate().skip(i).take(MAX_LINEAS_FIRMA) {
+        let sin_comentario = linea.split('#').next().unwrap_or("");
+        for c in sin_comentario.chars() {
+            match c {
+                '(' | '[' => profundidad += 1,
+                ')' | ']' => profundidad -= 1,
+                _ => {}
+            }
+        }
+        if profundidad <= 0 {
+            fin = sin_comentario.trim_end().ends_with(':').then_some(j);
+            break;
+        }
+    }
+    let fin = fin?;
+
+    let cuerpo = lineas[fin + 1..].iter().find(|l| !l.trim().is_empty());
+    if let Some(cuerpo) = cuerpo {
+        let texto = cuerpo.trim_start();
+        if ["\"\"\"", "'''", "r\"\"\"", "r'''"]
+            .iter()
+            .any(|p| texto.starts_with(p))
+        {
+            return None;
+        }
+    }
+    let base = indentacion_de(lineas[i]);
+    let indentacion = match cuerpo.map(|c| indentacion_de(c)) {
+        Some(ind) if ind.len() > base.len() => ind.to_string(),
+        _ => format!("{}    ", base),
+    };
+    Some((fin + 1, indentacion))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nombres(codigo: &str, estilo: Estilo) -> Vec<String> {
+        buscar_sin_documentar(codigo, estilo)
+            .into_iter()
+            .map(|s| s.nombre)
+            .collect()
+    }
+
+    #[test]
+    fn test_detecta_simbolos_sin_documentar() {
+        let ts = "/** Documentado */\nexport function a() {}\n\n@Injectable()\nexport class UsersService {\n  @Get()\n  m() {}\n}\n@Module({\n  imports: [],\n})\nexport class AppModule {}\nexport { a };\nexport const b = 1;\n";
+        assert_eq!(
+            nombres(ts, Estilo::JsDoc),
+            vec!["UsersService", "AppModule", "b"]
+        );
+
+        let py = "def publica(a,\n            b):\n    return a\n\ndef _privada():\n    pass\n\nclass Doc:\n    \"\"\"Ya tiene.\"\"\"\n\ndef corta(): return 1\n";
+        assert_eq!(nombres(py, Estilo::Docstring), vec!["publica"]);
+
+        let go = "// Sumar suma.\nfunc Sumar() {}\nfunc (s *Servicio) Crear() {}\nfunc interno() {}\ntype Servicio struct{}\n";
+        assert_eq!(nombres(go, Estilo::GoDoc), vec!["Crear", "Servicio"]);
+
+        let rs = "/// Doc\npub fn a() {}\n#[derive(Debug)]\npub struct B;\nimpl B {\n    pub fn nuevo() -> Self { B }\n    fn privado() {}\n}\npub(crate) fn c() {}\n";
+        assert_eq!(nombres(rs, Estilo::RustDoc), vec!["B", "nuevo"]);
+
+        let php = "<?php\nclass User {\n    /** Doc */\n    public function a() {}\n    public static function b() {}\n    private function c() {}\n}\n";
+        assert_eq!(nombres(php, Estilo::PhpDoc), vec!["User", "b"]);
+    }
+
+    #[test]
+    fn test_inserta_solo_comentarios_validos() {
+        let codigo = "@Injectable()\nexport class A {}\nexport const b = 1;\n";
+        let simbolos = buscar_sin_documentar(codigo, Estilo::JsDoc);
+        let propuestas = interpretar_propuestas(
+            r#"Aquí están: [{"simbolo": "A", "comentario": "/**\n* Servicio A.\n*/"}, {"simbolo": "b", "comentario": "const b = 2;"}]"#,
+        );
+        let (nuevo, insertados) =
+            insertar_comentarios(codigo, &simbolos, &propuestas, Estilo::JsDoc);
+        assert_eq!(insertados, 1);
+        assert_eq!(
+            nuevo,
+            "/**\n * Servicio A.\n */\n@Injectable()\nexport class A {}\nexport const b = 1;\n"
+        );
+
+        let py = "class Repo:\n    def __init__(self):\n        pass\n";
+        let simbolos = buscar_sin_documentar(py, Estilo::Docstring);
+        let propuestas = HashMap::from([(
+            "Repo".to_string(),
+            "\"\"\"Repositorio de usuarios.\"\"\"".to_string(),
+        )]);
+        let (nuevo, _) = insertar_comentarios(py, &simbolos, &propuestas, Estilo::Docstring);
+        assert_eq!(
+            nuevo,
+            "class Repo:\n    \"\"\"Repositorio de usuarios.\"\"\"\n    def __init__(self):\n        pass\n"
+        );
+    }
+}
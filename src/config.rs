@@ -1,3 +1,4 @@
+use crate::i18n::{t, tf};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -17,6 +18,41 @@ pub struct FrameworkDetection {
     pub test_patterns: Vec<String>, // Patrones de ubicación de tests (ej: ["test/{name}/{name}.spec.ts"])
 }
 
+/// Regla de arquitectura con identificador estable
+///
+/// Las reglas de los paquetes integrados (`rules::pack_para`) y las reglas
+/// propias del proyecto (`custom_rules`) comparten este formato.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ArchitectureRule {
+    pub id: String,
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<String>,
+}
+
+/// Configuración de un paquete dentro de un monorepo/workspace
+///
+/// Descubierta automáticamente por `workspace::detectar_paquetes`. Los cambios
+/// en archivos dentro de `path` usan el framework, reglas, patrones y runner de
+/// tests de su paquete en lugar de los de la raíz.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PackageConfig {
+    pub name: String,
+    pub path: String, // Ruta relativa a la raíz del proyecto (separador '/')
+    pub framework: String,
+    pub code_language: String,
+    pub manager: String,
+    pub test_command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub testing_framework: Option<String>,
+    #[serde(default)]
+    pub architecture_rules: Vec<String>,
+    #[serde(default)]
+    pub parent_patterns: Vec<String>,
+    #[serde(default)]
+    pub test_patterns: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum AIProvider {
     Claude,
@@ -42,7 +78,7 @@ impl AIProvider {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct AIConfig {
     pub name: String,
     pub provider: AIProvider,
@@ -51,7 +87,7 @@ pub struct AIConfig {
     pub model: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct ModelConfig {
     pub name: String,
     pub url: String,
@@ -68,7 +104,7 @@ impl Default for ModelConfig {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SentinelConfig {
     pub version: String,
     pub project_name: String,
@@ -88,9 +124,55 @@ pub struct SentinelConfig {
     pub testing_framework: Option<String>, // Framework de testing principal (ej: "Jest", "Pytest")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub testing_status: Option<String>, // Estado: "valid", "incomplete", "missing"
+    // Consultar a la IA aunque la detección local sea concluyente
+    #[serde(default)]
+    pub force_ai_detection: bool,
+    // Personalización del paquete de reglas del framework
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disabled_rules: Vec<String>, // IDs de reglas a desactivar (ej: "nestjs/guards-for-auth")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_rules: Vec<ArchitectureRule>, // Reglas propias (mismo id = reemplaza la del paquete)
+    // Paquetes del monorepo (vacío en proyectos de un solo paquete)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub packages: Vec<PackageConfig>,
+    // Intentos máximos del modo de corrección automática de tests
+    #[serde(default = "intentos_fix_por_defecto")]
+    pub fix_max_attempts: u32,
+    // Cobertura de las líneas modificadas en cada cambio
+    #[serde(default)]
+    pub collect_coverage: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage_threshold: Option<f64>, // Porcentaje mínimo (0-100); por debajo no se ofrece commit
+    // Tiempo máximo por ejecución de tests en segundos (0 = sin límite)
+    #[serde(default = "timeout_tests_por_defecto")]
+    pub test_timeout_secs: u64,
+    // Valores que no se redactan al enviar código a la IA (admite comodines * y ?)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub secret_allowlist: Vec<String>,
+    // Directorio de la documentación generada (ej: "docs/api"); sin definir, junto al código
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docs_dir: Option<String>,
+    // Directorio de los reportes de `sentinel report` (por defecto "docs/reports")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reports_dir: Option<String>,
+    // Proponer comentarios de documentación (JSDoc, docstrings, GoDoc...) para símbolos exportados
+    #[serde(default)]
+    pub inline_doc_comments: bool,
+    // Idioma de los mensajes y de las respuestas de la IA ("es", "en"); sin definir se usa LANG
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+fn intentos_fix_por_defecto() -> u32 {
+    3
+}
+
+fn timeout_tests_por_defecto() -> u64 {
+    300
 }
 
 impl SentinelConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn default(
         name: String,
         manager: String,
@@ -105,8 +187,8 @@ impl SentinelConfig {
             version: SENTINEL_VERSION.to_string(),
             project_name: name,
             framework,
-            manager: manager.clone(),
-            test_command: format!("{} run test", manager),
+            test_command: Self::comando_test_por_defecto(&manager),
+            manager,
             architecture_rules: rules,
             file_extensions: extensions,
             code_language,
@@ -132,9 +214,27 @@ impl SentinelConfig {
             use_cache: true,
             testing_framework: None,
             testing_status: None,
+            force_ai_detection: false,
+            disabled_rules: vec![],
+            custom_rules: vec![],
+            packages: vec![],
+            fix_max_attempts: intentos_fix_por_defecto(),
+            collect_coverage: false,
+            coverage_threshold: None,
+            test_timeout_secs: timeout_tests_por_defecto(),
+            secret_allowlist: vec![],
+            docs_dir: None,
+            reports_dir: None,
+            inline_doc_comments: false,
+            language: None,
         }
     }
 
+    /// Indica si los tests deben recolectar cobertura (explícito o por umbral)
+    pub fn cobertura_activa(&self) -> bool {
+        self.collect_coverage || self.coverage_threshold.is_some()
+    }
+
     pub fn save(&self, path: &Path) -> anyhow::Result<()> {
         let toml = toml::to_string_pretty(self)?;
         fs::write(path.join(".sentinelrc.toml"), toml)?;
@@ -177,10 +277,7 @@ impl SentinelConfig {
             }
 
             fs::write(&gitignore_path, content)?;
-            println!(
-                "{}",
-                "   ✅ Archivos sensibles agregados a .gitignore".green()
-            );
+            println!("{}", t("config.gitignore_actualizado").green());
         }
 
         Ok(())
@@ -197,19 +294,22 @@ impl SentinelConfig {
         // Intentar deserializar directamente primero (configuración actual)
         if let Ok(mut config) = toml::from_str::<SentinelConfig>(&content) {
             // Validar y migrar si es necesario
+            if config.version == SENTINEL_VERSION && config.normalizar_comandos_heredados() {
+                let _ = config.save(path);
+            }
             if config.version != SENTINEL_VERSION {
                 println!(
                     "{}",
-                    format!(
-                        "   🔄 Migrando configuración de versión {} a {}...",
-                        config.version, SENTINEL_VERSION
+                    tf(
+                        "config.migrando_version",
+                        &[("antes", &config.version), ("despues", SENTINEL_VERSION)]
                     )
                     .yellow()
                 );
                 config = Self::migrar_config(config, path);
                 // Guardar la configuración migrada
                 let _ = config.save(path);
-                println!("{}", "   ✅ Configuración migrada exitosamente".green());
+                println!("{}", t("config.migrada").green());
             }
             return Some(config);
         }
@@ -230,10 +330,7 @@ impl SentinelConfig {
         }
 
         if let Ok(old_config) = toml::from_str::<SentinelConfigV1>(&content) {
-            println!(
-                "{}",
-                "   🔄 Detectada configuración antigua, migrando...".yellow()
-            );
+            println!("{}", t("config.migrando_antigua").yellow());
 
             // Crear nueva configuración con valores migrados o defaults
             let nombre = old_config.project_name.unwrap_or_else(|| {
@@ -351,15 +448,12 @@ impl SentinelConfig {
 
             // Guardar la configuración migrada
             let _ = new_config.save(path);
-            println!("{}", "   ✅ Configuración migrada exitosamente".green());
+            println!("{}", t("config.migrada").green());
 
             return Some(new_config);
         }
 
-        println!(
-            "{}",
-            "   ⚠️  No se pudo cargar la configuración. Se creará una nueva.".yellow()
-        );
+        println!("{}", t("config.no_se_pudo_cargar").yellow());
         None
     }
 
@@ -370,8 +464,9 @@ impl SentinelConfig {
 
         // Asegurar que todos los campos necesarios existan
         if config.test_command.is_empty() {
-            config.test_command = format!("{} run test", config.manager);
+            config.test_command = Self::comando_test_por_defecto(&config.manager);
         }
+        config.normalizar_comandos_heredados();
 
         if config.ignore_patterns.is_empty() {
             config.ignore_patterns = vec![
@@ -471,6 +566,434 @@ impl SentinelConfig {
         config
     }
 
+    /// Reglas de arquitectura vigentes para el análisis
+    ///
+    /// `architecture_rules` se convierten en reglas con ids `project/N`. Si
+    /// existe un paquete integrado para `framework`, sus reglas van primero y de
+    /// `architecture_rules` solo se agregan las que no vienen del perfil
+    /// detectado (las que el paquete ya cubre). En ambos casos se aplican
+    /// `disabled_rules` y `custom_rules`.
+    pub fn reglas_efectivas(&self) -> Vec<ArchitectureRule> {
+        let del_proyecto = |omitidas: &[String]| {
+            self.architecture_rules
+                .iter()
+                .enumerate()
+                .filter(|(_, r)| !omitidas.contains(r))
+                .map(|(i, r)| ArchitectureRule {
+                    id: format!("project/{}", i + 1),
+                    description: r.clone(),
+                    examples: vec![],
+                })
+                .collect::<Vec<_>>()
+        };
+        let base = match crate::rules::pack_para(&self.framework) {
+            Some(pack) => {
+                let por_defecto = crate::detector::perfil(&self.framework)
+                    .map(|p| p.rules)
+                    .unwrap_or_default();
+                let mut reglas = pack.rules.clone();
+                reglas.extend(del_proyecto(&por_defecto));
+                reglas
+            }
+            None => del_proyecto(&[]),
+        };
+
+        crate::rules::aplicar_personalizacion(base, &self.disabled_rules, &self.custom_rules)
+    }
+
+    /// Configuración efectiva para un archivo de un paquete del monorepo
+    ///
+    /// Reemplaza los campos específicos del paquete (framework, gestor, runner,
+    /// reglas y patrones) conservando el resto de la configuración de la raíz.
+    /// A las extensiones de la raíz se suman las del framework del paquete (un
+    /// servicio Go dentro de un workspace TypeScript).
+    pub fn para_paquete(&self, paquete: &PackageConfig) -> SentinelConfig {
+        let mut config = self.clone();
+        if let Some(perfil) = crate::detector::perfil(&paquete.framework) {
+            for extension in perfil.extensions {
+                if !config.file_extensions.contains(&extension) {
+                    config.file_extensions.push(extension);
+                }
+            }
+        }
+        config.framework = paquete.framework.clone();
+        config.code_language = paquete.code_language.clone();
+        config.manager = paquete.manager.clone();
+        config.test_command = paquete.test_command.clone();
+        if paquete.testing_framework.is_some() {
+            config.testing_framework = paquete.testing_framework.clone();
+        }
+        if !paquete.architecture_rules.is_empty() {
+            config.architecture_rules = paquete.architecture_rules.clone();
+        }
+        if !paquete.parent_patterns.is_empty() {
+            config.parent_patterns = paquete.parent_patterns.clone();
+        }
+        if !paquete.test_patterns.is_empty() {
+            config.test_patterns = paquete.test_patterns.clone();
+        }
+        config
+    }
+
+    /// Relee `.sentinelrc.toml` para aplicarlo en caliente mientras el watcher corre
+    ///
+    /// A diferencia de `load`, no imprime mensajes de migración ni reescribe el
+    /// archivo (evita disparar otro evento del watcher). Si la versión es antigua,
+    /// los campos faltantes se completan solo en memoria.
+    pub fn recargar(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read_to_string(path.join(".sentinelrc.toml"))?;
+        let mut config = toml::from_str::<SentinelConfig>(&content)?;
+
+        if config.version != SENTINEL_VERSION {
+            config = Self::migrar_config(config, path);
+        }
+        config.normalizar_comandos_heredados();
+
+        if let Err(errores) = config.validar() {
+            return Err(anyhow::anyhow!(errores.join("; ")));
+        }
+
+        Ok(config)
+    }
+
+    /// Valida que la configuración sea utilizable por el análisis y el watcher
+    ///
+    /// # Retorna
+    /// * `Ok(())` si la configuración es válida
+    /// * `Err(errores)` con la lista de problemas encontrados
+    pub fn validar(&self) -> Result<(), Vec<String>> {
+        let mut errores = Vec::new();
+
+        if self.file_extensions.is_empty() {
+            errores.push(t("config.error_extensiones_vacias"));
+        }
+        if self
+            .file_extensions
+            .iter()
+            .any(|ext| ext.trim().is_empty() || ext.starts_with('.'))
+        {
+            errores.push(t("config.error_extensiones_con_punto"));
+        }
+        if self.ai_configs.is_empty() {
+            errores.push(t("config.error_sin_modelos"));
+        }
+        for ai in &self.ai_configs {
+            if ai.model.trim().is_empty() {
+                errores.push(tf("config.error_modelo_vacio", &[("nombre", &ai.name)]));
+            }
+            if ai.api_url.trim().is_empty() {
+                errores.push(tf("config.error_url_vacia", &[("nombre", &ai.name)]));
+            }
+        }
+        for paquete in &self.packages {
+            if paquete.path.trim().is_empty() || paquete.path.contains("..") {
+                errores.push(tf(
+                    "config.error_ruta_paquete",
+                    &[("nombre", &paquete.name)],
+                ));
+            }
+        }
+        if self.fix_max_attempts == 0 || self.fix_max_attempts > 10 {
+            errores.push(t("config.error_intentos_fix"));
+        }
+        if let Some(umbral) = self.coverage_threshold
+            && !(0.0..=100.0).contains(&umbral)
+        {
+            errores.push(t("config.error_umbral_cobertura"));
+        }
+        for (campo, dir) in [
+            ("docs_dir", &self.docs_dir),
+            ("reports_dir", &self.reports_dir),
+        ] {
+            if let Some(dir) = dir
+                && (dir.trim().is_empty() || Path::new(dir).is_absolute() || dir.contains(".."))
+            {
+                errores.push(tf("config.error_ruta_relativa", &[("campo", campo)]));
+            }
+        }
+        if let Some(idioma) = &self.language
+            && !crate::i18n::IDIOMAS.contains(&idioma.as_str())
+        {
+            errores.push(tf(
+                "config.error_idioma",
+                &[("idiomas", &crate::i18n::IDIOMAS.join(", "))],
+            ));
+        }
+        if self.ignore_patterns.iter().any(|p| p.trim().is_empty()) {
+            errores.push(t("config.error_patron_vacio"));
+        }
+
+        if errores.is_empty() {
+            Ok(())
+        } else {
+            Err(errores)
+        }
+    }
+
+    /// Reemplaza la configuración por `nueva` si difiere en cualquier campo
+    ///
+    /// # Retorna
+    /// `None` si son idénticas; si no, el resumen de cambios para mostrar.
+    pub fn aplicar_recarga(&mut self, nueva: SentinelConfig) -> Option<Vec<String>> {
+        if *self == nueva {
+            return None;
+        }
+        let cambios = self.resumen_cambios(&nueva);
+        *self = nueva;
+        Some(cambios)
+    }
+
+    /// Genera un resumen legible de lo que cambió entre dos configuraciones
+    ///
+    /// Solo se usa para informar al usuario al recargar en caliente: la recarga
+    /// compara la configuración completa (`aplicar_recarga`). Retorna una línea
+    /// por campo modificado; las claves de API nunca se muestran.
+    pub fn resumen_cambios(&self, nueva: &SentinelConfig) -> Vec<String> {
+        fn lista(cambios: &mut Vec<String>, campo: &str, antes: &[String], despues: &[String]) {
+            let agregados: Vec<&String> = despues.iter().filter(|x| !antes.contains(x)).collect();
+            let eliminados: Vec<&String> = antes.iter().filter(|x| !despues.contains(x)).collect();
+
+            if agregados.is_empty() && eliminados.is_empty() {
+                if antes != despues {
+                    cambios.push(format!("{}: {}", campo, t("config.cambio_orden")));
+                }
+                return;
+            }
+
+            let mut partes = Vec::new();
+            if !agregados.is_empty() {
+                partes.push(format!(
+                    "+{}",
+                    agregados
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", +")
+                ));
+            }
+            if !eliminados.is_empty() {
+                partes.push(format!(
+                    "-{}",
+                    eliminados
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", -")
+                ));
+            }
+            cambios.push(format!("{}: {}", campo, partes.join(" ")));
+        }
+
+        fn valor(cambios: &mut Vec<String>, campo: &str, antes: &str, despues: &str) {
+            if antes != despues {
+                cambios.push(format!("{}: {} → {}", campo, antes, despues));
+            }
+        }
+
+        let mut cambios = Vec::new();
+
+        valor(
+            &mut cambios,
+            "project_name",
+            &self.project_name,
+            &nueva.project_name,
+        );
+        valor(&mut cambios, "framework", &self.framework, &nueva.framework);
+        valor(&mut cambios, "manager", &self.manager, &nueva.manager);
+        valor(
+            &mut cambios,
+            "test_command",
+            &self.test_command,
+            &nueva.test_command,
+        );
+        valor(
+            &mut cambios,
+            "code_language",
+            &self.code_language,
+            &nueva.code_language,
+        );
+        valor(
+            &mut cambios,
+            "language",
+            self.language.as_deref().unwrap_or("auto"),
+            nueva.language.as_deref().unwrap_or("auto"),
+        );
+        lista(
+            &mut cambios,
+            "architecture_rules",
+            &self.architecture_rules,
+            &nueva.architecture_rules,
+        );
+        lista(
+            &mut cambios,
+            "file_extensions",
+            &self.file_extensions,
+            &nueva.file_extensions,
+        );
+        lista(
+            &mut cambios,
+            "ignore_patterns",
+            &self.ignore_patterns,
+            &nueva.ignore_patterns,
+        );
+        lista(
+            &mut cambios,
+            "parent_patterns",
+            &self.parent_patterns,
+            &nueva.parent_patterns,
+        );
+        lista(
+            &mut cambios,
+            "test_patterns",
+            &self.test_patterns,
+            &nueva.test_patterns,
+        );
+
+        lista(
+            &mut cambios,
+            "secret_allowlist",
+            &self.secret_allowlist,
+            &nueva.secret_allowlist,
+        );
+        lista(
+            &mut cambios,
+            "disabled_rules",
+            &self.disabled_rules,
+            &nueva.disabled_rules,
+        );
+        valor(
+            &mut cambios,
+            "fix_max_attempts",
+            &self.fix_max_attempts.to_string(),
+            &nueva.fix_max_attempts.to_string(),
+        );
+        valor(
+            &mut cambios,
+            "collect_coverage",
+            &self.collect_coverage.to_string(),
+            &nueva.collect_coverage.to_string(),
+        );
+        valor(
+            &mut cambios,
+            "test_timeout_secs",
+            &self.test_timeout_secs.to_string(),
+            &nueva.test_timeout_secs.to_string(),
+        );
+        let umbral = |c: &SentinelConfig| {
+            c.coverage_threshold
+                .map(|u| format!("{}%", u))
+                .unwrap_or_else(|| t("config.cambio_ninguno"))
+        };
+        valor(
+            &mut cambios,
+            "coverage_threshold",
+            &umbral(self),
+            &umbral(nueva),
+        );
+        let docs_dir = |c: &SentinelConfig| {
+            c.docs_dir
+                .clone()
+                .unwrap_or_else(|| t("config.cambio_junto_al_codigo"))
+        };
+        valor(&mut cambios, "docs_dir", &docs_dir(self), &docs_dir(nueva));
+        let reports_dir = |c: &SentinelConfig| {
+            c.reports_dir
+                .clone()
+                .unwrap_or_else(|| "docs/reports".to_string())
+        };
+        valor(
+            &mut cambios,
+            "reports_dir",
+            &reports_dir(self),
+            &reports_dir(nueva),
+        );
+        valor(
+            &mut cambios,
+            "inline_doc_comments",
+            &self.inline_doc_comments.to_string(),
+            &nueva.inline_doc_comments.to_string(),
+        );
+        let ids = |c: &SentinelConfig| -> Vec<String> {
+            c.custom_rules.iter().map(|r| r.id.clone()).collect()
+        };
+        lista(&mut cambios, "custom_rules", &ids(self), &ids(nueva));
+        if ids(self) == ids(nueva) && self.custom_rules != nueva.custom_rules {
+            cambios.push(format!(
+                "custom_rules: {}",
+                t("config.cambio_reglas_propias")
+            ));
+        }
+
+        let paquetes = |c: &SentinelConfig| -> Vec<String> {
+            c.packages
+                .iter()
+                .map(|p| format!("{} ({})", p.path, p.framework))
+                .collect()
+        };
+        lista(&mut cambios, "packages", &paquetes(self), &paquetes(nueva));
+
+        let modelos = |c: &SentinelConfig| -> Vec<String> {
+            c.ai_configs
+                .iter()
+                .map(|ai| format!("{} ({})", ai.name, ai.model))
+                .collect()
+        };
+        lista(&mut cambios, "ai_configs", &modelos(self), &modelos(nueva));
+        for antes in &self.ai_configs {
+            let Some(despues) = nueva.ai_configs.iter().find(|ai| ai.name == antes.name) else {
+                continue;
+            };
+            let campo = |nombre: &str| format!("ai_configs[{}].{}", antes.name, nombre);
+            valor(
+                &mut cambios,
+                &campo("provider"),
+                antes.provider.as_str(),
+                despues.provider.as_str(),
+            );
+            valor(
+                &mut cambios,
+                &campo("api_url"),
+                &antes.api_url,
+                &despues.api_url,
+            );
+            if antes.api_key != despues.api_key {
+                cambios.push(format!(
+                    "{}: {}",
+                    campo("api_key"),
+                    t("config.cambio_api_key")
+                ));
+            }
+        }
+
+        if self.use_cache != nueva.use_cache {
+            cambios.push(format!(
+                "use_cache: {} → {}",
+                self.use_cache, nueva.use_cache
+            ));
+        }
+        if self.testing_framework != nueva.testing_framework {
+            cambios.push(format!(
+                "testing_framework: {} → {}",
+                self.testing_framework.as_deref().unwrap_or("N/A"),
+                nueva.testing_framework.as_deref().unwrap_or("N/A")
+            ));
+        }
+        valor(
+            &mut cambios,
+            "testing_status",
+            self.testing_status.as_deref().unwrap_or("N/A"),
+            nueva.testing_status.as_deref().unwrap_or("N/A"),
+        );
+        valor(
+            &mut cambios,
+            "force_ai_detection",
+            &self.force_ai_detection.to_string(),
+            &nueva.force_ai_detection.to_string(),
+        );
+
+        cambios
+    }
+
     pub fn debe_ignorar(&self, path: &Path) -> bool {
         let path_str = path.to_str().unwrap_or("");
 
@@ -504,11 +1027,59 @@ impl SentinelConfig {
             "pnpm".to_string()
         } else if path.join("yarn.lock").exists() {
             "yarn".to_string()
+        } else if path.join("bun.lockb").exists() || path.join("bun.lock").exists() {
+            "bun".to_string()
+        } else if path.join("package.json").exists() {
+            "npm".to_string()
+        } else if path.join("Cargo.toml").exists() {
+            "cargo".to_string()
+        } else if path.join("go.mod").exists() {
+            "go".to_string()
+        } else if path.join("poetry.lock").exists() {
+            "poetry".to_string()
+        } else if path.join("pyproject.toml").exists() || path.join("requirements.txt").exists() {
+            "pip".to_string()
+        } else if path.join("composer.json").exists() {
+            "composer".to_string()
         } else {
             "npm".to_string()
         }
     }
 
+    /// Comando de tests por defecto según el gestor de paquetes
+    /// Reemplaza el `{manager} run test` que versiones anteriores guardaban
+    /// para todos los gestores (`cargo run test`, `go run test`...) por el
+    /// comando por defecto actual, para que no se tome como personalizado
+    ///
+    /// # Retorna
+    /// `true` si cambió algún comando.
+    fn normalizar_comandos_heredados(&mut self) -> bool {
+        fn normalizar(manager: &str, comando: &mut String) -> bool {
+            let por_defecto = SentinelConfig::comando_test_por_defecto(manager);
+            if comando.trim() == format!("{} run test", manager) && *comando != por_defecto {
+                *comando = por_defecto;
+                return true;
+            }
+            false
+        }
+        let mut cambio = normalizar(&self.manager, &mut self.test_command);
+        for paquete in &mut self.packages {
+            cambio |= normalizar(&paquete.manager, &mut paquete.test_command);
+        }
+        cambio
+    }
+
+    pub fn comando_test_por_defecto(manager: &str) -> String {
+        match manager {
+            "cargo" => "cargo test".to_string(),
+            "go" => "go test ./...".to_string(),
+            "poetry" => "poetry run pytest".to_string(),
+            "pip" => "pytest".to_string(),
+            "composer" => "composer test".to_string(),
+            gestor => format!("{} run test", gestor),
+        }
+    }
+
     /// Lista los archivos en la raíz del proyecto (excluyendo node_modules, .git, etc.)
     pub fn listar_archivos_raiz(path: &Path) -> Vec<String> {
         let mut archivos = Vec::new();
@@ -538,8 +1109,192 @@ impl SentinelConfig {
         let config_path = path.join(".sentinelrc.toml");
         if config_path.exists() {
             fs::remove_file(config_path)?;
-            println!("{}", "🗑️  Configuración eliminada correctamente.".yellow());
+            println!("{}", t("config.eliminada").yellow());
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn config_base() -> SentinelConfig {
+        SentinelConfig::default(
+            "demo".to_string(),
+            "npm".to_string(),
+            "NestJS".to_string(),
+            vec!["SOLID".to_string(), "Clean Code".to_string()],
+            vec!["ts".to_string()],
+            "typescript".to_string(),
+            vec![".service.ts".to_string()],
+            vec!["test/{name}/{name}.spec.ts".to_string()],
+        )
+    }
+
+    #[test]
+    fn test_validar_config_valida() {
+        assert!(config_base().validar().is_ok());
+    }
+
+    #[test]
+    fn test_validar_detecta_errores() {
+        let mut config = config_base();
+        config.file_extensions = vec![".ts".to_string()];
+        config.ai_configs.clear();
+        config.ignore_patterns.push(" ".to_string());
+        config.language = Some("fr".to_string());
+
+        let errores = config.validar().unwrap_err();
+        assert_eq!(errores.len(), 4);
+    }
+
+    #[test]
+    fn test_resumen_cambios_sin_cambios() {
+        let config = config_base();
+        assert!(config.resumen_cambios(&config.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_resumen_cambios_reglas_y_modelos() {
+        let antes = config_base();
+        let mut despues = antes.clone();
+        despues.architecture_rules = vec!["SOLID".to_string(), "DDD".to_string()];
+        despues.ai_configs.insert(
+            0,
+            AIConfig {
+                name: "Local".to_string(),
+                provider: AIProvider::Ollama,
+                api_url: "http://localhost:11434/v1".to_string(),
+                api_key: String::new(),
+                model: "llama3".to_string(),
+            },
+        );
+        despues.use_cache = false;
+
+        let cambios = antes.resumen_cambios(&despues);
+        assert!(cambios.contains(&"architecture_rules: +DDD -Clean Code".to_string()));
+        assert!(
+            cambios
+                .iter()
+                .any(|c| c.starts_with("ai_configs: +Local (llama3)"))
+        );
+        assert!(cambios.contains(&"use_cache: true → false".to_string()));
+    }
+
+    #[test]
+    fn test_recarga_aplica_cambio_de_api_key() {
+        let mut actual = config_base();
+        let mut nueva = actual.clone();
+        nueva.ai_configs[0].api_key = "sk-ant-nueva-clave".to_string();
+
+        let cambios = actual.aplicar_recarga(nueva.clone()).unwrap();
+        assert_eq!(actual.ai_configs[0].api_key, "sk-ant-nueva-clave");
+        assert_eq!(
+            cambios,
+            vec![format!(
+                "ai_configs[Claude Default].api_key: {}",
+                t("config.cambio_api_key")
+            )]
+        );
+        assert!(actual.aplicar_recarga(nueva).is_none());
+    }
+
+    #[test]
+    fn test_resumen_cambios_orden_de_modelos() {
+        let mut antes = config_base();
+        antes.ai_configs.push(AIConfig {
+            name: "Gemini".to_string(),
+            provider: AIProvider::Gemini,
+            api_url: "https://generativelanguage.googleapis.com".to_string(),
+            api_key: String::new(),
+            model: "gemini-2.0-flash".to_string(),
+        });
+        let mut despues = antes.clone();
+        despues.ai_configs.reverse();
+
+        assert_eq!(
+            antes.resumen_cambios(&despues),
+            vec![format!("ai_configs: {}", t("config.cambio_orden"))]
+        );
+    }
+
+    #[test]
+    fn test_reglas_efectivas_con_paquete() {
+        let mut config = config_base();
+        config.disabled_rules = vec!["nestjs/guards-for-auth".to_string()];
+        config.custom_rules = vec![ArchitectureRule {
+            id: "team/no-any".to_string(),
+            description: "Prohibido usar any".to_string(),
+            examples: vec![],
+        }];
+
+        let reglas = config.reglas_efectivas();
+        assert!(reglas.iter().any(|r| r.id == "nestjs/thin-controllers"));
+        assert!(!reglas.iter().any(|r| r.id == "nestjs/guards-for-auth"));
+        assert_eq!(reglas.last().unwrap().id, "team/no-any");
+        // Las reglas propias de architecture_rules se agregan al paquete
+        assert!(
+            reglas
+                .iter()
+                .any(|r| r.id == "project/1" && r.description == "SOLID")
+        );
+
+        // Las del perfil detectado ya las cubre el paquete
+        config.architecture_rules = crate::detector::perfil("NestJS").unwrap().rules;
+        let reglas = config.reglas_efectivas();
+        assert!(!reglas.iter().any(|r| r.id.starts_with("project/")));
+    }
+
+    #[test]
+    fn test_reglas_efectivas_sin_paquete() {
+        let mut config = config_base();
+        config.framework = "Generic".to_string();
+        config.disabled_rules = vec!["project/2".to_string()];
+
+        let reglas = config.reglas_efectivas();
+        assert_eq!(reglas.len(), 1);
+        assert_eq!(reglas[0].id, "project/1");
+        assert_eq!(reglas[0].description, "SOLID");
+    }
+
+    #[test]
+    fn test_normaliza_comandos_de_test_heredados() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = config_base();
+        config.manager = "cargo".to_string();
+        config.test_command = "cargo run test".to_string();
+        fs::write(
+            temp_dir.path().join(".sentinelrc.toml"),
+            toml::to_string_pretty(&config).unwrap(),
+        )
+        .unwrap();
+
+        let cargada = SentinelConfig::load(temp_dir.path()).unwrap();
+        assert_eq!(cargada.test_command, "cargo test");
+        // Queda guardado para que la recarga en caliente no lo vea como cambio
+        assert_eq!(
+            SentinelConfig::recargar(temp_dir.path())
+                .unwrap()
+                .test_command,
+            "cargo test"
+        );
+
+        // npm run test es el comando por defecto de npm y no se toca
+        let mut config = config_base();
+        assert!(!config.normalizar_comandos_heredados());
+        assert_eq!(config.test_command, "npm run test");
+    }
+
+    #[test]
+    fn test_recargar_rechaza_config_invalida() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = config_base();
+        config.file_extensions.clear();
+        let toml = toml::to_string_pretty(&config).unwrap();
+        fs::write(temp_dir.path().join(".sentinelrc.toml"), toml).unwrap();
+
+        assert!(SentinelConfig::recargar(temp_dir.path()).is_err());
+    }
+}
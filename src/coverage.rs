@@ -0,0 +1,490 @@
+//! # Cobertura de las líneas modificadas
+//!
+//! Cuando `collect_coverage` o `coverage_threshold` están configurados, los tests
+//! se ejecutan pidiendo al runner un reporte de cobertura en un directorio
+//! temporal. Después se cruza ese reporte con las líneas modificadas del archivo
+//! (según `git diff`) para mostrar las que ningún test ejecuta y, si hay umbral,
+//! bloquear el commit cuando la cobertura queda por debajo.
+//!
+//! Formatos soportados: JSON de istanbul (Jest, Vitest), JSON de coverage.py
+//! (pytest-cov), perfiles de `go test -coverprofile` y lcov en general.
+
+use crate::config::SentinelConfig;
+use crate::i18n::{t, tf};
+use colored::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Cobertura por archivo: número de línea → si algún test la ejecutó
+type Reporte = HashMap<String, BTreeMap<usize, bool>>;
+
+/// Reportes lcov que generan por defecto los runners sin flags dedicados
+const REPORTES_LCOV: &[&str] = &["coverage/lcov.info", "lcov.info"];
+
+/// Resultado de cruzar la cobertura con las líneas modificadas
+#[derive(Debug, PartialEq)]
+pub struct ResumenCobertura {
+    pub cubiertas: usize,
+    pub no_cubiertas: Vec<usize>,
+}
+
+impl ResumenCobertura {
+    /// Porcentaje de líneas modificadas ejecutables que están cubiertas
+    pub fn porcentaje(&self) -> f64 {
+        let total = self.cubiertas + self.no_cubiertas.len();
+        if total == 0 {
+            100.0
+        } else {
+            self.cubiertas as f64 * 100.0 / total as f64
+        }
+    }
+}
+
+/// Directorio temporal donde los runners escriben su reporte
+fn directorio_reportes() -> PathBuf {
+    std::env::temp_dir().join(format!("sentinel-coverage-{}", std::process::id()))
+}
+
+/// Vacía el directorio de reportes y devuelve los argumentos de cobertura para el runner
+///
+/// Los runners sin flags conocidos (Mocha con nyc, Cargo con llvm-cov, PHPUnit...)
+/// no reciben argumentos: se usa el `lcov.info` que generen por su cuenta.
+pub fn argumentos_runner(config: &SentinelConfig) -> Vec<String> {
+    let dir = directorio_reportes();
+    let _ = fs::remove_dir_all(&dir);
+    let _ = fs::create_dir_all(&dir);
+    let ruta = |archivo: &str| dir.join(archivo).to_string_lossy().to_string();
+
+    let runner = config
+        .testing_framework
+        .as_deref()
+        .unwrap_or("Jest")
+        .to_lowercase();
+
+    if runner.contains("vitest") {
+        vec![
+            "--coverage.enabled=true".to_string(),
+            "--coverage.reporter=json".to_string(),
+            format!("--coverage.reportsDirectory={}", dir.to_string_lossy()),
+        ]
+    } else if runner.contains("pytest") {
+        vec![
+            "--cov=.".to_string(),
+            format!("--cov-report=json:{}", ruta("coverage.json")),
+        ]
+    } else if runner == "go" || runner.starts_with("go ") {
+        vec![format!("-coverprofile={}", ruta("coverage.out"))]
+    } else if runner.contains("jest") {
+        vec![
+            "--coverage".to_string(),
+            "--coverageReporters=json".to_string(),
+            format!("--coverageDirectory={}", dir.to_string_lossy()),
+        ]
+    } else {
+        vec![]
+    }
+}
+
+/// Carga el reporte de la última ejecución de tests
+///
+/// Busca primero en el directorio temporal y después los `lcov.info` del
+/// paquete, aceptando estos solo si son posteriores al inicio de la ejecución
+/// (la fecha de creación del directorio temporal) para no usar datos viejos.
+fn cargar_reporte(package_root: &Path) -> Option<Reporte> {
+    let dir = directorio_reportes();
+    let leer = |nombre: &str| fs::read_to_string(dir.join(nombre)).ok();
+
+    if let Some(json) = leer("coverage-final.json") {
+        return parsear_istanbul(&json);
+    }
+    if let Some(json) = leer("coverage.json") {
+        return parsear_coverage_py(&json);
+    }
+    if let Some(perfil) = leer("coverage.out") {
+        return Some(parsear_perfil_go(&perfil));
+    }
+
+    let inicio = fs::metadata(&dir).and_then(|m| m.modified()).ok()?;
+    REPORTES_LCOV.iter().find_map(|nombre| {
+        let ruta = package_root.join(nombre);
+        let reciente = fs::metadata(&ruta)
+            .and_then(|m| m.modified())
+            .is_ok_and(|m| m >= inicio);
+        if !reciente {
+            return None;
+        }
+        fs::read_to_string(ruta).ok().map(|t| parsear_lcov(&t))
+    })
+}
+
+/// Marca una línea; una línea cubierta por algún bloque queda cubierta
+fn marcar(lineas: &mut BTreeMap<usize, bool>, linea: usize, cubierta: bool) {
+    let entrada = lineas.entry(linea).or_insert(false);
+    *entrada |= cubierta;
+}
+
+/// Parsea `coverage-final.json` de istanbul (Jest, Vitest, nyc)
+///
+/// Cada sentencia cuenta en su línea de inicio, igual que el resumen de istanbul.
+fn parsear_istanbul(json: &str) -> Option<Reporte> {
+    let valor: serde_json::Value = serde_json::from_str(json).ok()?;
+    let mut reporte = Reporte::new();
+
+    for (archivo, datos) in valor.as_object()? {
+        let lineas = reporte.entry(archivo.clone()).or_default();
+        let (Some(mapa), Some(conteos)) =
+            (datos["statementMap"].as_object(), datos["s"].as_object())
+        else {
+            continue;
+        };
+        for (id, sentencia) in mapa {
+            if let Some(linea) = sentencia["start"]["line"].as_u64() {
+                let veces = conteos.get(id).and_then(|c| c.as_u64()).unwrap_or(0);
+                marcar(lineas, linea as usize, veces > 0);
+            }
+        }
+    }
+    Some(reporte)
+}
+
+/// Parsea el JSON de coverage.py (`--cov-report=json`)
+fn parsear_coverage_py(json: &str) -> Option<Reporte> {
+    let valor: serde_json::Value = serde_json::from_str(json).ok()?;
+    let mut reporte = Reporte::new();
+
+    for (archivo, datos) in valor["files"].as_object()? {
+        let lineas = reporte.entry(archivo.clone()).or_default();
+        for (campo, cubierta) in [("executed_lines", true), ("missing_lines", false)] {
+            for linea in datos[campo].as_array().into_iter().flatten() {
+                if let Some(linea) = linea.as_u64() {
+                    marcar(lineas, linea as usize, cubierta);
+                }
+            }
+        }
+    }
+    Some(reporte)
+}
+
+/// Parsea un perfil de `go test -coverprofile` (`archivo.go:l1.c1,l2.c2 sentencias veces`)
+fn parsear_perfil_go(perfil: &str) -> Reporte {
+    let mut reporte = Reporte::new();
+
+    for linea in perfil.lines().filter(|l| !l.starts_with("mode:")) {
+        let Some((archivo, resto)) = linea.rsplit_once(':') else {
+            continue;
+        };
+        let campos: Vec<&str> = resto.split_whitespace().collect();
+        let [rango, _, veces] = campos[..] else {
+            continue;
+        };
+        let Some((inicio, fin)) = rango.split_once(',') else {
+            continue;
+        };
+        let numero = |pos: &str| pos.split('.').next().and_then(|l| l.parse::<usize>().ok());
+        if let (Some(inicio), Some(fin)) = (numero(inicio), numero(fin)) {
+            let cubierta = veces.parse::<u64>().unwrap_or(0) > 0;
+            let lineas = reporte.entry(archivo.to_string()).or_default();
+            for l in inicio..=fin {
+                marcar(lineas, l, cubierta);
+            }
+        }
+    }
+    reporte
+}
+
+/// Parsea un reporte lcov (`SF:`, `DA:linea,veces`, `end_of_record`)
+fn parsear_lcov(texto: &str) -> Reporte {
+    let mut reporte = Reporte::new();
+    let mut actual: Option<String> = None;
+
+    for linea in texto.lines().map(str::trim) {
+        if let Some(archivo) = linea.strip_prefix("SF:") {
+            actual = Some(archivo.to_string());
+        } else if let Some(datos) = linea.strip_prefix("DA:")
+            && let Some(archivo) = &actual
+        {
+            let mut campos = datos.split(',');
+            if let (Some(Ok(l)), Some(Ok(veces))) = (
+                campos.next().map(str::parse::<usize>),
+                campos.next().map(str::parse::<u64>),
+            ) {
+                marcar(reporte.entry(archivo.clone()).or_default(), l, veces > 0);
+            }
+        } else if linea == "end_of_record" {
+            actual = None;
+        }
+    }
+    reporte
+}
+
+/// Busca en el reporte la entrada del archivo modificado
+///
+/// Los runners usan rutas absolutas (istanbul), relativas al directorio de
+/// ejecución (coverage.py, lcov) o rutas de import (Go, `modulo/pkg/archivo.go`).
+fn cobertura_de<'a>(
+    reporte: &'a Reporte,
+    changed_path: &Path,
+    package_root: &Path,
+) -> Option<&'a BTreeMap<usize, bool>> {
+    let relativa = changed_path
+        .strip_prefix(package_root)
+        .unwrap_or(changed_path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    reporte.iter().find_map(|(archivo, lineas)| {
+        let ruta = Path::new(archivo);
+        let coincide = ruta == changed_path
+            || package_root.join(ruta) == changed_path
+            || archivo
+                .replace('\\', "/")
+                .ends_with(&format!("/{}", relativa));
+        coincide.then_some(lineas)
+    })
+}
+
+/// Extrae las líneas nuevas o modificadas de un `git diff -U0`
+fn lineas_de_diff(diff: &str) -> BTreeSet<usize> {
+    let mut lineas = BTreeSet::new();
+
+    for hunk in diff.lines().filter(|l| l.starts_with("@@")) {
+        // @@ -a,b +c,d @@: las líneas c..c+d del archivo nuevo
+        let Some(nuevo) = hunk.split_whitespace().find(|p| p.starts_with('+')) else {
+            continue;
+        };
+        let mut partes = nuevo[1..].split(',');
+        let inicio = partes.next().and_then(|n| n.parse::<usize>().ok());
+        let largo = partes.next().map_or(Some(1), |n| n.parse::<usize>().ok());
+        if let (Some(inicio), Some(largo)) = (inicio, largo) {
+            lineas.extend(inicio..inicio + largo);
+        }
+    }
+    lineas
+}
+
+/// Líneas modificadas del archivo respecto a `HEAD`
+///
+/// Un archivo fuera del control de versiones se considera nuevo por completo.
+fn lineas_modificadas(changed_path: &Path, project_path: &Path) -> BTreeSet<usize> {
+    let todas = || {
+        let total = fs::read_to_string(changed_path)
+            .map(|c| c.lines().count())
+            .unwrap_or(0);
+        (1..=total).collect()
+    };
+
+    let versionado = Command::new("git")
+        .args(["ls-files", "--error-unmatch"])
+        .arg(changed_path)
+        .current_dir(project_path)
+        .output()
+        .is_ok_and(|o| o.status.success());
+    if !versionado {
+        return todas();
+    }
+
+    match Command::new("git")
+        .args(["diff", "-U0", "HEAD", "--"])
+        .arg(changed_path)
+        .current_dir(project_path)
+        .output()
+    {
+        Ok(out) if out.status.success() => lineas_de_diff(&String::from_utf8_lossy(&out.stdout)),
+        _ => todas(),
+    }
+}
+
+/// Cruza la cobertura con las líneas modificadas
+///
+/// Las líneas que el reporte no menciona (comentarios, líneas vacías,
+/// declaraciones) no son ejecutables y no cuentan.
+fn resumir(lineas: &BTreeMap<usize, bool>, modificadas: &BTreeSet<usize>) -> ResumenCobertura {
+    let mut resumen = ResumenCobertura {
+        cubiertas: 0,
+        no_cubiertas: vec![],
+    };
+    for linea in modificadas {
+        match lineas.get(linea) {
+            Some(true) => resumen.cubiertas += 1,
+            Some(false) => resumen.no_cubiertas.push(*linea),
+            None => {}
+        }
+    }
+    resumen
+}
+
+/// Agrupa números de línea consecutivos (`3, 7-9, 12`)
+fn formatear_rangos(lineas: &[usize]) -> String {
+    let mut rangos: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < lineas.len() {
+        let inicio = lineas[i];
+        while i + 1 < lineas.len() && lineas[i + 1] == lineas[i] + 1 {
+            i += 1;
+        }
+        if lineas[i] == inicio {
+            rangos.push(inicio.to_string());
+        } else {
+            rangos.push(format!("{}-{}", inicio, lineas[i]));
+        }
+        i += 1;
+    }
+    rangos.join(", ")
+}
+
+/// Muestra la cobertura de las líneas modificadas y aplica el umbral
+///
+/// # Retorna
+///
+/// `false` si hay `coverage_threshold` y la cobertura queda por debajo o no
+/// se encontró reporte para verificarla (el commit no se ofrece). Sin
+/// cobertura activa, `true`.
+pub fn verificar_cobertura(
+    changed_path: &Path,
+    package_root: &Path,
+    project_path: &Path,
+    config: &SentinelConfig,
+) -> bool {
+    if !config.cobertura_activa() {
+        return true;
+    }
+
+    let Some(lineas) = cargar_reporte(package_root)
+        .as_ref()
+        .and_then(|r| cobertura_de(r, changed_path, package_root).cloned())
+    else {
+        println!("{}", t("cobertura.sin_reporte").yellow());
+        // Con umbral, la falta de reporte no puede dejar pasar el commit
+        if let Some(umbral) = config.coverage_threshold {
+            println!(
+                "{}",
+                tf(
+                    "cobertura.umbral_sin_reporte",
+                    &[("umbral", &umbral.to_string())]
+                )
+                .red()
+            );
+            return false;
+        }
+        return true;
+    };
+
+    let resumen = resumir(&lineas, &lineas_modificadas(changed_path, project_path));
+    let total = resumen.cubiertas + resumen.no_cubiertas.len();
+    if total == 0 {
+        println!("{}", t("cobertura.sin_lineas"));
+        return true;
+    }
+
+    let porcentaje = resumen.porcentaje();
+    println!(
+        "{}",
+        tf(
+            "cobertura.resumen",
+            &[
+                ("porcentaje", &format!("{:.1}", porcentaje)),
+                ("cubiertas", &resumen.cubiertas.to_string()),
+                ("total", &total.to_string())
+            ]
+        )
+    );
+    if !resumen.no_cubiertas.is_empty() {
+        println!(
+            "   {} {}",
+            t("cobertura.sin_cubrir").yellow(),
+            formatear_rangos(&resumen.no_cubiertas)
+        );
+    }
+
+    match config.coverage_threshold {
+        Some(umbral) if porcentaje < umbral => {
+            println!(
+                "{}",
+                tf("cobertura.bajo_umbral", &[("umbral", &umbral.to_string())]).red()
+            );
+            false
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsear_formatos() {
+        let istanbul = r#"{"/p/src/a.ts": {"statementMap": {"0": {"start": {"line": 2}}, "1": {"start": {"line": 5}}}, "s": {"0": 3, "1": 0}}}"#;
+        let reporte = parsear_istanbul(istanbul).unwrap();
+        assert_eq!(
+            reporte["/p/src/a.ts"],
+            BTreeMap::from([(2, true), (5, false)])
+        );
+
+        let py = r#"{"files": {"app/a.py": {"executed_lines": [1, 2], "missing_lines": [4]}}}"#;
+        let reporte = parsear_coverage_py(py).unwrap();
+        assert_eq!(
+            reporte["app/a.py"],
+            BTreeMap::from([(1, true), (2, true), (4, false)])
+        );
+
+        let go = "mode: set\nex.com/m/pkg/a.go:3.10,5.2 2 1\nex.com/m/pkg/a.go:5.2,7.3 1 0\n";
+        let reporte = parsear_perfil_go(go);
+        assert_eq!(
+            reporte["ex.com/m/pkg/a.go"],
+            BTreeMap::from([(3, true), (4, true), (5, true), (6, false), (7, false)])
+        );
+
+        let lcov = "SF:src/lib.rs\nDA:1,1\nDA:2,0\nend_of_record\n";
+        let reporte = parsear_lcov(lcov);
+        assert_eq!(
+            reporte["src/lib.rs"],
+            BTreeMap::from([(1, true), (2, false)])
+        );
+    }
+
+    #[test]
+    fn test_cobertura_de_rutas() {
+        let reporte = parsear_perfil_go("mode: set\nex.com/m/pkg/a.go:3.1,3.9 1 1\n");
+        let lineas = cobertura_de(&reporte, Path::new("/p/pkg/a.go"), Path::new("/p"));
+        assert!(lineas.is_some());
+        assert!(cobertura_de(&reporte, Path::new("/p/pkg/b.go"), Path::new("/p")).is_none());
+    }
+
+    #[test]
+    fn test_resumen_lineas_modificadas() {
+        let diff = "@@ -3,0 +4,2 @@\n+a\n+b\n@@ -10 +12 @@\n-x\n+y\n@@ -20,3 +21,0 @@\n";
+        let modificadas = lineas_de_diff(diff);
+        assert_eq!(modificadas, BTreeSet::from([4, 5, 12]));
+
+        let lineas = BTreeMap::from([(4, true), (5, false), (12, false), (30, false)]);
+        let resumen = resumir(&lineas, &modificadas);
+        assert_eq!(resumen.cubiertas, 1);
+        assert_eq!(resumen.no_cubiertas, vec![5, 12]);
+        assert_eq!(formatear_rangos(&[3, 7, 8, 9, 12]), "3, 7-9, 12");
+    }
+
+    #[test]
+    fn test_umbral_sin_reporte_no_deja_pasar() {
+        let mut config = SentinelConfig::default(
+            "demo".to_string(),
+            "npm".to_string(),
+            "NestJS".to_string(),
+            vec![],
+            vec!["ts".to_string()],
+            "typescript".to_string(),
+            vec![],
+            vec![],
+        );
+        let archivo = Path::new("/no-existe/src/a.ts");
+        let raiz = Path::new("/no-existe");
+
+        config.collect_coverage = true;
+        assert!(verificar_cobertura(archivo, raiz, raiz, &config));
+
+        config.coverage_threshold = Some(80.0);
+        assert!(!verificar_cobertura(archivo, raiz, raiz, &config));
+    }
+}
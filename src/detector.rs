@@ -0,0 +1,748 @@
+//! # Detección heurística de frameworks (sin IA)
+//!
+//! Primera pasada determinista antes de consultar a la IA: inspecciona los
+//! manifiestos de dependencias del proyecto (`package.json`, `Cargo.toml`,
+//! `go.mod`, `pyproject.toml`/`requirements.txt`, `composer.json`,
+//! `pom.xml`/`build.gradle` y `*.csproj`) y construye la detección a partir
+//! de una tabla de perfiles integrada en el binario.
+//!
+//! Solo cuando el resultado es ambiguo (varios frameworks en ecosistemas
+//! distintos) o desconocido se recurre a `ai::detectar_framework_con_ia`.
+
+use crate::config::FrameworkDetection;
+use std::fs;
+use std::path::Path;
+
+/// Resultado de la detección local
+#[derive(Debug, Clone)]
+pub enum ResultadoDeteccion {
+    /// Un único framework identificado con certeza
+    Detectado(FrameworkDetection),
+    /// Varios candidatos igual de probables (ej: Django + React en la raíz)
+    Ambiguo(Vec<FrameworkDetection>),
+    /// No se encontró ningún manifiesto reconocible
+    Desconocido,
+}
+
+/// Perfil integrado de un framework soportado
+struct PerfilFramework {
+    framework: &'static str,
+    code_language: &'static str,
+    extensions: &'static [&'static str],
+    parent_patterns: &'static [&'static str],
+    test_patterns: &'static [&'static str],
+    rules: &'static [&'static str],
+}
+
+/// Confianza de un candidato: un framework concreto pesa más que el lenguaje base
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Confianza {
+    Baja,
+    Alta,
+}
+
+const REGLAS_WEB: &[&str] = &[
+    "Separación clara entre capa de presentación, lógica de negocio y acceso a datos",
+    "Validación de toda entrada externa en el borde de la aplicación",
+    "Manejo de errores explícito y consistente",
+    "Funciones pequeñas con una única responsabilidad",
+];
+
+const PERFILES: &[PerfilFramework] = &[
+    PerfilFramework {
+        framework: "NestJS",
+        code_language: "typescript",
+        extensions: &["ts"],
+        parent_patterns: &[
+            ".service.ts",
+            ".controller.ts",
+            ".repository.ts",
+            ".gateway.ts",
+            ".module.ts",
+        ],
+        test_patterns: &["test/{name}/{name}.spec.ts", "src/{name}/{name}.spec.ts"],
+        rules: &[
+            "Inyección de dependencias mediante constructores y providers",
+            "Controladores delgados: la lógica vive en services",
+            "DTOs con class-validator para toda entrada",
+            "Un módulo por dominio funcional",
+        ],
+    },
+    PerfilFramework {
+        framework: "Next.js",
+        code_language: "typescript",
+        extensions: &["ts", "tsx"],
+        parent_patterns: &[],
+        test_patterns: &["src/{name}.test.tsx", "__tests__/{name}.test.tsx"],
+        rules: &[
+            "Server Components por defecto, 'use client' solo cuando es necesario",
+            "Acceso a datos en el servidor, nunca secretos en el cliente",
+            "Rutas y layouts organizados por segmento en app/",
+            "Componentes pequeños y reutilizables",
+        ],
+    },
+    PerfilFramework {
+        framework: "Angular",
+        code_language: "typescript",
+        extensions: &["ts"],
+        parent_patterns: &[".component.ts", ".service.ts", ".module.ts"],
+        test_patterns: &[
+            "src/app/{name}/{name}.component.spec.ts",
+            "src/app/{name}.spec.ts",
+        ],
+        rules: &[
+            "Componentes de presentación sin lógica de negocio",
+            "Estado y acceso HTTP encapsulados en services inyectables",
+            "Suscripciones RxJS gestionadas (async pipe o takeUntil)",
+            "Módulos o standalone components por feature",
+        ],
+    },
+    PerfilFramework {
+        framework: "Vue",
+        code_language: "typescript",
+        extensions: &["vue", "ts", "js"],
+        parent_patterns: &[],
+        test_patterns: &["tests/unit/{name}.spec.ts", "src/__tests__/{name}.spec.ts"],
+        rules: &[
+            "Composition API con composables para lógica reutilizable",
+            "Props tipadas y eventos declarados explícitamente",
+            "Estado global solo a través de Pinia/Vuex",
+            "Componentes de una sola responsabilidad",
+        ],
+    },
+    PerfilFramework {
+        framework: "React",
+        code_language: "typescript",
+        extensions: &["ts", "tsx", "js", "jsx"],
+        parent_patterns: &[],
+        test_patterns: &["src/{name}.test.tsx", "src/__tests__/{name}.test.tsx"],
+        rules: &[
+            "Componentes funcionales y hooks, sin efectos secundarios en el render",
+            "Estado elevado solo lo necesario; evitar prop drilling profundo",
+            "Hooks personalizados para lógica reutilizable",
+            "Listas con keys estables",
+        ],
+    },
+    PerfilFramework {
+        framework: "Express",
+        code_language: "typescript",
+        extensions: &["ts", "js"],
+        parent_patterns: &[".service.ts", ".controller.ts", ".router.ts"],
+        test_patterns: &["test/{name}.test.ts", "__tests__/{name}.test.ts"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Django",
+        code_language: "python",
+        extensions: &["py"],
+        parent_patterns: &["_service.py", "views.py", "models.py"],
+        test_patterns: &["tests/test_{name}.py", "{name}/tests.py"],
+        rules: &[
+            "Fat models, thin views: lógica de dominio fuera de las vistas",
+            "Consultas ORM sin N+1 (select_related/prefetch_related)",
+            "Formularios o serializers para validar entrada",
+            "Configuración sensible fuera de settings.py versionado",
+        ],
+    },
+    PerfilFramework {
+        framework: "FastAPI",
+        code_language: "python",
+        extensions: &["py"],
+        parent_patterns: &["_service.py", "_router.py", "_repository.py"],
+        test_patterns: &["tests/test_{name}.py"],
+        rules: &[
+            "Modelos Pydantic para request y response",
+            "Dependencias inyectadas con Depends",
+            "Routers delgados que delegan en services",
+            "Operaciones de I/O asíncronas de extremo a extremo",
+        ],
+    },
+    PerfilFramework {
+        framework: "Flask",
+        code_language: "python",
+        extensions: &["py"],
+        parent_patterns: &["_service.py", "_routes.py"],
+        test_patterns: &["tests/test_{name}.py"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Laravel",
+        code_language: "php",
+        extensions: &["php"],
+        parent_patterns: &["Controller.php", "Service.php", "Repository.php"],
+        test_patterns: &["tests/Unit/{Name}Test.php", "tests/Feature/{Name}Test.php"],
+        rules: &[
+            "Form Requests para validar entrada",
+            "Controladores delgados, lógica en services o actions",
+            "Eloquent sin consultas N+1 (eager loading)",
+            "Autorización mediante policies y gates",
+        ],
+    },
+    PerfilFramework {
+        framework: "Symfony",
+        code_language: "php",
+        extensions: &["php"],
+        parent_patterns: &["Controller.php", "Service.php", "Repository.php"],
+        test_patterns: &["tests/{Name}Test.php"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Spring Boot",
+        code_language: "java",
+        extensions: &["java"],
+        parent_patterns: &["Service.java", "Controller.java", "Repository.java"],
+        test_patterns: &["src/test/java/{Name}Test.java"],
+        rules: &[
+            "Inyección por constructor, sin @Autowired en campos",
+            "Controladores REST delgados que delegan en @Service",
+            "DTOs validados con Bean Validation",
+            "Transacciones declaradas en la capa de servicio",
+        ],
+    },
+    PerfilFramework {
+        framework: "ASP.NET Core",
+        code_language: "csharp",
+        extensions: &["cs"],
+        parent_patterns: &["Controller.cs", "Service.cs", "Repository.cs"],
+        test_patterns: &["tests/{Name}Tests.cs"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Axum",
+        code_language: "rust",
+        extensions: &["rs"],
+        parent_patterns: &["mod.rs"],
+        test_patterns: &["tests/{name}.rs"],
+        rules: &[
+            "Handlers delgados que delegan en servicios",
+            "Errores propios que implementan IntoResponse",
+            "Estado compartido mediante State y Arc",
+            "Sin unwrap/expect en rutas de producción",
+        ],
+    },
+    PerfilFramework {
+        framework: "Actix",
+        code_language: "rust",
+        extensions: &["rs"],
+        parent_patterns: &["mod.rs"],
+        test_patterns: &["tests/{name}.rs"],
+        rules: &[
+            "Handlers delgados que delegan en servicios",
+            "Errores propios que implementan ResponseError",
+            "Estado compartido mediante web::Data",
+            "Sin unwrap/expect en rutas de producción",
+        ],
+    },
+    PerfilFramework {
+        framework: "Gin",
+        code_language: "go",
+        extensions: &["go"],
+        parent_patterns: &["_service.go", "_handler.go", "_repository.go"],
+        test_patterns: &["{name}_test.go"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Echo",
+        code_language: "go",
+        extensions: &["go"],
+        parent_patterns: &["_service.go", "_handler.go", "_repository.go"],
+        test_patterns: &["{name}_test.go"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Fiber",
+        code_language: "go",
+        extensions: &["go"],
+        parent_patterns: &["_service.go", "_handler.go", "_repository.go"],
+        test_patterns: &["{name}_test.go"],
+        rules: REGLAS_WEB,
+    },
+    // Perfiles de lenguaje base (confianza baja: no hay framework reconocible)
+    PerfilFramework {
+        framework: "Go",
+        code_language: "go",
+        extensions: &["go"],
+        parent_patterns: &["_service.go", "_handler.go", "_repository.go"],
+        test_patterns: &["{name}_test.go"],
+        rules: &[
+            "Errores devueltos y envueltos con contexto, nunca ignorados",
+            "Interfaces pequeñas definidas por el consumidor",
+            "context.Context como primer parámetro en operaciones de I/O",
+            "Paquetes organizados por dominio, no por tipo",
+        ],
+    },
+    PerfilFramework {
+        framework: "Rust",
+        code_language: "rust",
+        extensions: &["rs"],
+        parent_patterns: &["mod.rs"],
+        test_patterns: &["tests/{name}.rs"],
+        rules: &[
+            "Errores con Result y tipos propios, sin unwrap en producción",
+            "Ownership claro: evitar clones innecesarios",
+            "Módulos pequeños con API pública mínima",
+            "Tipos que hagan irrepresentables los estados inválidos",
+        ],
+    },
+    PerfilFramework {
+        framework: "Node.js",
+        code_language: "javascript",
+        extensions: &["js", "ts"],
+        parent_patterns: &[],
+        test_patterns: &["test/{name}.test.js", "__tests__/{name}.test.js"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Python",
+        code_language: "python",
+        extensions: &["py"],
+        parent_patterns: &["_service.py", "_repository.py"],
+        test_patterns: &["tests/test_{name}.py"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "PHP",
+        code_language: "php",
+        extensions: &["php"],
+        parent_patterns: &["Service.php", "Repository.php"],
+        test_patterns: &["tests/{Name}Test.php"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: "Java",
+        code_language: "java",
+        extensions: &["java"],
+        parent_patterns: &["Service.java", "Repository.java"],
+        test_patterns: &["src/test/java/{Name}Test.java"],
+        rules: REGLAS_WEB,
+    },
+    PerfilFramework {
+        framework: ".NET",
+        code_language: "csharp",
+        extensions: &["cs"],
+        parent_patterns: &["Service.cs", "Repository.cs"],
+        test_patterns: &["tests/{Name}Tests.cs"],
+        rules: REGLAS_WEB,
+    },
+];
+
+/// Detecta el framework del proyecto sin consultar a la IA
+///
+/// Cada manifiesto aporta como máximo un candidato (el de mayor prioridad dentro
+/// de su ecosistema). Si un único candidato tiene la confianza más alta, la
+/// detección es concluyente; si varios empatan, es ambigua.
+pub fn detectar_framework_local(project_path: &Path) -> ResultadoDeteccion {
+    let candidatos: Vec<(&str, Confianza)> = [
+        detectar_node(project_path),
+        detectar_rust(project_path),
+        detectar_go(project_path),
+        detectar_python(project_path),
+        detectar_php(project_path),
+        detectar_java(project_path),
+        detectar_dotnet(project_path),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let Some(max) = candidatos.iter().map(|(_, c)| *c).max() else {
+        return ResultadoDeteccion::Desconocido;
+    };
+
+    let mejores: Vec<FrameworkDetection> = candidatos
+        .iter()
+        .filter(|(_, c)| *c == max)
+        .filter_map(|(nombre, _)| perfil(nombre))
+        .collect();
+
+    match mejores.len() {
+        0 => ResultadoDeteccion::Desconocido,
+        1 => ResultadoDeteccion::Detectado(mejores.into_iter().next().unwrap()),
+        _ => ResultadoDeteccion::Ambiguo(mejores),
+    }
+}
+
+/// Construye la detección a partir del perfil integrado de un framework
+///
+/// La búsqueda no distingue mayúsculas (`"nestjs"` y `"NestJS"` son equivalentes).
+pub fn perfil(framework: &str) -> Option<FrameworkDetection> {
+    PERFILES
+        .iter()
+        .find(|p| p.framework.eq_ignore_ascii_case(framework))
+        .map(|p| FrameworkDetection {
+            framework: p.framework.to_string(),
+            rules: p.rules.iter().map(|s| s.to_string()).collect(),
+            extensions: p.extensions.iter().map(|s| s.to_string()).collect(),
+            code_language: p.code_language.to_string(),
+            parent_patterns: p.parent_patterns.iter().map(|s| s.to_string()).collect(),
+            test_patterns: p.test_patterns.iter().map(|s| s.to_string()).collect(),
+        })
+}
+
+/// Perfil genérico usado cuando ni la heurística ni la IA pudieron decidir
+pub fn perfil_generico() -> FrameworkDetection {
+    FrameworkDetection {
+        framework: "Generic".to_string(),
+        rules: vec![
+            "Clean Code principles".to_string(),
+            "SOLID design patterns".to_string(),
+            "Code maintainability".to_string(),
+            "Comprehensive testing".to_string(),
+        ],
+        extensions: vec!["js".to_string(), "ts".to_string()],
+        code_language: "typescript".to_string(),
+        parent_patterns: vec![],
+        test_patterns: vec!["{name}.test.ts".to_string(), "{name}.spec.ts".to_string()],
+    }
+}
+
+/// Detecta el runner de tests de un proyecto (o paquete) a partir de sus manifiestos
+///
+/// Retorna el nombre usado en `testing_framework` (ej: "Jest", "Vitest", "Pytest").
+/// Usado para los paquetes de un monorepo, donde no se consulta a la IA.
+pub fn detectar_test_runner(project_path: &Path) -> Option<String> {
+    if let Ok(content) = fs::read_to_string(project_path.join("package.json"))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+    {
+        let tiene = |dep: &str| {
+            ["dependencies", "devDependencies"]
+                .iter()
+                .any(|seccion| json[seccion].get(dep).is_some())
+        };
+        let script_test = json["scripts"]["test"].as_str().unwrap_or("");
+
+        for (dep, runner) in [("vitest", "Vitest"), ("jest", "Jest"), ("mocha", "Mocha")] {
+            if tiene(dep) || script_test.contains(dep) {
+                return Some(runner.to_string());
+            }
+        }
+    }
+
+    let mut python = String::new();
+    for archivo in [
+        "pyproject.toml",
+        "requirements.txt",
+        "requirements-dev.txt",
+        "pytest.ini",
+    ] {
+        if let Ok(texto) = fs::read_to_string(project_path.join(archivo)) {
+            python.push_str(&texto.to_lowercase());
+        }
+    }
+    if project_path.join("pytest.ini").exists() || contiene_dependencia(&python, "pytest") {
+        return Some("Pytest".to_string());
+    }
+
+    if project_path.join("go.mod").exists() {
+        return Some("Go test".to_string());
+    }
+    if project_path.join("Cargo.toml").exists() {
+        return Some("Cargo test".to_string());
+    }
+
+    if let Ok(content) = fs::read_to_string(project_path.join("composer.json")) {
+        if content.contains("pestphp/pest") {
+            return Some("Pest".to_string());
+        }
+        if content.contains("phpunit/phpunit") {
+            return Some("PHPUnit".to_string());
+        }
+    }
+
+    if fs::read_to_string(project_path.join("pom.xml")).is_ok_and(|c| c.contains("junit")) {
+        return Some("JUnit".to_string());
+    }
+
+    None
+}
+
+fn detectar_node(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let content = fs::read_to_string(project_path.join("package.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+
+    let tiene = |dep: &str| {
+        ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .any(|seccion| json[seccion].get(dep).is_some())
+    };
+
+    // El orden define la jerarquía: el meta-framework gana a la librería base
+    let jerarquia = [
+        ("@nestjs/core", "NestJS"),
+        ("next", "Next.js"),
+        ("@angular/core", "Angular"),
+        ("vue", "Vue"),
+        ("react", "React"),
+        ("express", "Express"),
+    ];
+
+    match jerarquia.iter().find(|(dep, _)| tiene(dep)) {
+        Some((_, framework)) => Some((framework, Confianza::Alta)),
+        None => Some(("Node.js", Confianza::Baja)),
+    }
+}
+
+fn detectar_rust(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let content = fs::read_to_string(project_path.join("Cargo.toml")).ok()?;
+    let manifest: toml::Value = toml::from_str(&content).ok()?;
+
+    let tiene = |dep: &str| {
+        ["dependencies", "dev-dependencies"]
+            .iter()
+            .any(|seccion| manifest.get(seccion).and_then(|d| d.get(dep)).is_some())
+            || manifest
+                .get("workspace")
+                .and_then(|w| w.get("dependencies"))
+                .and_then(|d| d.get(dep))
+                .is_some()
+    };
+
+    if tiene("axum") {
+        Some(("Axum", Confianza::Alta))
+    } else if tiene("actix-web") {
+        Some(("Actix", Confianza::Alta))
+    } else {
+        Some(("Rust", Confianza::Baja))
+    }
+}
+
+fn detectar_go(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let content = fs::read_to_string(project_path.join("go.mod")).ok()?;
+
+    if content.contains("github.com/gin-gonic/gin") {
+        Some(("Gin", Confianza::Alta))
+    } else if content.contains("github.com/labstack/echo") {
+        Some(("Echo", Confianza::Alta))
+    } else if content.contains("github.com/gofiber/fiber") {
+        Some(("Fiber", Confianza::Alta))
+    } else {
+        Some(("Go", Confianza::Baja))
+    }
+}
+
+fn detectar_python(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let mut content = String::new();
+    for archivo in ["pyproject.toml", "requirements.txt", "Pipfile", "setup.py"] {
+        if let Ok(texto) = fs::read_to_string(project_path.join(archivo)) {
+            content.push_str(&texto.to_lowercase());
+            content.push('\n');
+        }
+    }
+    if content.is_empty() {
+        return None;
+    }
+
+    if contiene_dependencia(&content, "django") {
+        Some(("Django", Confianza::Alta))
+    } else if contiene_dependencia(&content, "fastapi") {
+        Some(("FastAPI", Confianza::Alta))
+    } else if contiene_dependencia(&content, "flask") {
+        Some(("Flask", Confianza::Alta))
+    } else {
+        Some(("Python", Confianza::Baja))
+    }
+}
+
+fn detectar_php(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let content = fs::read_to_string(project_path.join("composer.json")).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let tiene = |dep: &str| json["require"].get(dep).is_some();
+
+    if tiene("laravel/framework") {
+        Some(("Laravel", Confianza::Alta))
+    } else if tiene("symfony/framework-bundle") {
+        Some(("Symfony", Confianza::Alta))
+    } else {
+        Some(("PHP", Confianza::Baja))
+    }
+}
+
+fn detectar_java(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let mut content = String::new();
+    for archivo in ["pom.xml", "build.gradle", "build.gradle.kts"] {
+        if let Ok(texto) = fs::read_to_string(project_path.join(archivo)) {
+            content.push_str(&texto);
+        }
+    }
+    if content.is_empty() {
+        return None;
+    }
+
+    if content.contains("spring-boot") || content.contains("org.springframework.boot") {
+        Some(("Spring Boot", Confianza::Alta))
+    } else {
+        Some(("Java", Confianza::Baja))
+    }
+}
+
+fn detectar_dotnet(project_path: &Path) -> Option<(&'static str, Confianza)> {
+    let csproj = fs::read_dir(project_path)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .find(|p| p.extension().and_then(|e| e.to_str()) == Some("csproj"))?;
+    let content = fs::read_to_string(csproj).ok()?;
+
+    if content.contains("Microsoft.NET.Sdk.Web") || content.contains("Microsoft.AspNetCore") {
+        Some(("ASP.NET Core", Confianza::Alta))
+    } else {
+        Some((".NET", Confianza::Baja))
+    }
+}
+
+/// Verifica si `nombre` aparece como dependencia completa (no como prefijo de otra)
+///
+/// `fastapi` coincide en `fastapi==0.110` o `"fastapi>=0.1"`, pero no en `fastapi-users`.
+fn contiene_dependencia(texto: &str, nombre: &str) -> bool {
+    let es_parte_de_nombre = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+
+    texto.match_indices(nombre).any(|(inicio, _)| {
+        let antes = texto[..inicio].chars().next_back();
+        let despues = texto[inicio + nombre.len()..].chars().next();
+        !antes.is_some_and(es_parte_de_nombre) && !despues.is_some_and(es_parte_de_nombre)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn framework(resultado: ResultadoDeteccion) -> String {
+        match resultado {
+            ResultadoDeteccion::Detectado(d) => d.framework,
+            otro => panic!("se esperaba detección concluyente, se obtuvo {:?}", otro),
+        }
+    }
+
+    #[test]
+    fn test_detecta_nestjs_por_dependencias() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies": {"@nestjs/core": "^10.0.0", "express": "^4.0.0"}}"#,
+        )
+        .unwrap();
+
+        let resultado = detectar_framework_local(temp_dir.path());
+        assert_eq!(framework(resultado), "NestJS");
+    }
+
+    #[test]
+    fn test_next_tiene_prioridad_sobre_react() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies": {"react": "18.0.0", "next": "14.0.0"}}"#,
+        )
+        .unwrap();
+
+        let deteccion = match detectar_framework_local(temp_dir.path()) {
+            ResultadoDeteccion::Detectado(d) => d,
+            otro => panic!("{:?}", otro),
+        };
+        assert_eq!(deteccion.framework, "Next.js");
+        assert!(deteccion.extensions.contains(&"tsx".to_string()));
+    }
+
+    #[test]
+    fn test_detecta_django_en_requirements() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("requirements.txt"),
+            "Django==5.0\npsycopg2>=2.9\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            framework(detectar_framework_local(temp_dir.path())),
+            "Django"
+        );
+    }
+
+    #[test]
+    fn test_detecta_axum_en_cargo_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"api\"\n\n[dependencies]\naxum = \"0.7\"\ntokio = \"1\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(framework(detectar_framework_local(temp_dir.path())), "Axum");
+    }
+
+    #[test]
+    fn test_framework_gana_a_lenguaje_base() {
+        let temp_dir = TempDir::new().unwrap();
+        // package.json solo con herramientas de formato: no define el framework
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"devDependencies": {"prettier": "3.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("pyproject.toml"),
+            "[project]\ndependencies = [\"fastapi>=0.110\"]\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            framework(detectar_framework_local(temp_dir.path())),
+            "FastAPI"
+        );
+    }
+
+    #[test]
+    fn test_proyecto_ambiguo() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"dependencies": {"react": "18.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("requirements.txt"), "django\n").unwrap();
+
+        match detectar_framework_local(temp_dir.path()) {
+            ResultadoDeteccion::Ambiguo(candidatos) => assert_eq!(candidatos.len(), 2),
+            otro => panic!("se esperaba ambigüedad, se obtuvo {:?}", otro),
+        }
+    }
+
+    #[test]
+    fn test_proyecto_desconocido() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            detectar_framework_local(temp_dir.path()),
+            ResultadoDeteccion::Desconocido
+        ));
+    }
+
+    #[test]
+    fn test_detectar_test_runner() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{"devDependencies": {"vitest": "1.0.0"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            detectar_test_runner(temp_dir.path()),
+            Some("Vitest".to_string())
+        );
+
+        let go_dir = TempDir::new().unwrap();
+        fs::write(go_dir.path().join("go.mod"), "module example.com/api\n").unwrap();
+        assert_eq!(
+            detectar_test_runner(go_dir.path()),
+            Some("Go test".to_string())
+        );
+    }
+
+    #[test]
+    fn test_contiene_dependencia_respeta_limites() {
+        assert!(contiene_dependencia("fastapi==0.110\n", "fastapi"));
+        assert!(!contiene_dependencia("fastapi-users==1.0\n", "fastapi"));
+        assert!(contiene_dependencia("\"django>=4\"", "django"));
+    }
+}
@@ -0,0 +1,207 @@
+//! # Diffs de texto por líneas
+//!
+//! Genera diffs en formato unificado para mostrar al usuario los cambios que
+//! Sentinel propone escribir en disco (tests generados, correcciones, etc.)
+//! antes de pedir su aprobación.
+
+use colored::*;
+
+/// Líneas de contexto alrededor de cada cambio
+const LINEAS_CONTEXTO: usize = 3;
+
+/// Límite de celdas de la tabla LCS; por encima se muestra un reemplazo completo
+const MAX_CELDAS_LCS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operacion {
+    Igual,
+    Eliminada,
+    Agregada,
+}
+
+/// Genera un diff unificado entre dos versiones de un archivo
+///
+/// `ruta` se usa en las cabeceras `---`/`+++`. Si `antes` está vacío la
+/// cabecera indica un archivo nuevo (`/dev/null`).
+///
+/// # Retorna
+/// El diff como texto, o un string vacío si no hay diferencias.
+pub fn diff_unificado(antes: &str, despues: &str, ruta: &str) -> String {
+    let a: Vec<&str> = antes.lines().collect();
+    let b: Vec<&str> = despues.lines().collect();
+    let ops = calcular_operaciones(&a, &b);
+
+    if ops.iter().all(|(op, _)| *op == Operacion::Igual) {
+        return String::new();
+    }
+
+    let origen = if a.is_empty() {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{}", ruta)
+    };
+    let mut salida = format!("--- {}\n+++ b/{}\n", origen, ruta);
+
+    // Agrupar cambios cercanos en hunks con contexto
+    let cambios: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _))| *op != Operacion::Igual)
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut i = 0;
+    while i < cambios.len() {
+        let inicio = cambios[i].saturating_sub(LINEAS_CONTEXTO);
+        let mut fin = cambios[i];
+        while i + 1 < cambios.len() && cambios[i + 1] <= fin + 2 * LINEAS_CONTEXTO + 1 {
+            i += 1;
+            fin = cambios[i];
+        }
+        let fin = (fin + LINEAS_CONTEXTO + 1).min(ops.len());
+        i += 1;
+
+        // Posiciones de inicio en cada archivo (1-indexadas)
+        let linea_a = ops[..inicio]
+            .iter()
+            .filter(|(op, _)| *op != Operacion::Agregada)
+            .count();
+        let linea_b = ops[..inicio]
+            .iter()
+            .filter(|(op, _)| *op != Operacion::Eliminada)
+            .count();
+        let hunk = &ops[inicio..fin];
+        let largo_a = hunk
+            .iter()
+            .filter(|(op, _)| *op != Operacion::Agregada)
+            .count();
+        let largo_b = hunk
+            .iter()
+            .filter(|(op, _)| *op != Operacion::Eliminada)
+            .count();
+
+        salida.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if largo_a == 0 { linea_a } else { linea_a + 1 },
+            largo_a,
+            if largo_b == 0 { linea_b } else { linea_b + 1 },
+            largo_b
+        ));
+        for (op, linea) in hunk {
+            let prefijo = match op {
+                Operacion::Igual => ' ',
+                Operacion::Eliminada => '-',
+                Operacion::Agregada => '+',
+            };
+            salida.push(prefijo);
+            salida.push_str(linea);
+            salida.push('\n');
+        }
+    }
+
+    salida
+}
+
+/// Imprime un diff unificado con colores (verde agregadas, rojo eliminadas)
+pub fn mostrar_diff(diff: &str) {
+    for linea in diff.lines() {
+        if linea.starts_with("+++") || linea.starts_with("---") {
+            println!("{}", linea.bold());
+        } else if linea.starts_with("@@") {
+            println!("{}", linea.cyan());
+        } else if linea.starts_with('+') {
+            println!("{}", linea.green());
+        } else if linea.starts_with('-') {
+            println!("{}", linea.red());
+        } else {
+            println!("{}", linea.dimmed());
+        }
+    }
+}
+
+/// Secuencia de operaciones por línea usando la subsecuencia común más larga
+fn calcular_operaciones<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<(Operacion, &'a str)> {
+    // Recortar prefijo y sufijo comunes reduce la tabla en los casos habituales
+    let prefijo = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let sufijo = a[prefijo..]
+        .iter()
+        .rev()
+        .zip(b[prefijo..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let medio_a = &a[prefijo..a.len() - sufijo];
+    let medio_b = &b[prefijo..b.len() - sufijo];
+
+    let mut ops: Vec<(Operacion, &str)> = a[..prefijo]
+        .iter()
+        .map(|l| (Operacion::Igual, *l))
+        .collect();
+
+    let (n, m) = (medio_a.len(), medio_b.len());
+    if (n + 1) * (m + 1) > MAX_CELDAS_LCS {
+        ops.extend(medio_a.iter().map(|l| (Operacion::Eliminada, *l)));
+        ops.extend(medio_b.iter().map(|l| (Operacion::Agregada, *l)));
+    } else {
+        // tabla[i][j] = LCS de medio_a[i..] y medio_b[j..]
+        let mut tabla = vec![vec![0usize; m + 1]; n + 1];
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                tabla[i][j] = if medio_a[i] == medio_b[j] {
+                    tabla[i + 1][j + 1] + 1
+                } else {
+                    tabla[i + 1][j].max(tabla[i][j + 1])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < n && j < m {
+            if medio_a[i] == medio_b[j] {
+                ops.push((Operacion::Igual, medio_a[i]));
+                i += 1;
+                j += 1;
+            } else if tabla[i + 1][j] >= tabla[i][j + 1] {
+                ops.push((Operacion::Eliminada, medio_a[i]));
+                i += 1;
+            } else {
+                ops.push((Operacion::Agregada, medio_b[j]));
+                j += 1;
+            }
+        }
+        ops.extend(medio_a[i..].iter().map(|l| (Operacion::Eliminada, *l)));
+        ops.extend(medio_b[j..].iter().map(|l| (Operacion::Agregada, *l)));
+    }
+
+    ops.extend(a[a.len() - sufijo..].iter().map(|l| (Operacion::Igual, *l)));
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_archivo_nuevo() {
+        let diff = diff_unificado("", "a\nb\n", "test/a.spec.ts");
+        assert_eq!(
+            diff,
+            "--- /dev/null\n+++ b/test/a.spec.ts\n@@ -0,0 +1,2 @@\n+a\n+b\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_cambio_con_contexto() {
+        let antes = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let despues = "1\n2\n3\n4\n5\ncinco\n7\n8\n9\n10\n";
+        let diff = diff_unificado(antes, despues, "x.txt");
+        assert_eq!(
+            diff,
+            "--- a/x.txt\n+++ b/x.txt\n@@ -3,7 +3,7 @@\n 3\n 4\n 5\n-6\n+cinco\n 7\n 8\n 9\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_sin_cambios() {
+        assert!(diff_unificado("a\nb", "a\nb", "x").is_empty());
+    }
+}
@@ -1,53 +1,725 @@
 //! Módulo de documentación
 //!
 //! Funciones para generar documentación automática de archivos modificados.
+//!
+//! Cada documento lleva el hash del código fuente del que se generó: si el
+//! archivo no cambió, no se vuelve a consultar a la IA. Las secciones escritas
+//! a mano entre `<!-- sentinel:manual -->` y `<!-- /sentinel:manual -->` se
+//! conservan al regenerar.
+//!
+//! `sentinel docs build` documenta todos los archivos vigilados y los enlaza
+//! en un índice (`INDEX.md`) y una visión general de la arquitectura
+//! (`ARCHITECTURE.md`) con las dependencias entre módulos.
 
 use crate::ai;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::files;
+use crate::graph::GrafoDependencias;
+use crate::i18n::{self, t, tf};
+use crate::workspace;
 use colored::*;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::SentinelConfig;
 use crate::stats::SentinelStats;
 
+/// Inicio de una sección escrita a mano (se conserva al regenerar)
+pub const INICIO_MANUAL: &str = "<!-- sentinel:manual -->";
+/// Fin de una sección escrita a mano
+pub const FIN_MANUAL: &str = "<!-- /sentinel:manual -->";
+/// Prefijo del comentario que guarda el hash del código documentado
+const PREFIJO_HASH: &str = "<!-- sentinel:hash=";
+
+/// Índice y visión general (dentro de `docs_dir`, o de `docs/` si no está definido)
+const ARCHIVO_INDICE: &str = "INDEX.md";
+const ARCHIVO_ARQUITECTURA: &str = "ARCHITECTURE.md";
+
+/// Caracteres máximos del resumen de cada archivo en el prompt de arquitectura
+const MAX_RESUMEN_ARCHIVO: usize = 600;
+
 /// Genera un "manual de bolsillo" automático para cada archivo modificado.
+///
+/// # Retorna
+/// Ruta del documento (también cuando no se regeneró porque el código no cambió).
 pub fn actualizar_documentacion(
     codigo: &str,
     file_path: &Path,
     config: &SentinelConfig,
     stats: Arc<Mutex<SentinelStats>>,
     project_path: &Path,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<PathBuf> {
     let file_name = file_path.file_name().unwrap().to_str().unwrap();
+    let docs_path = ruta_documento(file_path, project_path, config);
+    let anterior = fs::read_to_string(&docs_path).unwrap_or_default();
+    let hash = hash_codigo(codigo);
+
+    if hash_registrado(&anterior) == Some(hash.as_str()) {
+        println!(
+            "{}",
+            tf(
+                "docs.sin_cambios",
+                &[("ruta", &docs_path.display().to_string())]
+            )
+            .dimmed()
+        );
+        return Ok(docs_path);
+    }
+
     println!(
-        "📚 Actualizando manual de bolsillo para: {}",
-        file_name.magenta()
+        "{}",
+        tf(
+            "docs.actualizando",
+            &[("archivo", &file_name.magenta().to_string())]
+        )
     );
 
-    let prompt = format!(
-        "Como documentador técnico de NestJS, analiza este código: {}. \
-        Genera un resumen técnico ultra-conciso (máximo 150 palabras) en Markdown. \
-        Enfócate en: ¿Qué hace este servicio? y ¿Cuáles son sus métodos principales? \
-        Usa emojis para las secciones. No uses introducciones innecesarias.\n\n{}",
-        file_name, codigo
+    let prompt = prompts::renderizar(
+        "docs",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("language", &config.code_language),
+            ("file_name", file_name),
+            ("code", &delimitar("CODIGO", codigo)),
+        ],
     );
 
     let resumen =
         ai::consultar_ia_dinamico(prompt, ai::TaskType::Light, config, stats, project_path)?;
 
-    let mut docs_path = file_path.to_path_buf();
-    docs_path.set_extension("md");
-
-    let nueva_doc = format!(
-        "# 📖 Documentación: {}\n\n> ✨ Actualizado automáticamente por Sentinel v{}\n\n{}\n\n---\n*Último refactor: {:?}*",
-        file_name,
-        crate::config::SENTINEL_VERSION,
-        resumen,
-        std::time::SystemTime::now()
+    let segundos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let nueva_doc = componer_documento(
+        &format!("📖 {}", tf("docs.titulo", &[("archivo", file_name)])),
+        resumen.trim(),
+        &secciones_manuales(&anterior),
+        &hash,
+        &fecha_iso8601(segundos),
     );
 
+    if let Some(dir) = docs_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
     fs::write(&docs_path, nueva_doc)?;
-    println!("   ✅ Documento generado: {}", docs_path.display());
+    println!(
+        "{}",
+        tf(
+            "docs.generado",
+            &[("ruta", &docs_path.display().to_string())]
+        )
+    );
+    Ok(docs_path)
+}
+
+/// Ruta del documento de un archivo fuente
+///
+/// Sin `docs_dir` se escribe junto al código (`users.service.md`); con
+/// `docs_dir = "docs/api"` se replica el árbol del proyecto
+/// (`docs/api/src/users/users.service.md`).
+pub fn ruta_documento(file_path: &Path, project_path: &Path, config: &SentinelConfig) -> PathBuf {
+    let mut ruta = match &config.docs_dir {
+        Some(dir) => {
+            let relativa = file_path
+                .strip_prefix(project_path)
+                .unwrap_or_else(|_| Path::new(file_path.file_name().unwrap_or_default()));
+            project_path.join(dir).join(relativa)
+        }
+        None => file_path.to_path_buf(),
+    };
+    ruta.set_extension("md");
+    ruta
+}
+
+fn hash_codigo(codigo: &str) -> String {
+    format!("{:016x}", crate::secrets::fnv1a(codigo.as_bytes()))
+}
+
+/// Hash del código con el que se generó un documento existente
+fn hash_registrado(documento: &str) -> Option<&str> {
+    let inicio = documento.find(PREFIJO_HASH)? + PREFIJO_HASH.len();
+    let fin = documento[inicio..].find("-->")?;
+    Some(documento[inicio..inicio + fin].trim())
+}
+
+/// Secciones manuales de un documento, con sus marcadores
+///
+/// Una sección sin marcador de cierre se conserva hasta el final del documento
+/// para no perder texto escrito a mano.
+fn secciones_manuales(documento: &str) -> Vec<String> {
+    let mut secciones = Vec::new();
+    let mut resto = documento;
+    while let Some(inicio) = resto.find(INICIO_MANUAL) {
+        let desde = &resto[inicio..];
+        match desde.find(FIN_MANUAL) {
+            Some(fin) => {
+                let fin = fin + FIN_MANUAL.len();
+                secciones.push(desde[..fin].to_string());
+                resto = &desde[fin..];
+            }
+            None => {
+                secciones.push(format!("{}\n{}", desde.trim_end(), FIN_MANUAL));
+                break;
+            }
+        }
+    }
+    secciones
+}
+
+fn componer_documento(
+    titulo: &str,
+    cuerpo: &str,
+    manuales: &[String],
+    hash: &str,
+    fecha: &str,
+) -> String {
+    let manuales = if manuales.is_empty() {
+        // Sección vacía para que el equipo sepa dónde escribir
+        format!("{}\n\n{}", INICIO_MANUAL, FIN_MANUAL)
+    } else {
+        manuales.join("\n\n")
+    };
+    format!(
+        "# {}\n\n> ✨ {}\n\n{}\n\n{}\n\n---\n*{}*\n{}{} -->\n",
+        titulo,
+        tf(
+            "docs.generado_por",
+            &[("version", crate::config::SENTINEL_VERSION)]
+        ),
+        cuerpo,
+        manuales,
+        tf("docs.ultima_actualizacion", &[("fecha", fecha)]),
+        PREFIJO_HASH,
+        hash
+    )
+}
+
+/// Texto generado de un documento (sin título, pie ni secciones manuales)
+fn resumen_de(documento: &str) -> String {
+    documento
+        .lines()
+        .skip_while(|linea| !linea.starts_with("> "))
+        .skip(1)
+        .take_while(|linea| !linea.starts_with(INICIO_MANUAL) && *linea != "---")
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+// ---------------------------------------------------------------------------
+// sentinel docs build
+// ---------------------------------------------------------------------------
+
+/// Documento de un archivo fuente, para el índice y la visión general
+struct EntradaDocumento {
+    /// Ruta relativa del fuente (con '/')
+    fuente: String,
+    documento: PathBuf,
+    resumen: String,
+}
+
+/// Ejecuta `sentinel docs build`
+///
+/// Documenta todos los archivos vigilados (los que no cambiaron reutilizan su
+/// documento), y genera `INDEX.md` y `ARCHITECTURE.md`. Con `mermaid` la visión
+/// general incluye un diagrama de dependencias entre módulos.
+///
+/// # Retorna
+/// Código de salida: 0 si todo se generó, 1 si algún documento falló, 2 sin configuración.
+pub fn construir(project_path: &Path, mermaid: bool) -> i32 {
+    let Some(config) = SentinelConfig::load(project_path) else {
+        eprintln!("{}", t("docs.sin_config").red().bold());
+        return 2;
+    };
+    i18n::establecer(config.language.as_deref());
+    let stats = Arc::new(Mutex::new(SentinelStats::cargar(project_path)));
+
+    let fuentes = listar_fuentes(project_path, &config);
+    if fuentes.is_empty() {
+        println!("{}", t("docs.sin_fuentes").yellow());
+        return 0;
+    }
+    println!(
+        "{}",
+        tf("docs.construyendo", &[("n", &fuentes.len().to_string())])
+            .bright_cyan()
+            .bold()
+    );
+
+    let mut entradas = Vec::new();
+    let mut fallidos = 0;
+    for fuente in &fuentes {
+        let contexto = workspace::contexto_para(&config, project_path, fuente);
+        let resultado = fs::read_to_string(fuente)
+            .map_err(anyhow::Error::from)
+            .and_then(|codigo| {
+                actualizar_documentacion(
+                    &codigo,
+                    fuente,
+                    &contexto.config,
+                    Arc::clone(&stats),
+                    project_path,
+                )
+            });
+        match resultado {
+            Ok(documento) => entradas.push(EntradaDocumento {
+                fuente: ruta_relativa(project_path, fuente),
+                resumen: resumen_de(&fs::read_to_string(&documento).unwrap_or_default()),
+                documento,
+            }),
+            Err(e) => {
+                fallidos += 1;
+                println!(
+                    "{}",
+                    tf(
+                        "docs.error_archivo",
+                        &[
+                            ("archivo", &ruta_relativa(project_path, fuente)),
+                            ("error", &e.to_string())
+                        ]
+                    )
+                    .red()
+                );
+            }
+        }
+    }
+
+    let directorio = project_path.join(config.docs_dir.as_deref().unwrap_or("docs"));
+    let dependencias =
+        dependencias_entre_modulos(&GrafoDependencias::cargar(project_path).dependencias());
+    let resultado = fs::create_dir_all(&directorio)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| {
+            let indice = directorio.join(ARCHIVO_INDICE);
+            fs::write(&indice, generar_indice(&entradas, &directorio))?;
+            println!(
+                "{}",
+                tf("docs.generado", &[("ruta", &indice.display().to_string())])
+            );
+            if entradas.is_empty() {
+                return Ok(());
+            }
+            generar_arquitectura(
+                project_path,
+                &config,
+                &directorio,
+                &entradas,
+                &dependencias,
+                mermaid,
+                Arc::clone(&stats),
+            )
+        });
+    stats.lock().unwrap().guardar(project_path);
+
+    if let Err(e) = resultado {
+        eprintln!(
+            "{}",
+            tf("docs.error_indice", &[("error", &e.to_string())]).red()
+        );
+        return 1;
+    }
+    if fallidos > 0 { 1 } else { 0 }
+}
+
+/// Archivos fuente vigilados (mismos filtros que el monitor), ordenados
+fn listar_fuentes(project_path: &Path, config: &SentinelConfig) -> Vec<PathBuf> {
+    fn recorrer(dir: &Path, config: &SentinelConfig, salida: &mut Vec<PathBuf>) {
+        let Ok(entradas) = fs::read_dir(dir) else {
+            return;
+        };
+        for entrada in entradas.flatten() {
+            let ruta = entrada.path();
+            let nombre = entrada.file_name().to_string_lossy().to_string();
+            if ruta.is_dir() {
+                if !nombre.starts_with('.') && !config.ignore_patterns.contains(&nombre) {
+                    recorrer(&ruta, config, salida);
+                }
+            } else if !config.debe_ignorar(&ruta) && !files::es_archivo_test(&nombre) {
+                salida.push(ruta);
+            }
+        }
+    }
+
+    let mut fuentes = Vec::new();
+    for dir in workspace::directorios_a_vigilar(config, project_path) {
+        recorrer(&dir, config, &mut fuentes);
+    }
+    fuentes.sort();
+    fuentes.dedup();
+    fuentes
+}
+
+fn ruta_relativa(project_path: &Path, path: &Path) -> String {
+    path.strip_prefix(project_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Módulo de un archivo: su directorio relativo ("." en la raíz)
+fn modulo_de(fuente: &str) -> &str {
+    fuente.rsplit_once('/').map(|(dir, _)| dir).unwrap_or(".")
+}
+
+/// Agrupa los imports entre archivos en dependencias entre módulos
+fn dependencias_entre_modulos(aristas: &[(String, String)]) -> BTreeMap<String, BTreeSet<String>> {
+    let mut modulos: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for (archivo, importado) in aristas {
+        let (origen, destino) = (modulo_de(archivo), modulo_de(importado));
+        if origen != destino {
+            modulos
+                .entry(origen.to_string())
+                .or_default()
+                .insert(destino.to_string());
+        }
+    }
+    modulos
+}
+
+/// Enlace Markdown relativo desde un directorio hacia un archivo
+fn enlace_relativo(desde: &Path, hacia: &Path) -> String {
+    let desde: Vec<Component> = desde.components().collect();
+    let hacia: Vec<Component> = hacia.components().collect();
+    let comunes = desde.iter().zip(&hacia).take_while(|(a, b)| a == b).count();
+
+    let mut partes: Vec<String> = vec!["..".to_string(); desde.len() - comunes];
+    partes.extend(
+        hacia[comunes..]
+            .iter()
+            .map(|c| c.as_os_str().to_string_lossy().to_string()),
+    );
+    partes.join("/")
+}
+
+/// Primera línea con contenido de un resumen, sin marcas de encabezado
+fn primera_linea(resumen: &str) -> String {
+    let linea = resumen
+        .lines()
+        .map(|l| l.trim_start_matches('#').trim())
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    let mut texto: String = linea.chars().take(120).collect();
+    if linea.chars().count() > 120 {
+        texto.push('…');
+    }
+    texto
+}
+
+/// `INDEX.md`: un apartado por módulo con un enlace a cada documento
+fn generar_indice(entradas: &[EntradaDocumento], directorio: &Path) -> String {
+    let mut por_modulo: BTreeMap<&str, Vec<&EntradaDocumento>> = BTreeMap::new();
+    for entrada in entradas {
+        por_modulo
+            .entry(modulo_de(&entrada.fuente))
+            .or_default()
+            .push(entrada);
+    }
+
+    let mut indice = format!(
+        "# 📚 {}\n\n> {} · [{}]({})\n",
+        t("docs.indice_titulo"),
+        tf(
+            "docs.indice_descripcion",
+            &[("n", &entradas.len().to_string())]
+        ),
+        t("docs.arquitectura_enlace"),
+        ARCHIVO_ARQUITECTURA
+    );
+    for (modulo, entradas) in por_modulo {
+        indice.push_str(&format!("\n## `{}`\n\n", modulo));
+        for entrada in entradas {
+            let nombre = entrada.fuente.rsplit('/').next().unwrap_or(&entrada.fuente);
+            let descripcion = primera_linea(&entrada.resumen);
+            indice.push_str(&format!(
+                "- [{}]({}){}\n",
+                nombre,
+                enlace_relativo(directorio, &entrada.documento),
+                if descripcion.is_empty() {
+                    String::new()
+                } else {
+                    format!(" — {}", descripcion)
+                }
+            ));
+        }
+    }
+    indice
+}
+
+/// Diagrama Mermaid de dependencias entre módulos
+fn diagrama_mermaid(
+    modulos: &BTreeSet<String>,
+    dependencias: &BTreeMap<String, BTreeSet<String>>,
+) -> String {
+    let mut nodos: BTreeSet<&str> = modulos.iter().map(String::as_str).collect();
+    for (origen, destinos) in dependencias {
+        nodos.insert(origen);
+        nodos.extend(destinos.iter().map(String::as_str));
+    }
+    let id = |modulo: &str| nodos.iter().position(|n| *n == modulo).unwrap_or(0);
+
+    let mut diagrama = String::from("```mermaid\ngraph LR\n");
+    for (i, nodo) in nodos.iter().enumerate() {
+        diagrama.push_str(&format!(
+            "    m{}[\"{}\"]\n",
+            i,
+            nodo.replace('"', "#quot;")
+        ));
+    }
+    for (origen, destinos) in dependencias {
+        for destino in destinos {
+            diagrama.push_str(&format!("    m{} --> m{}\n", id(origen), id(destino)));
+        }
+    }
+    diagrama.push_str("```");
+    diagrama
+}
+
+/// `ARCHITECTURE.md`: visión general escrita por la IA a partir de los resúmenes
+///
+/// Como los documentos por archivo, lleva el hash de sus datos de entrada y no
+/// se regenera si no cambiaron.
+fn generar_arquitectura(
+    project_path: &Path,
+    config: &SentinelConfig,
+    directorio: &Path,
+    entradas: &[EntradaDocumento],
+    dependencias: &BTreeMap<String, BTreeSet<String>>,
+    mermaid: bool,
+    stats: Arc<Mutex<SentinelStats>>,
+) -> anyhow::Result<()> {
+    let mut modulos_txt = String::new();
+    let mut modulo_actual = "";
+    for entrada in entradas {
+        let modulo = modulo_de(&entrada.fuente);
+        if modulo != modulo_actual {
+            modulos_txt.push_str(&format!("\n### {}\n", modulo));
+            modulo_actual = modulo;
+        }
+        let resumen: String = entrada.resumen.chars().take(MAX_RESUMEN_ARCHIVO).collect();
+        modulos_txt.push_str(&format!("- {}: {}\n", entrada.fuente, resumen));
+    }
+    let dependencias_txt = dependencias
+        .iter()
+        .map(|(origen, destinos)| {
+            format!(
+                "{} -> {}",
+                origen,
+                destinos.iter().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let ruta = directorio.join(ARCHIVO_ARQUITECTURA);
+    let anterior = fs::read_to_string(&ruta).unwrap_or_default();
+    let hash = hash_codigo(&format!(
+        "{}\n{}\n{}",
+        modulos_txt, dependencias_txt, mermaid
+    ));
+    if hash_registrado(&anterior) == Some(hash.as_str()) {
+        println!(
+            "{}",
+            tf("docs.sin_cambios", &[("ruta", &ruta.display().to_string())]).dimmed()
+        );
+        return Ok(());
+    }
+
+    println!("{}", t("docs.generando_arquitectura").magenta());
+    let prompt = prompts::renderizar(
+        "docs-overview",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("language", &config.code_language),
+            ("project_name", &config.project_name),
+            ("modules", &delimitar("MODULOS", modulos_txt.trim())),
+            (
+                "dependencies",
+                &delimitar("DEPENDENCIAS ENTRE MODULOS", &dependencias_txt),
+            ),
+        ],
+    );
+    let mut cuerpo =
+        ai::consultar_ia_dinamico(prompt, ai::TaskType::Deep, config, stats, project_path)?
+            .trim()
+            .to_string();
+
+    if mermaid {
+        let modulos: BTreeSet<String> = entradas
+            .iter()
+            .map(|e| modulo_de(&e.fuente).to_string())
+            .collect();
+        cuerpo.push_str(&format!(
+            "\n\n## 🗺️ {}\n\n{}",
+            t("docs.diagrama_titulo"),
+            diagrama_mermaid(&modulos, dependencias)
+        ));
+    }
+
+    let segundos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    fs::write(
+        &ruta,
+        componer_documento(
+            &format!(
+                "🏛️ {}",
+                tf(
+                    "docs.arquitectura_titulo",
+                    &[("proyecto", &config.project_name)]
+                )
+            ),
+            &cuerpo,
+            &secciones_manuales(&anterior),
+            &hash,
+            &fecha_iso8601(segundos),
+        ),
+    )?;
+    println!(
+        "{}",
+        tf("docs.generado", &[("ruta", &ruta.display().to_string())])
+    );
     Ok(())
 }
+
+/// Fecha UTC en formato ISO-8601 (`2025-01-31T13:45:00Z`) a partir de segundos Unix
+pub fn fecha_iso8601(segundos: u64) -> String {
+    let dias = (segundos / 86_400) as i64;
+    let resto = segundos % 86_400;
+
+    // Conversión de días desde 1970-01-01 a fecha civil (algoritmo de H. Hinnant)
+    let z = dias + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let dia = doy - (153 * mp + 2) / 5 + 1;
+    let mes = if mp < 10 { mp + 3 } else { mp - 9 };
+    let anio = yoe + era * 400 + i64::from(mes <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        anio,
+        mes,
+        dia,
+        resto / 3_600,
+        (resto % 3_600) / 60,
+        resto % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fecha_iso8601() {
+        assert_eq!(fecha_iso8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(fecha_iso8601(951_827_696), "2000-02-29T12:34:56Z");
+        assert_eq!(fecha_iso8601(1_735_689_599), "2024-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_regenerar_conserva_secciones_manuales_y_hash() {
+        let anterior = format!(
+            "# 📖 a.ts\n\nResumen viejo\n\n{}\nNotas del equipo\n{}\n\n---\n<!-- sentinel:hash=abc -->\n",
+            INICIO_MANUAL, FIN_MANUAL
+        );
+        assert_eq!(hash_registrado(&anterior), Some("abc"));
+
+        let manuales = secciones_manuales(&anterior);
+        assert_eq!(
+            manuales,
+            vec![format!(
+                "{}\nNotas del equipo\n{}",
+                INICIO_MANUAL, FIN_MANUAL
+            )]
+        );
+
+        let nuevo = componer_documento(
+            "a.ts",
+            "Resumen nuevo",
+            &manuales,
+            "def",
+            "2025-01-01T00:00:00Z",
+        );
+        assert!(nuevo.contains("Resumen nuevo") && !nuevo.contains("Resumen viejo"));
+        assert!(nuevo.contains("Notas del equipo"));
+        assert!(nuevo.contains("2025-01-01T00:00:00Z"));
+        assert_eq!(hash_registrado(&nuevo), Some("def"));
+        assert_eq!(resumen_de(&nuevo), "Resumen nuevo");
+    }
+
+    #[test]
+    fn test_indice_y_diagrama_por_modulo() {
+        let raiz = Path::new("/p");
+        let entradas = vec![
+            EntradaDocumento {
+                fuente: "src/users/users.service.ts".to_string(),
+                documento: raiz.join("src/users/users.service.md"),
+                resumen: "## 🎯 Gestiona usuarios\nDetalle".to_string(),
+            },
+            EntradaDocumento {
+                fuente: "src/db/db.ts".to_string(),
+                documento: raiz.join("docs/src/db/db.md"),
+                resumen: String::new(),
+            },
+        ];
+        let indice = generar_indice(&entradas, &raiz.join("docs"));
+        assert!(indice.contains(
+            "## `src/users`\n\n- [users.service.ts](../src/users/users.service.md) — 🎯 Gestiona usuarios\n"
+        ));
+        assert!(indice.contains("- [db.ts](src/db/db.md)\n"));
+
+        let dependencias = dependencias_entre_modulos(&[
+            (
+                "src/users/users.service.ts".to_string(),
+                "src/db/db.ts".to_string(),
+            ),
+            (
+                "src/users/users.service.ts".to_string(),
+                "src/users/dto.ts".to_string(),
+            ),
+        ]);
+        let modulos = BTreeSet::from(["src/db".to_string(), "src/users".to_string()]);
+        assert_eq!(
+            diagrama_mermaid(&modulos, &dependencias),
+            "```mermaid\ngraph LR\n    m0[\"src/db\"]\n    m1[\"src/users\"]\n    m1 --> m0\n```"
+        );
+    }
+
+    #[test]
+    fn test_ruta_documento_con_docs_dir() {
+        let mut config = SentinelConfig::default(
+            "app".to_string(),
+            "npm".to_string(),
+            "NestJS".to_string(),
+            vec![],
+            vec!["ts".to_string()],
+            "typescript".to_string(),
+            vec![],
+            vec![],
+        );
+        let raiz = Path::new("/p");
+        let fuente = raiz.join("src/users/users.service.ts");
+        assert_eq!(
+            ruta_documento(&fuente, raiz, &config),
+            raiz.join("src/users/users.service.md")
+        );
+
+        config.docs_dir = Some("docs/api".to_string());
+        assert_eq!(
+            ruta_documento(&fuente, raiz, &config),
+            raiz.join("docs/api/src/users/users.service.md")
+        );
+    }
+}
@@ -5,10 +5,12 @@
 //! del módulo completo en lugar de buscar tests para el archivo individual.
 //!
 //! Los patrones de archivos padre son ahora dinámicos y detectados por IA según
-//! el framework del proyecto (NestJS, Django, Laravel, etc.)
+//! el framework del proyecto (NestJS, Django, Laravel, etc.). La detección no
+//! depende del lenguaje: respeta las extensiones configuradas y los estilos de
+//! sufijo `.service.ts`, `_service.py`, `_handler.go` y `UserController.php`.
 
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Verifica si un archivo es de tipo "padre" según los patrones del framework
 ///
@@ -33,137 +35,536 @@ pub fn es_archivo_padre(file_name: &str, parent_patterns: &[String]) -> bool {
         .any(|pattern| file_name.ends_with(pattern))
 }
 
+/// Archivos que representan al módulo de su directorio (`index.ts`, `mod.rs`, `__init__.py`)
+const ARCHIVOS_INDICE: &[&str] = &["index", "mod", "__init__"];
+
+/// Directorios raíz de código que no se consideran módulos por sí mismos
+const RAICES_CODIGO: &[&str] = &["src", "lib", "app", "internal", "pkg"];
+
+/// Niveles de directorio que se suben buscando un padre
+const MAX_NIVELES_PADRE: usize = 3;
+
+/// Verifica si un archivo es de test según las convenciones de los lenguajes soportados
+///
+/// # Ejemplos
+/// ```ignore
+/// assert!(es_archivo_test("user.spec.ts"));
+/// assert!(es_archivo_test("test_user.py"));
+/// assert!(es_archivo_test("user_test.go"));
+/// assert!(es_archivo_test("UserTest.php"));
+/// assert!(!es_archivo_test("user.service.ts"));
+/// ```
+pub fn es_archivo_test(file_name: &str) -> bool {
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    file_name.contains(".spec.")
+        || file_name.contains("-spec.")
+        || file_name.contains(".test.")
+        || stem.starts_with("test_")
+        || stem.ends_with("_test")
+        || stem.ends_with("Test")
+        || stem.ends_with("Tests")
+}
+
+/// Obtiene el nombre base del módulo de un archivo
+///
+/// Si el archivo coincide con un patrón de padre, se quita el sufijo con su
+/// separador (punto, guion bajo o PascalCase). Los archivos cuyo nombre es el
+/// patrón completo (`views.py`, `mod.rs`) o archivos índice toman el nombre
+/// de su directorio. En otro caso se usa el nombre hasta el primer punto.
+///
+/// # Ejemplos
+/// ```ignore
+/// let patrones = vec![".service.ts".to_string(), "_handler.go".to_string(), "Controller.php".to_string()];
+/// assert_eq!(nombre_base(Path::new("src/user.service.ts"), &patrones), "user");
+/// assert_eq!(nombre_base(Path::new("user_handler.go"), &patrones), "user");
+/// assert_eq!(nombre_base(Path::new("UserController.php"), &patrones), "User");
+/// assert_eq!(nombre_base(Path::new("src/users/mod.rs"), &patrones), "users");
+/// ```
+pub fn nombre_base(path: &Path, parent_patterns: &[String]) -> String {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let nombre_directorio = || {
+        path.parent()
+            .and_then(|d| d.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    if let Some(pattern) = parent_patterns
+        .iter()
+        .find(|p| file_name.ends_with(p.as_str()))
+    {
+        let base = file_name[..file_name.len() - pattern.len()].trim_end_matches(['.', '_', '-']);
+        if base.is_empty() {
+            return nombre_directorio();
+        }
+        return base.to_string();
+    }
+
+    let stem = file_name.split('.').next().unwrap_or_default();
+    if ARCHIVOS_INDICE.contains(&stem) {
+        return nombre_directorio();
+    }
+    stem.to_string()
+}
+
 /// Detecta si un archivo es un "hijo" y retorna el nombre del módulo padre
 ///
-/// Esta función busca en el mismo directorio del archivo modificado si existe
-/// un archivo padre según los patrones del framework y retorna el nombre
-/// base del módulo. Si hay múltiples padres, usa el de mayor prioridad (primero en la lista).
+/// Busca en el directorio del archivo modificado un archivo padre según los
+/// patrones del framework (sufijos con punto, guion bajo o PascalCase) y, si no
+/// hay ninguno, un archivo índice hermano (`index.*`, `mod.rs`, `__init__.py`).
+/// Si el directorio no tiene padre se repite la búsqueda en los directorios
+/// superiores (hasta `MAX_NIVELES_PADRE`, sin salir del proyecto). Solo se
+/// consideran archivos con las extensiones configuradas y que no sean tests.
+///
+/// Si hay varios padres en el mismo directorio, gana el de mayor prioridad
+/// (primero en `parent_patterns`) y, a igual prioridad, el que comparte
+/// prefijo con el archivo modificado.
 ///
 /// # Argumentos
 /// * `changed_path` - Path del archivo modificado
-/// * `project_path` - Path raíz del proyecto (no usado directamente, pero útil para validaciones futuras)
-/// * `parent_patterns` - Patrones de archivos padre del framework (detectados por IA)
+/// * `project_path` - Path raíz del proyecto (límite de la búsqueda hacia arriba)
+/// * `parent_patterns` - Patrones de archivos padre del framework
+/// * `extensions` - Extensiones de código del proyecto (`file_extensions`); vacío = todas
 ///
 /// # Retorna
-/// * `Some(nombre_base)` - Si se detecta un padre, retorna el nombre base (ej: "call" para "call.service.ts")
+/// * `Some(nombre_base)` - Si se detecta un padre (ej: "call" para "call.service.ts")
 /// * `None` - Si no se detecta ningún padre
 ///
 /// # Ejemplos
-/// ```
-/// // Archivo: src/calls/call-inbound.ts
-/// // Existe: src/calls/call.service.ts
-/// // Retorna: Some("call")
-///
-/// // Archivo: src/users/users.service.ts
-/// // No existe ningún padre (es el padre)
-/// // Retorna: None
+/// ```text
+/// // Archivo: src/calls/call-inbound.ts, existe src/calls/call.service.ts → Some("call")
+/// // Archivo: app/users/dto/create_user.py, existe app/users/user_service.py → Some("user")
+/// // Archivo: src/users/repo.rs, existe src/users/mod.rs → Some("users")
 /// ```
 pub fn detectar_archivo_padre(
     changed_path: &Path,
-    _project_path: &Path,
+    project_path: &Path,
     parent_patterns: &[String],
+    extensions: &[String],
 ) -> Option<String> {
-    // Obtener el directorio del archivo modificado
-    let dir = changed_path.parent()?;
+    let changed_name = changed_path.file_name()?.to_str()?;
+    let mut dir = changed_path.parent()?;
+
+    for _ in 0..MAX_NIVELES_PADRE {
+        if !dir.starts_with(project_path) || dir == project_path {
+            break;
+        }
+
+        if let Some(padre) =
+            buscar_padre_en_directorio(dir, changed_name, parent_patterns, extensions)
+        {
+            return Some(padre);
+        }
+
+        let es_raiz_codigo = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| RAICES_CODIGO.contains(&n));
+        if es_raiz_codigo {
+            break;
+        }
+        dir = dir.parent()?;
+    }
 
-    // Leer todos los archivos en el directorio
+    None
+}
+
+/// Busca el padre dentro de un único directorio
+fn buscar_padre_en_directorio(
+    dir: &Path,
+    changed_name: &str,
+    parent_patterns: &[String],
+    extensions: &[String],
+) -> Option<String> {
     let entries = fs::read_dir(dir).ok()?;
 
-    // Recopilar todos los archivos padres encontrados
-    let mut padres: Vec<(String, usize)> = Vec::new();
+    // (nombre_base, prioridad, comparte_prefijo)
+    let mut padres: Vec<(String, usize, bool)> = Vec::new();
+    let mut tiene_indice = false;
 
-    for entry in entries {
-        let entry = entry.ok()?;
+    for entry in entries.flatten() {
         let path = entry.path();
 
-        // Solo procesar archivos, no directorios
+        // Solo procesar archivos de código que no sean tests
         if !path.is_file() {
             continue;
         }
-
-        // Solo procesar archivos .ts que no sean .spec.ts
-        let file_name = path.file_name()?.to_str()?;
-
-        if !file_name.ends_with(".ts") || file_name.contains(".spec.") {
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        if (!extensions.is_empty() && !extensions.iter().any(|e| e == extension))
+            || es_archivo_test(file_name)
+        {
             continue;
         }
 
-        // Verificar si es un archivo padre
         if es_archivo_padre(file_name, parent_patterns) {
             // Encontrar la prioridad de este tipo de archivo (según el orden en parent_patterns)
             let priority = parent_patterns
                 .iter()
-                .position(|pattern| file_name.ends_with(pattern))
+                .position(|pattern| file_name.ends_with(pattern.as_str()))
                 .unwrap_or(parent_patterns.len());
-
-            // Extraer el nombre base (ej: "call.service.ts" -> "call")
-            let base_name = file_name.split('.').next().unwrap_or("").to_string();
-
+            let base_name = nombre_base(&path, parent_patterns);
             if !base_name.is_empty() {
-                padres.push((base_name, priority));
+                let comparte_prefijo = changed_name
+                    .to_lowercase()
+                    .starts_with(&base_name.to_lowercase());
+                padres.push((base_name, priority, comparte_prefijo));
             }
+        } else if ARCHIVOS_INDICE.contains(&file_name.split('.').next().unwrap_or_default()) {
+            tiene_indice = true;
         }
     }
 
-    // Retornar el padre con mayor prioridad (menor índice)
-    if padres.is_empty() {
-        None
-    } else {
-        // Ordenar por prioridad y tomar el primero
-        padres.sort_by_key(|(_, priority)| *priority);
-        Some(padres[0].0.clone())
+    // Mayor prioridad (menor índice), luego el que comparte prefijo; nombre para desempatar
+    padres.sort_by(|a, b| a.1.cmp(&b.1).then(b.2.cmp(&a.2)).then(a.0.cmp(&b.0)));
+    if let Some((base_name, _, _)) = padres.into_iter().next() {
+        return Some(base_name);
+    }
+
+    let es_raiz_codigo = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| RAICES_CODIGO.contains(&n));
+    if tiene_indice && !es_raiz_codigo {
+        return dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.to_string());
     }
+    None
 }
 
-/// Busca archivos de test para un módulo usando los patrones del framework
+/// Directorios que no se recorren al expandir `**` en los patrones de test
+const DIRECTORIOS_EXCLUIDOS: &[&str] = &["node_modules", "target", "dist", "build", "vendor"];
+
+/// Profundidad máxima que recorre un `**`
+const MAX_PROFUNDIDAD_GLOB: usize = 12;
+
+/// Busca todos los archivos de test de un módulo usando los patrones del framework
+///
+/// Placeholders soportados en cada patrón:
+/// * `{name}` / `{Name}` - Nombre base tal cual / con la primera letra en mayúscula
+/// * `{name:kebab}`, `{name:snake}`, `{name:camel}`, `{name:pascal}` - Variantes de caso
+/// * `{dir}` - Directorio del archivo modificado relativo a `project_path`
+/// * `{ext}` - Extensión del archivo modificado
 ///
-/// Esta función intenta encontrar archivos de test siguiendo los patrones específicos
-/// del framework detectado por IA. Reemplaza {name} con el nombre del módulo (lowercase)
-/// y {Name} con el nombre capitalizado.
+/// Tras la sustitución, el patrón admite comodines: `*` y `?` dentro de un
+/// segmento y `**` para cualquier cantidad de directorios.
 ///
 /// # Argumentos
 /// * `base_name` - Nombre base del módulo (ej: "user", "call")
+/// * `changed_path` - Archivo modificado (para `{dir}` y `{ext}`), si se conoce
 /// * `project_path` - Path raíz del proyecto
 /// * `test_patterns` - Patrones de ubicación de tests del framework
 ///
 /// # Retorna
-/// * `Some(PathBuf)` - Path relativo al archivo de test si se encuentra
-/// * `None` - Si no se encuentra ningún archivo de test
+/// Paths relativos (con '/') de todos los tests encontrados, sin duplicados y
+/// en el orden de los patrones.
 ///
 /// # Ejemplos
+/// ```ignore
+/// let patterns = vec!["src/**/__tests__/{name:kebab}.test.tsx".to_string()];
+/// // Para base_name = "UserCard" encuentra src/components/__tests__/user-card.test.tsx
 /// ```
-/// let patterns = vec!["test/{name}/{name}.spec.ts".to_string()];
-/// // Para base_name = "user"
-/// // Buscará: test/user/user.spec.ts
+pub fn buscar_archivos_test(
+    base_name: &str,
+    changed_path: Option<&Path>,
+    project_path: &Path,
+    test_patterns: &[String],
+) -> Vec<String> {
+    let dir = changed_path
+        .and_then(|p| p.parent())
+        .and_then(|d| d.strip_prefix(project_path).ok())
+        .map(|d| d.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let ext = changed_path
+        .and_then(|p| p.extension())
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    let mut encontrados: Vec<String> = Vec::new();
+    for pattern in test_patterns {
+        let sustituido = sustituir_placeholders(pattern, base_name, &dir, ext);
+        for path in expandir_glob(project_path, &sustituido) {
+            if changed_path == Some(path.as_path()) {
+                continue;
+            }
+            let rel = path
+                .strip_prefix(project_path)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            if !encontrados.contains(&rel) {
+                encontrados.push(rel);
+            }
+        }
+    }
+    encontrados
+}
+
+/// Tests de un archivo modificado buscados por nombre
+///
+/// Además de los del módulo padre (`base_name`) se buscan los del propio
+/// archivo: un índice hermano (`index.ts` junto a `Button.tsx`) convierte al
+/// directorio en módulo, pero `Button.test.tsx` sigue siendo el test de
+/// `Button.tsx`.
+pub fn buscar_tests_por_nombre(
+    base_name: &str,
+    changed_path: &Path,
+    project_path: &Path,
+    parent_patterns: &[String],
+    test_patterns: &[String],
+) -> Vec<String> {
+    let mut tests =
+        buscar_archivos_test(base_name, Some(changed_path), project_path, test_patterns);
+    let propio = nombre_base(changed_path, parent_patterns);
+    if !propio.is_empty() && propio != base_name {
+        for test in buscar_archivos_test(&propio, Some(changed_path), project_path, test_patterns) {
+            if !tests.contains(&test) {
+                tests.push(test);
+            }
+        }
+    }
+    tests
+}
+
+/// Ruta donde crear el test de un módulo que aún no tiene tests
+///
+/// Usa el primer patrón de `test_patterns` que se pueda concretar: los `**`
+/// se interpretan como cero directorios y se omiten los patrones con `*` o `?`.
+///
+/// # Ejemplos
+/// ```ignore
+/// let patterns = vec!["src/**/__tests__/{name}.test.ts".to_string()];
+/// // Para base_name = "user" → Some("src/__tests__/user.test.ts")
 /// ```
-pub fn buscar_archivo_test(
+pub fn ruta_test_sugerida(
     base_name: &str,
+    changed_path: Option<&Path>,
     project_path: &Path,
     test_patterns: &[String],
 ) -> Option<String> {
-    // Capitalizar primera letra para {Name}
-    let capitalized = {
-        let mut chars = base_name.chars();
+    let dir = changed_path
+        .and_then(|p| p.parent())
+        .and_then(|d| d.strip_prefix(project_path).ok())
+        .map(|d| d.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    let ext = changed_path
+        .and_then(|p| p.extension())
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+
+    test_patterns
+        .iter()
+        .map(|pattern| sustituir_placeholders(pattern, base_name, &dir, ext))
+        .map(|ruta| {
+            ruta.split('/')
+                .filter(|s| *s != "**")
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .find(|ruta| !ruta.is_empty() && !ruta.contains(['*', '?']))
+}
+
+/// Reemplaza los placeholders de un patrón de test y normaliza separadores vacíos
+fn sustituir_placeholders(pattern: &str, base_name: &str, dir: &str, ext: &str) -> String {
+    let palabras = separar_palabras(base_name);
+    let capitalizar = |palabra: &str| {
+        let mut chars = palabra.chars();
         match chars.next() {
             Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
             None => String::new(),
         }
     };
+    let pascal: String = palabras.iter().map(|p| capitalizar(p)).collect();
+    let camel = match palabras.split_first() {
+        Some((primera, resto)) => {
+            primera.clone() + &resto.iter().map(|p| capitalizar(p)).collect::<String>()
+        }
+        None => String::new(),
+    };
 
-    // Intentar cada patrón
-    for pattern in test_patterns {
-        let path_str = pattern
-            .replace("{name}", base_name)
-            .replace("{Name}", &capitalized);
+    let sustituido = pattern
+        .replace("{name:kebab}", &palabras.join("-"))
+        .replace("{name:snake}", &palabras.join("_"))
+        .replace("{name:camel}", &camel)
+        .replace("{name:pascal}", &pascal)
+        .replace("{name}", base_name)
+        .replace("{Name}", &capitalizar(base_name))
+        .replace("{dir}", dir)
+        .replace("{ext}", ext);
+
+    // "{dir}/x" en la raíz deja "/x": se descartan los segmentos vacíos
+    sustituido
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
 
-        let test_path = project_path.join(&path_str);
+/// Separa un nombre en palabras en minúsculas (kebab, snake, camelCase y PascalCase)
+///
+/// # Ejemplos
+/// ```ignore
+/// assert_eq!(separar_palabras("userProfile"), vec!["user", "profile"]);
+/// assert_eq!(separar_palabras("HTTPClient"), vec!["http", "client"]);
+/// assert_eq!(separar_palabras("order-item_v2"), vec!["order", "item", "v2"]);
+/// ```
+fn separar_palabras(nombre: &str) -> Vec<String> {
+    let chars: Vec<char> = nombre.chars().collect();
+    let mut palabras: Vec<String> = Vec::new();
+    let mut actual = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if matches!(c, '-' | '_' | '.' | ' ') {
+            if !actual.is_empty() {
+                palabras.push(std::mem::take(&mut actual));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !actual.is_empty() {
+            let anterior = chars[i - 1];
+            let siguiente_minuscula = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            // Nueva palabra: "userProfile" o el final de un acrónimo "HTTPClient"
+            if anterior.is_lowercase()
+                || anterior.is_ascii_digit()
+                || (anterior.is_uppercase() && siguiente_minuscula)
+            {
+                palabras.push(std::mem::take(&mut actual));
+            }
+        }
+        actual.extend(c.to_lowercase());
+    }
+    if !actual.is_empty() {
+        palabras.push(actual);
+    }
+    palabras
+}
 
-        if test_path.exists() {
-            return Some(path_str);
+/// Coincidencia de un nombre con un segmento con comodines `*` y `?`
+pub fn coincide_comodin(nombre: &str, patron: &str) -> bool {
+    let nombre: Vec<char> = nombre.chars().collect();
+    let patron: Vec<char> = patron.chars().collect();
+    let (mut n, mut p) = (0, 0);
+    let mut estrella: Option<(usize, usize)> = None;
+
+    while n < nombre.len() {
+        if p < patron.len() && (patron[p] == '?' || patron[p] == nombre[n]) {
+            n += 1;
+            p += 1;
+        } else if p < patron.len() && patron[p] == '*' {
+            estrella = Some((p, n));
+            p += 1;
+        } else if let Some((ep, en)) = estrella {
+            // Retroceder: la última '*' consume un carácter más
+            p = ep + 1;
+            n = en + 1;
+            estrella = Some((ep, en + 1));
+        } else {
+            return false;
         }
     }
+    patron[p..].iter().all(|c| *c == '*')
+}
 
-    None
+/// Expande un patrón relativo con comodines a los archivos existentes
+fn expandir_glob(project_path: &Path, pattern: &str) -> Vec<PathBuf> {
+    let segmentos: Vec<&str> = pattern.split('/').collect();
+    let Some((ultimo, directorios)) = segmentos.split_last() else {
+        return vec![];
+    };
+
+    let mut actuales = vec![project_path.to_path_buf()];
+    for segmento in directorios {
+        let mut siguientes = Vec::new();
+        for dir in &actuales {
+            if *segmento == "**" {
+                recolectar_directorios(dir, MAX_PROFUNDIDAD_GLOB, &mut siguientes);
+            } else if segmento.contains(['*', '?']) {
+                siguientes.extend(
+                    subdirectorios(dir)
+                        .into_iter()
+                        .filter(|d| nombre_de(d).is_some_and(|n| coincide_comodin(n, segmento))),
+                );
+            } else if dir.join(segmento).is_dir() {
+                siguientes.push(dir.join(segmento));
+            }
+        }
+        siguientes.dedup();
+        actuales = siguientes;
+    }
+
+    let mut archivos = Vec::new();
+    for dir in &actuales {
+        if *ultimo == "**" {
+            let mut dirs = Vec::new();
+            recolectar_directorios(dir, MAX_PROFUNDIDAD_GLOB, &mut dirs);
+            archivos.extend(dirs.iter().flat_map(|d| archivos_de(d)));
+        } else if ultimo.contains(['*', '?']) {
+            let mut coincidencias: Vec<PathBuf> = archivos_de(dir)
+                .into_iter()
+                .filter(|f| nombre_de(f).is_some_and(|n| coincide_comodin(n, ultimo)))
+                .collect();
+            coincidencias.sort();
+            archivos.extend(coincidencias);
+        } else if dir.join(ultimo).is_file() {
+            archivos.push(dir.join(ultimo));
+        }
+    }
+    archivos
+}
+
+fn nombre_de(path: &Path) -> Option<&str> {
+    path.file_name().and_then(|n| n.to_str())
+}
+
+fn subdirectorios(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            nombre_de(p).is_some_and(|n| !n.starts_with('.') && !DIRECTORIOS_EXCLUIDOS.contains(&n))
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn archivos_de(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    let mut archivos: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    archivos.sort();
+    archivos
+}
+
+/// Agrega `dir` y todos sus subdirectorios (para `**`, que también coincide con cero directorios)
+fn recolectar_directorios(dir: &Path, profundidad: usize, salida: &mut Vec<PathBuf>) {
+    salida.push(dir.to_path_buf());
+    if profundidad == 0 {
+        return;
+    }
+    for sub in subdirectorios(dir) {
+        recolectar_directorios(&sub, profundidad - 1, salida);
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +573,17 @@ mod test_buscar {
     use std::fs;
     use tempfile::TempDir;
 
+    /// Primer test encontrado (forma de los patrones sin `{dir}` ni `{ext}`)
+    fn buscar_archivo_test(
+        base_name: &str,
+        project_path: &Path,
+        test_patterns: &[String],
+    ) -> Option<String> {
+        buscar_archivos_test(base_name, None, project_path, test_patterns)
+            .into_iter()
+            .next()
+    }
+
     #[test]
     fn test_buscar_archivo_test_nestjs() {
         let temp_dir = TempDir::new().unwrap();
@@ -267,4 +679,241 @@ mod test_buscar {
 
         assert_eq!(result, Some("user_test.go".to_string()));
     }
+
+    fn crear(root: &Path, ruta: &str) {
+        let path = root.join(ruta);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+
+    #[test]
+    fn test_buscar_archivos_test_glob_recursivo_y_casos() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "src/components/__tests__/user-card.test.tsx");
+        crear(root, "src/pages/admin/__tests__/user-card.test.tsx");
+        crear(root, "src/__tests__/user_card.test.tsx");
+
+        let patterns = vec![
+            "src/**/__tests__/{name:kebab}.test.tsx".to_string(),
+            "src/**/__tests__/{name:snake}.test.*".to_string(),
+        ];
+        let result = buscar_archivos_test("UserCard", None, root, &patterns);
+
+        assert_eq!(
+            result,
+            vec![
+                "src/components/__tests__/user-card.test.tsx",
+                "src/pages/admin/__tests__/user-card.test.tsx",
+                "src/__tests__/user_card.test.tsx",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_buscar_archivos_test_dir_y_ext() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "src/users/user.service.spec.ts");
+        crear(root, "user.test.js");
+        let changed = root.join("src/users/user.service.ts");
+        let en_raiz = root.join("user.js");
+
+        let patterns = vec![
+            "{dir}/{name}.service.spec.{ext}".to_string(),
+            "{dir}/{name}.test.{ext}".to_string(),
+        ];
+        assert_eq!(
+            buscar_archivos_test("user", Some(&changed), root, &patterns),
+            vec!["src/users/user.service.spec.ts"]
+        );
+        assert_eq!(
+            buscar_archivos_test("user", Some(&en_raiz), root, &patterns),
+            vec!["user.test.js"]
+        );
+    }
+
+    #[test]
+    fn test_ruta_test_sugerida() {
+        let root = Path::new("/repo");
+        let changed = root.join("src/users/UserCard.tsx");
+        let patterns = vec![
+            "src/**/*.test.tsx".to_string(),
+            "src/**/__tests__/{name:kebab}.test.{ext}".to_string(),
+        ];
+        assert_eq!(
+            ruta_test_sugerida("UserCard", Some(&changed), root, &patterns),
+            Some("src/__tests__/user-card.test.tsx".to_string())
+        );
+        assert_eq!(
+            ruta_test_sugerida(
+                "user",
+                Some(&changed),
+                root,
+                &["{dir}/{name}.spec.ts".to_string()]
+            ),
+            Some("src/users/user.spec.ts".to_string())
+        );
+    }
+
+    #[test]
+    fn test_separar_palabras_y_comodines() {
+        assert_eq!(separar_palabras("userProfile"), vec!["user", "profile"]);
+        assert_eq!(separar_palabras("HTTPClient"), vec!["http", "client"]);
+        assert_eq!(
+            separar_palabras("order-item_v2"),
+            vec!["order", "item", "v2"]
+        );
+        assert_eq!(
+            sustituir_placeholders("{name:camel}/{name:pascal}", "order_item", "", ""),
+            "orderItem/OrderItem"
+        );
+
+        assert!(coincide_comodin("user.spec.ts", "*.spec.*"));
+        assert!(coincide_comodin("v1", "v?"));
+        assert!(!coincide_comodin("user.ts", "*.spec.ts"));
+    }
+}
+
+#[cfg(test)]
+mod test_padre {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn patrones(lista: &[&str]) -> Vec<String> {
+        lista.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn crear(root: &Path, ruta: &str) -> std::path::PathBuf {
+        let path = root.join(ruta);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_padre_typescript_prefiere_prefijo_comun() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "src/calls/call.service.ts");
+        crear(root, "src/calls/agent.service.ts");
+        crear(root, "src/calls/call.service.spec.ts");
+        let hijo = crear(root, "src/calls/call-inbound.ts");
+
+        let resultado =
+            detectar_archivo_padre(&hijo, root, &patrones(&[".service.ts"]), &patrones(&["ts"]));
+        assert_eq!(resultado, Some("call".to_string()));
+    }
+
+    #[test]
+    fn test_padre_python_en_directorio_superior() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "app/users/user_service.py");
+        let hijo = crear(root, "app/users/schemas/create_user.py");
+
+        let resultado = detectar_archivo_padre(
+            &hijo,
+            root,
+            &patrones(&["_service.py", "_router.py"]),
+            &patrones(&["py"]),
+        );
+        assert_eq!(resultado, Some("user".to_string()));
+    }
+
+    #[test]
+    fn test_padre_go_y_php() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "internal/user/user_handler.go");
+        crear(root, "internal/user/user_handler_test.go");
+        let hijo_go = crear(root, "internal/user/dto.go");
+        crear(root, "app/Http/Controllers/UserController.php");
+        let hijo_php = crear(root, "app/Http/Controllers/UserRequest.php");
+
+        let go = detectar_archivo_padre(
+            &hijo_go,
+            root,
+            &patrones(&["_service.go", "_handler.go"]),
+            &patrones(&["go"]),
+        );
+        assert_eq!(go, Some("user".to_string()));
+
+        let php = detectar_archivo_padre(
+            &hijo_php,
+            root,
+            &patrones(&["Controller.php", "Service.php"]),
+            &patrones(&["php"]),
+        );
+        assert_eq!(php, Some("User".to_string()));
+    }
+
+    #[test]
+    fn test_padre_archivo_indice_y_extensiones() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "src/users/mod.rs");
+        let hijo = crear(root, "src/users/repo.rs");
+        crear(root, "src/index.ts");
+        let suelto = crear(root, "src/main.ts");
+
+        let rust = detectar_archivo_padre(&hijo, root, &patrones(&["mod.rs"]), &patrones(&["rs"]));
+        assert_eq!(rust, Some("users".to_string()));
+
+        // El índice de la raíz de código no es un módulo
+        assert_eq!(
+            detectar_archivo_padre(&suelto, root, &[], &patrones(&["ts"])),
+            None
+        );
+
+        // Extensiones no configuradas se ignoran
+        assert_eq!(
+            detectar_archivo_padre(&hijo, root, &patrones(&["mod.rs"]), &patrones(&["py"])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_barrel_index_conserva_tests_del_archivo() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        crear(root, "src/components/index.ts");
+        let boton = crear(root, "src/components/Button.tsx");
+        crear(root, "src/components/Button.test.tsx");
+        let tests = patrones(&["{dir}/{name}.test.tsx"]);
+
+        // El barrel convierte al directorio en módulo...
+        let padre = detectar_archivo_padre(&boton, root, &[], &patrones(&["ts", "tsx"]));
+        assert_eq!(padre.as_deref(), Some("components"));
+        // ...pero Button.test.tsx sigue siendo el test de Button.tsx
+        assert_eq!(
+            buscar_tests_por_nombre("components", &boton, root, &[], &tests),
+            vec!["src/components/Button.test.tsx"]
+        );
+    }
+
+    #[test]
+    fn test_nombre_base_estilos() {
+        let p = patrones(&[".service.ts", "_service.py", "views.py", "Controller.php"]);
+        assert_eq!(nombre_base(Path::new("a/user.service.ts"), &p), "user");
+        assert_eq!(nombre_base(Path::new("a/user_service.py"), &p), "user");
+        assert_eq!(nombre_base(Path::new("blog/views.py"), &p), "blog");
+        assert_eq!(nombre_base(Path::new("UserController.php"), &p), "User");
+        assert_eq!(nombre_base(Path::new("a/user.dto.ts"), &p), "user");
+        assert_eq!(
+            nombre_base(Path::new("user_handler.go"), &patrones(&["_handler.go"])),
+            "user"
+        );
+        assert_eq!(nombre_base(Path::new("src/users/mod.rs"), &p), "users");
+    }
+
+    #[test]
+    fn test_es_archivo_test_por_lenguaje() {
+        assert!(es_archivo_test("user.spec.ts"));
+        assert!(es_archivo_test("test_user.py"));
+        assert!(es_archivo_test("user_test.go"));
+        assert!(es_archivo_test("UserTest.php"));
+        assert!(!es_archivo_test("user.service.ts"));
+    }
 }
@@ -0,0 +1,419 @@
+//! # Modo de corrección automática de tests
+//!
+//! Cuando los tests fallan y el usuario lo pide, Sentinel solicita a la IA un
+//! parche concreto (sobre el código o sobre el test), lo aplica en una copia
+//! temporal del proyecto, vuelve a ejecutar los tests y repite hasta
+//! `fix_max_attempts` veces. Solo si los tests pasan se muestra el diff final
+//! para su aprobación y se escribe en el proyecto real.
+
+use crate::ai::client::{Mensaje, consultar_conversacion_con_fallback};
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::config::SentinelConfig;
+use crate::diff;
+use crate::i18n::{es_afirmativo, t, tf};
+use crate::secrets;
+use crate::stats::SentinelStats;
+use crate::tests;
+use colored::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directorios que no se copian a la copia temporal
+const DIRECTORIOS_NO_COPIADOS: &[&str] = &[
+    ".git",
+    ".sentinel",
+    "node_modules",
+    "target",
+    "vendor",
+    "dist",
+    "build",
+    ".next",
+    "__pycache__",
+    ".venv",
+    "venv",
+];
+
+/// Directorios de dependencias que se enlazan (symlink) en vez de copiarse
+const DIRECTORIOS_ENLAZADOS: &[&str] = &["node_modules", "vendor", "target", ".venv", "venv"];
+
+/// Máximo de caracteres de la salida de tests que se envía a la IA
+const MAX_SALIDA_TESTS: usize = 6000;
+
+/// Máximo de caracteres de cada archivo que se envía a la IA
+const MAX_CONTENIDO_ARCHIVO: usize = 12000;
+
+/// Copia temporal del paquete que se elimina al salir de alcance
+struct CopiaTemporal {
+    raiz: PathBuf,
+}
+
+impl CopiaTemporal {
+    /// Copia `package_root` a un directorio temporal y enlaza sus dependencias
+    ///
+    /// Si el paquete no tiene `node_modules` propio (workspaces JS), se enlaza
+    /// el de la raíz del proyecto.
+    fn crear(package_root: &Path, project_path: &Path) -> anyhow::Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let raiz =
+            std::env::temp_dir().join(format!("sentinel-fix-{}-{}", std::process::id(), nanos));
+        fs::create_dir_all(&raiz)?;
+        let copia = Self { raiz };
+
+        copiar_directorio(package_root, &copia.raiz)?;
+        for nombre in DIRECTORIOS_ENLAZADOS {
+            let propio = package_root.join(nombre);
+            let origen = if propio.exists() {
+                propio
+            } else {
+                project_path.join(nombre)
+            };
+            if origen.exists() {
+                enlazar(&origen, &copia.raiz.join(nombre));
+            }
+        }
+        Ok(copia)
+    }
+}
+
+impl Drop for CopiaTemporal {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.raiz);
+    }
+}
+
+fn copiar_directorio(origen: &Path, destino: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(origen)?.flatten() {
+        let path = entry.path();
+        let nombre = entry.file_name();
+        let tipo = entry.file_type()?;
+
+        if tipo.is_dir() {
+            if DIRECTORIOS_NO_COPIADOS.contains(&nombre.to_string_lossy().as_ref()) {
+                continue;
+            }
+            let sub = destino.join(&nombre);
+            fs::create_dir_all(&sub)?;
+            copiar_directorio(&path, &sub)?;
+        } else if tipo.is_file() {
+            fs::copy(&path, destino.join(&nombre))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn enlazar(origen: &Path, destino: &Path) {
+    let _ = std::os::unix::fs::symlink(origen, destino);
+}
+
+#[cfg(windows)]
+fn enlazar(origen: &Path, destino: &Path) {
+    let _ = std::os::windows::fs::symlink_dir(origen, destino);
+}
+
+/// Parche propuesto por la IA: contenido completo de un archivo
+#[derive(Debug, PartialEq)]
+struct Parche {
+    ruta: String,
+    contenido: String,
+}
+
+/// Extrae el parche de la respuesta (`ARCHIVO: <ruta>` seguido de un bloque de código)
+fn parsear_parche(respuesta: &str) -> Option<Parche> {
+    let inicio = respuesta.find("ARCHIVO:")?;
+    let resto = &respuesta[inicio + "ARCHIVO:".len()..];
+    let ruta = resto
+        .lines()
+        .next()?
+        .trim()
+        .trim_matches(['`', '"', '\''])
+        .to_string();
+
+    let bloque = resto.find("```")?;
+    let codigo = &resto[bloque + 3..];
+    // Saltar la etiqueta de lenguaje de la primera línea
+    let codigo = &codigo[codigo.find('\n')? + 1..];
+    let fin = codigo.rfind("```")?;
+
+    if ruta.is_empty() {
+        return None;
+    }
+    Some(Parche {
+        ruta,
+        contenido: codigo[..fin].trim_end().to_string() + "\n",
+    })
+}
+
+/// Recorta un texto a sus últimos `max` caracteres (donde suelen estar los errores)
+fn recortar_final(texto: &str, max: usize) -> String {
+    let total = texto.chars().count();
+    if total <= max {
+        return texto.to_string();
+    }
+    let final_texto: String = texto.chars().skip(total - max).collect();
+    format!("[... salida recortada]\n{}", final_texto)
+}
+
+/// Intenta corregir automáticamente los tests que fallan
+///
+/// # Argumentos
+///
+/// * `changed_path` - Archivo fuente modificado
+/// * `test_paths` - Tests que fallan (relativos a `package_root`)
+/// * `package_root` - Directorio desde el que corre el runner
+/// * `project_path` - Raíz del proyecto (estadísticas)
+/// * `config` - Configuración vigente (`fix_max_attempts`, runner, modelos)
+/// * `stats` - Estadísticas compartidas
+/// * `leer_respuesta` - Lee la aprobación del usuario
+///
+/// # Retorna
+///
+/// * `Ok(true)` - Se encontró una corrección y el usuario la aplicó
+/// * `Ok(false)` - Sin corrección (intentos agotados, tests ya pasan o rechazada)
+pub fn corregir_tests(
+    changed_path: &Path,
+    test_paths: &[String],
+    package_root: &Path,
+    project_path: &Path,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    leer_respuesta: &dyn Fn() -> Option<String>,
+) -> anyhow::Result<bool> {
+    let source_rel = changed_path
+        .strip_prefix(package_root)
+        .unwrap_or(changed_path)
+        .to_string_lossy()
+        .replace('\\', "/");
+
+    // Archivos que la IA puede modificar, con su contenido original
+    let mut originales: HashMap<String, String> = HashMap::new();
+    for ruta in std::iter::once(&source_rel).chain(test_paths) {
+        let contenido = fs::read_to_string(package_root.join(ruta))?;
+        originales.insert(ruta.clone(), contenido);
+    }
+
+    println!("{}", t("fix.preparando_copia").magenta());
+    let copia = CopiaTemporal::crear(package_root, project_path)?;
+
+    let (pasan, salida) = tests::ejecutar_tests_capturando(test_paths, &copia.raiz, config);
+    if pasan {
+        println!("{}", t("fix.ya_pasan"));
+        return Ok(false);
+    }
+
+    let mut archivos = String::new();
+    for ruta in std::iter::once(&source_rel).chain(test_paths) {
+        archivos.push_str(&format!(
+            "\n--- {} ---\n{}\n",
+            ruta,
+            delimitar(
+                &format!("ARCHIVO {}", ruta),
+                &recortar_final(&originales[ruta], MAX_CONTENIDO_ARCHIVO)
+            )
+        ));
+    }
+
+    let mut conversacion = vec![Mensaje::usuario(prompts::renderizar(
+        "test-fix",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            (
+                "testing_framework",
+                config.testing_framework.as_deref().unwrap_or("testing"),
+            ),
+            ("language", &config.code_language),
+            (
+                "test_output",
+                &delimitar(
+                    "SALIDA DE TESTS",
+                    &recortar_final(&salida, MAX_SALIDA_TESTS),
+                ),
+            ),
+            ("files", &archivos),
+        ],
+    ))];
+
+    let mut exito = false;
+    for intento in 1..=config.fix_max_attempts {
+        let respuesta =
+            consultar_conversacion_con_fallback(&conversacion, config, Arc::clone(&stats))?;
+
+        let siguiente = match parsear_parche(&respuesta) {
+            Some(parche) if originales.contains_key(&parche.ruta) => {
+                println!(
+                    "{}",
+                    tf(
+                        "fix.intento_modificando",
+                        &[
+                            ("intento", &intento.to_string()),
+                            ("max", &config.fix_max_attempts.to_string()),
+                            ("ruta", &parche.ruta.cyan().to_string())
+                        ]
+                    )
+                );
+                // Los secretos viajaron redactados: devolverles su valor real
+                let contenido = secrets::restaurar(
+                    &parche.contenido,
+                    &originales[&parche.ruta],
+                    &config.secret_allowlist,
+                );
+                fs::write(copia.raiz.join(&parche.ruta), contenido)?;
+
+                let (pasan, salida) =
+                    tests::ejecutar_tests_capturando(test_paths, &copia.raiz, config);
+                if pasan {
+                    println!("{}", t("fix.tests_pasan"));
+                    exito = true;
+                    break;
+                }
+                println!("{}", t("fix.siguen_fallando"));
+                format!(
+                    "Apliqué tu cambio y los tests siguen fallando:\n{}\n\n\
+                    Propón otra corrección con el mismo formato (ARCHIVO + bloque completo).",
+                    delimitar(
+                        "SALIDA DE TESTS",
+                        &recortar_final(&salida, MAX_SALIDA_TESTS)
+                    )
+                )
+            }
+            Some(parche) => {
+                println!(
+                    "{}",
+                    tf(
+                        "fix.archivo_no_permitido",
+                        &[
+                            ("intento", &intento.to_string()),
+                            ("max", &config.fix_max_attempts.to_string()),
+                            ("ruta", &parche.ruta)
+                        ]
+                    )
+                );
+                format!(
+                    "'{}' no se puede modificar. Usa una de estas rutas: {}",
+                    parche.ruta,
+                    originales.keys().cloned().collect::<Vec<_>>().join(", ")
+                )
+            }
+            None => {
+                println!(
+                    "{}",
+                    tf(
+                        "fix.sin_parche",
+                        &[
+                            ("intento", &intento.to_string()),
+                            ("max", &config.fix_max_attempts.to_string())
+                        ]
+                    )
+                );
+                "Formato inválido. Responde solo con `ARCHIVO: <ruta>` y un bloque de código con el archivo completo.".to_string()
+            }
+        };
+
+        conversacion.push(Mensaje::asistente(respuesta));
+        conversacion.push(Mensaje::usuario(siguiente));
+    }
+
+    if !exito {
+        println!(
+            "   {} {}",
+            "⏹️".yellow(),
+            tf(
+                "fix.sin_correccion",
+                &[("max", &config.fix_max_attempts.to_string())]
+            )
+        );
+        return Ok(false);
+    }
+
+    // Diff final de todos los archivos modificados en la copia
+    let mut cambios: Vec<(String, String)> = Vec::new();
+    let mut rutas: Vec<&String> = originales.keys().collect();
+    rutas.sort();
+    for ruta in rutas {
+        let nuevo = fs::read_to_string(copia.raiz.join(ruta))?;
+        let diff_archivo = diff::diff_unificado(&originales[ruta], &nuevo, ruta);
+        if !diff_archivo.is_empty() {
+            println!();
+            diff::mostrar_diff(&diff_archivo);
+            cambios.push((ruta.clone(), nuevo));
+        }
+    }
+
+    print!("\n{}", t("fix.preguntar_aplicar"));
+    io::stdout().flush()?;
+    if !leer_respuesta().is_some_and(|r| es_afirmativo(&r)) {
+        println!("{}", t("fix.descartada"));
+        return Ok(false);
+    }
+
+    for (ruta, contenido) in &cambios {
+        fs::write(package_root.join(ruta), contenido)?;
+    }
+
+    let mut s = stats.lock().unwrap();
+    s.tests_fallidos_corregidos += 1;
+    s.tiempo_estimado_ahorrado_mins += 15;
+    s.guardar(project_path);
+    println!("{}", t("fix.aplicada").green());
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod test_fix {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parsear_parche() {
+        let respuesta = "Corrijo el test.\nARCHIVO: `src/user.spec.ts`\n```typescript\nit('ok', () => {});\n```\nListo.";
+        assert_eq!(
+            parsear_parche(respuesta),
+            Some(Parche {
+                ruta: "src/user.spec.ts".to_string(),
+                contenido: "it('ok', () => {});\n".to_string(),
+            })
+        );
+        assert_eq!(parsear_parche("Sin formato"), None);
+    }
+
+    #[test]
+    fn test_copia_temporal_excluye_y_limpia() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/jest")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("src/user.ts"), "export {}").unwrap();
+
+        let raiz = {
+            let copia = CopiaTemporal::crear(root, root).unwrap();
+            assert!(copia.raiz.join("src/user.ts").is_file());
+            assert!(!copia.raiz.join(".git").exists());
+            #[cfg(unix)]
+            assert!(
+                fs::symlink_metadata(copia.raiz.join("node_modules"))
+                    .unwrap()
+                    .file_type()
+                    .is_symlink()
+            );
+            copia.raiz.clone()
+        };
+        assert!(!raiz.exists());
+    }
+
+    #[test]
+    fn test_recortar_final() {
+        assert_eq!(recortar_final("abc", 5), "abc");
+        assert_eq!(recortar_final("abcdef", 2), "[... salida recortada]\nef");
+    }
+}
@@ -1,10 +1,13 @@
 //! Módulo de gestión de Git
 //!
-//! Funciones relacionadas con operaciones de Git: commits, reportes y gestión de historial.
+//! Funciones relacionadas con operaciones de Git: mensajes de commit y commits.
 
 use crate::ai;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::i18n::{es_afirmativo, t, tf};
+use crate::secrets;
 use colored::*;
-use std::fs;
 use std::path::Path;
 use std::process::Command;
 use std::sync::{Arc, Mutex};
@@ -12,18 +15,31 @@ use std::sync::{Arc, Mutex};
 use crate::config::SentinelConfig;
 use crate::stats::SentinelStats;
 
-/// Obtiene un resumen de los commits realizados hoy.
-pub fn obtener_resumen_git(project_path: &Path) -> String {
-    let output = Command::new("git")
-        .args(["log", "--since=00:00:00", "--oneline", "--pretty=format:%s"])
+/// Tamaño máximo del diff que se envía para generar el mensaje de commit
+const MAX_DIFF_COMMIT: usize = 12_000;
+
+/// Diff de los cambios sin commitear respecto a HEAD (recortado a `MAX_DIFF_COMMIT`)
+fn obtener_diff(project_path: &Path) -> String {
+    let diff = Command::new("git")
+        .args(["diff", "HEAD"])
         .current_dir(project_path)
         .output()
-        .expect("Fallo al leer git logs");
-
-    String::from_utf8_lossy(&output.stdout).to_string()
+        .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+        .unwrap_or_default();
+    if diff.len() <= MAX_DIFF_COMMIT {
+        return diff;
+    }
+    let mut corte = MAX_DIFF_COMMIT;
+    while !diff.is_char_boundary(corte) {
+        corte -= 1;
+    }
+    format!("{}\n[... diff recortado]", &diff[..corte])
 }
 
 /// Genera un mensaje de commit automático siguiendo Conventional Commits.
+///
+/// La plantilla `commit-message` recibe el diff de los cambios (`{diff}`) o,
+/// si no hay diff (repositorio sin commits o archivo nuevo), el código completo.
 pub fn generar_mensaje_commit(
     codigo: &str,
     file_name: &str,
@@ -31,13 +47,23 @@ pub fn generar_mensaje_commit(
     stats: Arc<Mutex<SentinelStats>>,
     project_path: &Path,
 ) -> String {
-    println!(
-        "{}",
-        "📝 Generando mensaje de commit inteligente...".magenta()
-    );
-    let prompt = format!(
-        "Genera un mensaje de commit corto (máximo 50 caracteres) siguiendo 'Conventional Commits' para los cambios en {}. Solo devuelve el texto del mensaje.\n\nCódigo:\n{}",
-        file_name, codigo
+    println!("{}", t("git.generando_mensaje").magenta());
+    let diff = obtener_diff(project_path);
+    let codigo = delimitar("CODIGO", codigo);
+    let diff = if diff.trim().is_empty() {
+        codigo.clone()
+    } else {
+        delimitar("DIFF", &diff)
+    };
+    let prompt = prompts::renderizar(
+        "commit-message",
+        project_path,
+        &[
+            ("file_name", file_name),
+            ("framework", &config.framework),
+            ("code", &codigo),
+            ("diff", &diff),
+        ],
     );
 
     match ai::consultar_ia_dinamico(prompt, ai::TaskType::Light, config, stats, project_path) {
@@ -46,65 +72,41 @@ pub fn generar_mensaje_commit(
     }
 }
 
-/// Genera un reporte de productividad diario usando Claude AI.
-pub fn generar_reporte_diario(
+/// Pregunta interactivamente al usuario si desea crear un commit.
+///
+/// Antes de confirmar escanea los cambios en staging y no hace el commit si
+/// encuentra secretos (ver `secrets::escanear_staged`); en ese caso los
+/// archivos señalados se sacan de staging para que un `git commit` manual
+/// tampoco los incluya.
+pub fn preguntar_commit(
     project_path: &Path,
+    mensaje: &str,
+    respuesta: &str,
     config: &SentinelConfig,
-    stats: Arc<Mutex<SentinelStats>>,
 ) {
-    println!(
-        "\n📊 {}...",
-        "Generando reporte de productividad diaria".magenta().bold()
-    );
-
-    let logs = obtener_resumen_git(project_path);
-    if logs.is_empty() {
-        println!(
-            "{}",
-            "⚠️ No hay commits registrados el día de hoy.".yellow()
-        );
-        return;
-    }
-
-    let prompt = format!(
-        "Actúa como un Lead Developer. Basado en estos mensajes de commit de hoy, \
-        genera un reporte de progreso diario para el equipo. \
-        Divide en: ✨ Logros Principales, 🛠️ Aspectos Técnicos (NestJS/Rust) y 🚀 Próximos Pasos. \
-        Sé profesional y directo.\n\nCommits del día:\n{}",
-        logs
-    );
-
-    match ai::consultar_ia_dinamico(prompt, ai::TaskType::Deep, config, stats, project_path) {
-        Ok(reporte) => {
-            println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-            println!("{}", "📝 REPORTE DIARIO DE SENTINEL".cyan().bold());
-            println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
-            println!("{}", reporte);
-            println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-
-            let _ = fs::write(project_path.join("docs/DAILY_REPORT.md"), reporte);
-        }
-        Err(e) => println!("❌ Error al generar reporte: {}", e),
-    }
-}
-
-/// Pregunta interactivamente al usuario si desea crear un commit.
-pub fn preguntar_commit(project_path: &Path, mensaje: &str, respuesta: &str) {
-    if respuesta == "s" {
+    if es_afirmativo(respuesta) {
         Command::new("git")
             .args(["add", "."])
             .current_dir(project_path)
             .status()
             .ok();
+
+        let hallazgos = secrets::escanear_staged(project_path, &config.secret_allowlist);
+        if !hallazgos.is_empty() {
+            secrets::quitar_de_staging(project_path, &hallazgos);
+            secrets::mostrar_bloqueo(&hallazgos);
+            return;
+        }
+
         match Command::new("git")
             .args(["commit", "-m", mensaje])
             .current_dir(project_path)
             .status()
         {
-            Ok(_) => println!("   ✅ Commit exitoso!"),
-            Err(e) => println!("   ❌ Error: {}", e),
+            Ok(_) => println!("{}", t("git.commit_exitoso")),
+            Err(e) => println!("{}", tf("git.error_commit", &[("error", &e.to_string())])),
         }
     } else {
-        println!("   ⏭️  Commit omitido.");
+        println!("{}", t("git.commit_omitido"));
     }
 }
@@ -0,0 +1,772 @@
+//! # Grafo de dependencias entre archivos
+//!
+//! Construye un grafo ligero de imports del proyecto (ES modules/CommonJS,
+//! imports de Python, paquetes de Go y `mod`/`use` de Rust) para encontrar
+//! todos los tests que dependen, directa o transitivamente, de un archivo
+//! modificado. El grafo se guarda en `.sentinel/graph.json` y se actualiza de
+//! forma incremental (solo se reanalizan los archivos cuya fecha cambió).
+
+use crate::files;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Versión del formato del grafo en disco (se reconstruye si no coincide)
+const VERSION_GRAFO: u32 = 1;
+
+/// Directorios que no se recorren al construir el grafo
+const DIRECTORIOS_EXCLUIDOS: &[&str] = &[
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "vendor",
+    "coverage",
+    "__pycache__",
+];
+
+/// Extensiones de los lenguajes con soporte de imports
+const EXTENSIONES_JS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+const EXTENSIONES_SOPORTADAS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs", "py", "go", "rs"];
+
+/// Archivo del grafo con sus dependencias directas
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct Nodo {
+    /// Fecha de modificación (segundos UNIX) cuando se analizó
+    modificado: u64,
+    /// Es un archivo de test
+    es_test: bool,
+    /// Archivos del proyecto que importa (rutas relativas con '/')
+    imports: Vec<String>,
+}
+
+/// Grafo de imports del proyecto, indexado por ruta relativa
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct GrafoDependencias {
+    version: u32,
+    nodos: HashMap<String, Nodo>,
+    /// Aristas invertidas (archivo → archivos que lo importan), derivadas de `nodos`
+    #[serde(skip)]
+    dependientes: HashMap<String, HashSet<String>>,
+}
+
+impl GrafoDependencias {
+    /// Carga el grafo guardado y lo pone al día con los cambios en disco
+    pub fn cargar(project_path: &Path) -> Self {
+        let mut grafo = fs::read_to_string(Self::ruta(project_path))
+            .ok()
+            .and_then(|content| serde_json::from_str::<GrafoDependencias>(&content).ok())
+            .filter(|g| g.version == VERSION_GRAFO)
+            .unwrap_or_default();
+        grafo.version = VERSION_GRAFO;
+        grafo.sincronizar(project_path);
+        grafo
+    }
+
+    /// Guarda el grafo en `.sentinel/graph.json`
+    pub fn guardar(&self, project_path: &Path) -> anyhow::Result<()> {
+        let path = Self::ruta(project_path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    fn ruta(project_path: &Path) -> PathBuf {
+        project_path.join(".sentinel/graph.json")
+    }
+
+    /// Recorre el proyecto y reanaliza solo los archivos nuevos o modificados
+    ///
+    /// # Retorna
+    /// Cantidad de archivos reanalizados.
+    pub fn sincronizar(&mut self, project_path: &Path) -> usize {
+        let mut vistos = HashSet::new();
+        let mut analizados = 0;
+
+        for path in listar_archivos(project_path) {
+            let rel = ruta_relativa(project_path, &path);
+            let modificado = fecha_modificacion(&path);
+            vistos.insert(rel.clone());
+
+            if self
+                .nodos
+                .get(&rel)
+                .is_some_and(|n| n.modificado == modificado)
+            {
+                continue;
+            }
+            self.nodos
+                .insert(rel, analizar_archivo(project_path, &path, modificado));
+            analizados += 1;
+        }
+
+        self.nodos.retain(|rel, _| vistos.contains(rel));
+        self.reindexar();
+        analizados
+    }
+
+    /// Reconstruye las aristas invertidas a partir de los nodos
+    fn reindexar(&mut self) {
+        self.dependientes.clear();
+        for (archivo, nodo) in &self.nodos {
+            for importado in &nodo.imports {
+                self.dependientes
+                    .entry(importado.clone())
+                    .or_default()
+                    .insert(archivo.clone());
+            }
+        }
+    }
+
+    /// Reemplaza (o elimina) un nodo manteniendo las aristas invertidas al día
+    fn reemplazar_nodo(&mut self, rel: String, nodo: Option<Nodo>) {
+        if let Some(anterior) = self.nodos.remove(&rel) {
+            for importado in &anterior.imports {
+                if let Some(dependientes) = self.dependientes.get_mut(importado) {
+                    dependientes.remove(&rel);
+                }
+            }
+        }
+        if let Some(nodo) = nodo {
+            for importado in &nodo.imports {
+                self.dependientes
+                    .entry(importado.clone())
+                    .or_default()
+                    .insert(rel.clone());
+            }
+            self.nodos.insert(rel, nodo);
+        }
+    }
+
+    /// Reanaliza un único archivo modificado (o lo elimina si ya no existe)
+    pub fn actualizar_archivo(&mut self, project_path: &Path, path: &Path) {
+        let rel = ruta_relativa(project_path, path);
+        if !path.is_file() {
+            self.reemplazar_nodo(rel, None);
+            return;
+        }
+        if !es_soportado(path) {
+            return;
+        }
+        let modificado = fecha_modificacion(path);
+        let nodo = analizar_archivo(project_path, path, modificado);
+        self.reemplazar_nodo(rel, Some(nodo));
+    }
+
+    /// Tests que dependen (directa o transitivamente) de un archivo
+    ///
+    /// Incluye el propio archivo si es un test.
+    ///
+    /// # Retorna
+    /// Rutas relativas a la raíz del proyecto, ordenadas.
+    pub fn tests_afectados(&self, project_path: &Path, path: &Path) -> Vec<String> {
+        let inicio = ruta_relativa(project_path, path);
+
+        let mut visitados: HashSet<&str> = HashSet::from([inicio.as_str()]);
+        let mut pendientes: VecDeque<&str> = VecDeque::from([inicio.as_str()]);
+        let mut tests = Vec::new();
+
+        while let Some(actual) = pendientes.pop_front() {
+            if self.nodos.get(actual).is_some_and(|n| n.es_test) {
+                tests.push(actual.to_string());
+            }
+            for dependiente in self.dependientes.get(actual).into_iter().flatten() {
+                if visitados.insert(dependiente) {
+                    pendientes.push_back(dependiente);
+                }
+            }
+        }
+
+        tests.sort();
+        tests
+    }
+
+    /// Imports directos entre archivos que no son tests
+    ///
+    /// # Retorna
+    /// Pares (archivo, archivo importado) con rutas relativas, ordenados.
+    pub fn dependencias(&self) -> Vec<(String, String)> {
+        let mut aristas: Vec<(String, String)> = self
+            .nodos
+            .iter()
+            .filter(|(_, nodo)| !nodo.es_test)
+            .flat_map(|(archivo, nodo)| {
+                nodo.imports
+                    .iter()
+                    .filter(|importado| self.nodos.get(*importado).is_some_and(|n| !n.es_test))
+                    .map(move |importado| (archivo.clone(), importado.clone()))
+            })
+            .collect();
+        aristas.sort();
+        aristas
+    }
+}
+
+fn listar_archivos(project_path: &Path) -> Vec<PathBuf> {
+    let mut archivos = Vec::new();
+    let mut pendientes = vec![project_path.to_path_buf()];
+
+    while let Some(dir) = pendientes.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let nombre = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() {
+                if !nombre.starts_with('.') && !DIRECTORIOS_EXCLUIDOS.contains(&nombre.as_str()) {
+                    pendientes.push(path);
+                }
+            } else if es_soportado(&path) {
+                archivos.push(path);
+            }
+        }
+    }
+    archivos
+}
+
+fn es_soportado(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| EXTENSIONES_SOPORTADAS.contains(&e))
+}
+
+fn fecha_modificacion(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+fn ruta_relativa(project_path: &Path, path: &Path) -> String {
+    path.strip_prefix(project_path)
+        .unwrap_or(path)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Normaliza `.` y `..` sin tocar el sistema de archivos
+fn normalizar(path: &Path) -> PathBuf {
+    let mut resultado = PathBuf::new();
+    for componente in path.components() {
+        match componente {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resultado.pop();
+            }
+            otro => resultado.push(otro),
+        }
+    }
+    resultado
+}
+
+fn analizar_archivo(project_path: &Path, path: &Path, modificado: u64) -> Nodo {
+    let contenido = fs::read_to_string(path).unwrap_or_default();
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let rel = ruta_relativa(project_path, path);
+
+    let imports: Vec<PathBuf> = match extension {
+        "py" => imports_python(project_path, path, &contenido),
+        "go" => imports_go(project_path, path, &contenido),
+        "rs" => imports_rust(project_path, path, &contenido),
+        e if EXTENSIONES_JS.contains(&e) => imports_js(path, &contenido),
+        _ => vec![],
+    };
+
+    let mut imports: Vec<String> = imports
+        .iter()
+        .filter(|p| p.starts_with(project_path) && p.as_path() != path)
+        .map(|p| ruta_relativa(project_path, p))
+        .collect();
+    imports.sort();
+    imports.dedup();
+
+    let es_test = files::es_archivo_test(file_name)
+        || (extension == "rs" && (rel.starts_with("tests/") || rel.contains("/tests/")));
+
+    Nodo {
+        modificado,
+        es_test,
+        imports,
+    }
+}
+
+/// Literales de texto que siguen a `from`, `import`, `require(` o `import(`
+fn especificadores_js(contenido: &str) -> Vec<String> {
+    let mut especificadores = Vec::new();
+    for linea in contenido.lines() {
+        for clave in ["from", "import", "require(", "import("] {
+            let mut resto = linea;
+            while let Some(pos) = resto.find(clave) {
+                resto = &resto[pos + clave.len()..];
+                let siguiente = resto.trim_start_matches([' ', '(']);
+                let Some(comilla) = siguiente
+                    .chars()
+                    .next()
+                    .filter(|c| matches!(c, '\'' | '"' | '`'))
+                else {
+                    continue;
+                };
+                if let Some(fin) = siguiente[1..].find(comilla) {
+                    especificadores.push(siguiente[1..=fin].to_string());
+                }
+            }
+        }
+    }
+    especificadores
+}
+
+fn imports_js(path: &Path, contenido: &str) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return vec![];
+    };
+
+    especificadores_js(contenido)
+        .into_iter()
+        .filter(|e| e.starts_with('.'))
+        .filter_map(|especificador| {
+            let base = normalizar(&dir.join(&especificador));
+            // En TS con ESM se importa "./util.js" aunque el archivo sea util.ts
+            let mut raices = vec![base.clone()];
+            if base
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| EXTENSIONES_JS.contains(&e))
+            {
+                raices.push(base.with_extension(""));
+            }
+            let mut candidatos = vec![base.clone()];
+            for raiz in &raices {
+                for ext in EXTENSIONES_JS {
+                    // Se agrega la extensión (no se reemplaza): "user.service" → "user.service.ts"
+                    let mut con_extension = raiz.clone().into_os_string();
+                    con_extension.push(format!(".{}", ext));
+                    candidatos.push(PathBuf::from(con_extension));
+                    candidatos.push(raiz.join(format!("index.{}", ext)));
+                }
+            }
+            candidatos.into_iter().find(|c| c.is_file())
+        })
+        .collect()
+}
+
+fn imports_python(project_path: &Path, path: &Path, contenido: &str) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return vec![];
+    };
+
+    // Módulos candidatos: (nivel relativo, "a.b.c")
+    let mut modulos: Vec<(usize, String)> = Vec::new();
+    for linea in contenido.lines() {
+        let linea = linea.trim();
+        if let Some(resto) = linea.strip_prefix("from ") {
+            let Some((modulo, nombres)) = resto.split_once(" import ") else {
+                continue;
+            };
+            let nivel = modulo.chars().take_while(|c| *c == '.').count();
+            let modulo = modulo.trim_start_matches('.').trim().to_string();
+            modulos.push((nivel, modulo.clone()));
+            // `from pkg import modulo` también puede referirse a un submódulo
+            for nombre in nombres.trim_matches(['(', ')', ' ']).split(',') {
+                let nombre = nombre.split_whitespace().next().unwrap_or_default();
+                if !nombre.is_empty() && nombre != "*" {
+                    let completo = if modulo.is_empty() {
+                        nombre.to_string()
+                    } else {
+                        format!("{}.{}", modulo, nombre)
+                    };
+                    modulos.push((nivel, completo));
+                }
+            }
+        } else if let Some(resto) = linea.strip_prefix("import ") {
+            for modulo in resto.split(',') {
+                if let Some(nombre) = modulo.split_whitespace().next() {
+                    modulos.push((0, nombre.to_string()));
+                }
+            }
+        }
+    }
+
+    // Los imports absolutos se resuelven desde cualquier directorio ancestro dentro del proyecto
+    let raices: Vec<&Path> = dir
+        .ancestors()
+        .take_while(|a| a.starts_with(project_path))
+        .collect();
+
+    modulos
+        .into_iter()
+        .filter_map(|(nivel, modulo)| {
+            let relativo: PathBuf = modulo.split('.').filter(|s| !s.is_empty()).collect();
+            let bases: Vec<PathBuf> = if nivel > 0 {
+                dir.ancestors()
+                    .nth(nivel - 1)
+                    .map(|b| vec![b.join(&relativo)])
+                    .unwrap_or_default()
+            } else {
+                raices.iter().map(|r| r.join(&relativo)).collect()
+            };
+            bases.into_iter().find_map(|base| {
+                [base.with_extension("py"), base.join("__init__.py")]
+                    .into_iter()
+                    .find(|c| c.is_file())
+            })
+        })
+        .collect()
+}
+
+/// Busca hacia arriba el directorio que contiene `manifiesto` (go.mod, Cargo.toml)
+fn buscar_manifiesto(project_path: &Path, path: &Path, manifiesto: &str) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .take_while(|a| a.starts_with(project_path))
+        .find(|a| a.join(manifiesto).is_file())
+        .map(|a| a.to_path_buf())
+}
+
+fn archivos_go(dir: &Path, incluir_tests: bool) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("go"))
+        .filter(|p| {
+            incluir_tests
+                || !p
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.ends_with("_test.go"))
+        })
+        .collect()
+}
+
+fn imports_go(project_path: &Path, path: &Path, contenido: &str) -> Vec<PathBuf> {
+    let Some(dir) = path.parent() else {
+        return vec![];
+    };
+    let mut resultado = Vec::new();
+
+    // Los tests dependen de todo su paquete (mismo directorio)
+    if path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with("_test.go"))
+    {
+        resultado.extend(archivos_go(dir, false));
+    }
+
+    let Some(raiz_modulo) = buscar_manifiesto(project_path, path, "go.mod") else {
+        return resultado;
+    };
+    let Some(modulo) = fs::read_to_string(raiz_modulo.join("go.mod"))
+        .ok()
+        .and_then(|c| {
+            c.lines().find_map(|l| {
+                l.trim()
+                    .strip_prefix("module ")
+                    .map(|m| m.trim().to_string())
+            })
+        })
+    else {
+        return resultado;
+    };
+
+    let mut en_bloque = false;
+    for linea in contenido.lines() {
+        let linea = linea.trim();
+        let import = if en_bloque {
+            if linea.starts_with(')') {
+                en_bloque = false;
+                continue;
+            }
+            linea
+        } else if linea.starts_with("import (") {
+            en_bloque = true;
+            continue;
+        } else if let Some(resto) = linea.strip_prefix("import ") {
+            resto
+        } else {
+            continue;
+        };
+
+        // Formatos: "pkg", alias "pkg", _ "pkg"
+        let Some(ruta) = import.split('"').nth(1) else {
+            continue;
+        };
+        if let Some(subruta) = ruta.strip_prefix(&modulo) {
+            let paquete = raiz_modulo.join(subruta.trim_start_matches('/'));
+            resultado.extend(archivos_go(&paquete, false));
+        }
+    }
+    resultado
+}
+
+fn imports_rust(project_path: &Path, path: &Path, contenido: &str) -> Vec<PathBuf> {
+    let Some(raiz_crate) = buscar_manifiesto(project_path, path, "Cargo.toml") else {
+        return vec![];
+    };
+    let src = raiz_crate.join("src");
+    let nombre_crate = fs::read_to_string(raiz_crate.join("Cargo.toml"))
+        .ok()
+        .and_then(|c| toml::from_str::<toml::Value>(&c).ok())
+        .and_then(|m| {
+            m.get("package")
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .map(|n| n.replace('-', "_"))
+        });
+
+    // Ruta de módulo del archivo actual (ej: src/ai/client.rs → ["ai", "client"])
+    let modulo_actual: Vec<String> = match path.strip_prefix(&src) {
+        Ok(rel) => {
+            let mut segmentos: Vec<String> = rel
+                .parent()
+                .into_iter()
+                .flat_map(|p| p.components())
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            let stem = rel.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+            if !["mod", "lib", "main"].contains(&stem) {
+                segmentos.push(stem.to_string());
+            }
+            segmentos
+        }
+        Err(_) => vec![],
+    };
+
+    let mut rutas: Vec<Vec<String>> = Vec::new();
+    for linea in contenido.lines() {
+        let linea = linea
+            .trim()
+            .trim_start_matches("pub(crate) ")
+            .trim_start_matches("pub ");
+
+        if let Some(nombre) = linea.strip_prefix("mod ").and_then(|r| r.strip_suffix(';')) {
+            let mut ruta = modulo_actual.clone();
+            ruta.push(nombre.trim().to_string());
+            rutas.push(ruta);
+        } else if let Some(uso) = linea.strip_prefix("use ") {
+            let uso = uso.trim_end_matches(';');
+            let (prefijo, grupo) = match uso.split_once('{') {
+                Some((p, g)) => (p.trim_end_matches("::"), Some(g.trim_end_matches('}'))),
+                None => (uso, None),
+            };
+            let mut segmentos: Vec<String> =
+                prefijo.split("::").map(|s| s.trim().to_string()).collect();
+
+            let base = match segmentos.first().cloned().as_deref() {
+                Some("crate") => vec![],
+                Some("super") => {
+                    let mut base = modulo_actual.clone();
+                    while segmentos.first().map(|s| s.as_str()) == Some("super") {
+                        segmentos.remove(0);
+                        base.pop();
+                    }
+                    segmentos.insert(0, "crate".to_string());
+                    base
+                }
+                Some(nombre) if Some(nombre) == nombre_crate.as_deref() => vec![],
+                _ => continue,
+            };
+            segmentos.remove(0);
+
+            let mut completa = base;
+            completa.extend(segmentos.into_iter().filter(|s| !s.is_empty()));
+            match grupo {
+                Some(grupo) => {
+                    for item in grupo.split(',') {
+                        let mut ruta = completa.clone();
+                        ruta.extend(
+                            item.split("::")
+                                .map(|s| {
+                                    s.split_whitespace().next().unwrap_or_default().to_string()
+                                })
+                                .filter(|s| !s.is_empty() && s != "self"),
+                        );
+                        rutas.push(ruta);
+                    }
+                }
+                None => rutas.push(completa),
+            }
+        }
+    }
+
+    rutas
+        .into_iter()
+        .filter_map(|ruta| resolver_modulo_rust(&src, &ruta))
+        .collect()
+}
+
+/// Resuelve la ruta de módulo más larga que exista como archivo
+fn resolver_modulo_rust(src: &Path, ruta: &[String]) -> Option<PathBuf> {
+    (1..=ruta.len()).rev().find_map(|n| {
+        let base: PathBuf = ruta[..n].iter().collect();
+        [
+            src.join(&base).with_extension("rs"),
+            src.join(&base).join("mod.rs"),
+        ]
+        .into_iter()
+        .find(|c| c.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn escribir(root: &Path, ruta: &str, contenido: &str) -> PathBuf {
+        let path = root.join(ruta);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, contenido).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_tests_afectados_js_transitivo() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let util = escribir(root, "src/utils/format.ts", "export const f = 1;");
+        escribir(
+            root,
+            "src/users/user.service.ts",
+            "import { f } from '../utils/format';",
+        );
+        escribir(
+            root,
+            "src/users/index.ts",
+            "export * from './user.service';",
+        );
+        escribir(
+            root,
+            "src/users/user.service.spec.ts",
+            "import { UserService } from './user.service.js';",
+        );
+        escribir(
+            root,
+            "test/app.e2e-spec.ts",
+            "const u = require(\"../src/users\");",
+        );
+        escribir(root, "src/orders/order.spec.ts", "import x from './order';");
+
+        let grafo = GrafoDependencias::cargar(root);
+        assert_eq!(
+            grafo.tests_afectados(root, &util),
+            vec!["src/users/user.service.spec.ts", "test/app.e2e-spec.ts"]
+        );
+
+        let index = root.join("src/users/index.ts");
+        assert_eq!(
+            grafo.tests_afectados(root, &index),
+            vec!["test/app.e2e-spec.ts"]
+        );
+    }
+
+    #[test]
+    fn test_tests_afectados_python_y_go() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let helpers = escribir(root, "app/core/helpers.py", "def h(): pass");
+        escribir(root, "app/core/__init__.py", "");
+        escribir(root, "app/users/service.py", "from ..core import helpers\n");
+        escribir(
+            root,
+            "tests/test_users.py",
+            "import os\nfrom app.users.service import crear\n",
+        );
+
+        escribir(root, "go.mod", "module example.com/shop\n\ngo 1.22\n");
+        let money = escribir(root, "pkg/money/money.go", "package money");
+        escribir(
+            root,
+            "internal/cart/cart.go",
+            "package cart\n\nimport (\n\t\"fmt\"\n\tm \"example.com/shop/pkg/money\"\n)\n",
+        );
+        escribir(root, "internal/cart/cart_test.go", "package cart");
+
+        let grafo = GrafoDependencias::cargar(root);
+        assert_eq!(
+            grafo.tests_afectados(root, &helpers),
+            vec!["tests/test_users.py"]
+        );
+        assert_eq!(
+            grafo.tests_afectados(root, &money),
+            vec!["internal/cart/cart_test.go"]
+        );
+    }
+
+    #[test]
+    fn test_tests_afectados_rust() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        escribir(root, "Cargo.toml", "[package]\nname = \"my-app\"\n");
+        escribir(root, "src/lib.rs", "pub mod ai;\nmod config;\n");
+        escribir(root, "src/ai/mod.rs", "pub mod client;\n");
+        let config = escribir(root, "src/config.rs", "pub struct Config;");
+        escribir(
+            root,
+            "src/ai/client.rs",
+            "use crate::config::Config;\nuse super::{client, mod_inexistente};\n",
+        );
+        escribir(root, "tests/client.rs", "use my_app::ai::client;\n");
+
+        let grafo = GrafoDependencias::cargar(root);
+        assert_eq!(
+            grafo.tests_afectados(root, &config),
+            vec!["tests/client.rs"]
+        );
+    }
+
+    #[test]
+    fn test_grafo_incremental_y_persistente() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let util = escribir(root, "src/util.ts", "export const a = 1;");
+        escribir(root, "src/a.test.ts", "import { a } from './util';");
+
+        let mut grafo = GrafoDependencias::cargar(root);
+        grafo.guardar(root).unwrap();
+        assert!(root.join(".sentinel/graph.json").exists());
+
+        // Sin cambios en disco no se reanaliza nada
+        let mut recargado = GrafoDependencias::cargar(root);
+        assert_eq!(recargado.sincronizar(root), 0);
+        assert_eq!(
+            recargado.tests_afectados(root, &util),
+            vec!["src/a.test.ts"]
+        );
+
+        // Un nuevo test se incorpora al actualizar solo ese archivo
+        let nuevo = escribir(root, "src/b.test.ts", "import { a } from './util';");
+        grafo.actualizar_archivo(root, &nuevo);
+        assert_eq!(
+            grafo.tests_afectados(root, &util),
+            vec!["src/a.test.ts", "src/b.test.ts"]
+        );
+
+        fs::remove_file(&nuevo).unwrap();
+        grafo.actualizar_archivo(root, &nuevo);
+        assert_eq!(grafo.tests_afectados(root, &util), vec!["src/a.test.ts"]);
+
+        // Un test que deja de importar el archivo sale de sus dependientes
+        let existente = escribir(root, "src/a.test.ts", "export {};");
+        grafo.actualizar_archivo(root, &existente);
+        assert!(grafo.tests_afectados(root, &util).is_empty());
+    }
+}
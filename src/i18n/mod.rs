@@ -0,0 +1,295 @@
+//! # Internacionalización
+//!
+//! Los mensajes de la terminal se leen de catálogos TOML por idioma
+//! (`src/i18n/es.toml`, `src/i18n/en.toml`) embebidos en el binario con
+//! `include_str!`. Cada mensaje tiene una clave con puntos (`main.pausado`) y
+//! puede llevar variables entre llaves (`{archivo}`).
+//!
+//! El idioma se elige con el campo `language` de `.sentinelrc.toml` ("es" o
+//! "en"); si no está definido se usa `LC_ALL`, `LC_MESSAGES` o `LANG`, y en
+//! último caso español. El mismo idioma se pide a la IA en sus respuestas.
+
+use crate::ai::prompts::sustituir;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Idioma de los mensajes y de las respuestas de la IA
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Idioma {
+    Es,
+    En,
+}
+
+impl Idioma {
+    /// Interpreta un código de idioma o locale (`es`, `en_US.UTF-8`...)
+    pub fn desde_codigo(codigo: &str) -> Option<Self> {
+        let codigo = codigo.trim().to_lowercase();
+        if codigo.starts_with("es") {
+            Some(Idioma::Es)
+        } else if codigo.starts_with("en") {
+            Some(Idioma::En)
+        } else {
+            None
+        }
+    }
+
+    pub fn codigo(&self) -> &'static str {
+        match self {
+            Idioma::Es => "es",
+            Idioma::En => "en",
+        }
+    }
+}
+
+/// Idiomas aceptados en el campo `language`
+pub const IDIOMAS: &[&str] = &["es", "en"];
+
+const CATALOGO_ES: &str = include_str!("es.toml");
+const CATALOGO_EN: &str = include_str!("en.toml");
+
+/// 0 = sin definir (se detecta del entorno), 1 = español, 2 = inglés
+static IDIOMA: AtomicU8 = AtomicU8::new(0);
+
+type Catalogo = HashMap<String, String>;
+
+fn catalogos() -> &'static (Catalogo, Catalogo) {
+    static CATALOGOS: OnceLock<(Catalogo, Catalogo)> = OnceLock::new();
+    CATALOGOS.get_or_init(|| (cargar(CATALOGO_ES), cargar(CATALOGO_EN)))
+}
+
+/// Convierte un catálogo TOML en un mapa plano `seccion.clave -> mensaje`
+fn cargar(contenido: &str) -> Catalogo {
+    fn aplanar(prefijo: &str, tabla: &toml::Table, catalogo: &mut Catalogo) {
+        for (clave, valor) in tabla {
+            let completa = if prefijo.is_empty() {
+                clave.clone()
+            } else {
+                format!("{}.{}", prefijo, clave)
+            };
+            match valor {
+                toml::Value::Table(sub) => aplanar(&completa, sub, catalogo),
+                toml::Value::String(texto) => {
+                    catalogo.insert(completa, texto.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let tabla: toml::Table =
+        toml::from_str(contenido).expect("catálogo de mensajes integrado con formato inválido");
+    let mut catalogo = Catalogo::new();
+    aplanar("", &tabla, &mut catalogo);
+    catalogo
+}
+
+/// Fija el idioma a partir de la configuración (o del entorno si es `None`)
+pub fn establecer(language: Option<&str>) {
+    let idioma = language
+        .and_then(Idioma::desde_codigo)
+        .unwrap_or_else(idioma_del_entorno);
+    IDIOMA.store(
+        match idioma {
+            Idioma::Es => 1,
+            Idioma::En => 2,
+        },
+        Ordering::Relaxed,
+    );
+}
+
+fn idioma_del_entorno() -> Idioma {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|valor| !valor.is_empty())
+        .and_then(|valor| Idioma::desde_codigo(&valor))
+        .unwrap_or(Idioma::Es)
+}
+
+/// Idioma vigente
+pub fn idioma() -> Idioma {
+    match IDIOMA.load(Ordering::Relaxed) {
+        1 => Idioma::Es,
+        2 => Idioma::En,
+        _ => idioma_del_entorno(),
+    }
+}
+
+/// Mensaje traducido; si falta en el idioma vigente se usa el español
+pub fn t(clave: &str) -> String {
+    let (es, en) = catalogos();
+    let catalogo = match idioma() {
+        Idioma::Es => es,
+        Idioma::En => en,
+    };
+    catalogo
+        .get(clave)
+        .or_else(|| es.get(clave))
+        .cloned()
+        .unwrap_or_else(|| clave.to_string())
+}
+
+/// Mensaje traducido con variables (`{nombre}`)
+pub fn tf(clave: &str, variables: &[(&str, &str)]) -> String {
+    sustituir(&t(clave), variables)
+}
+
+/// Instrucción de idioma que se agrega al prompt de sistema de cada consulta
+///
+/// Las palabras clave que Sentinel interpreta (CRITICO, SEGURO, LEER:,
+/// bloques de código, JSON) se mantienen tal cual en cualquier idioma.
+pub fn instruccion_respuesta() -> &'static str {
+    match idioma() {
+        Idioma::Es => "Responde en español.",
+        Idioma::En => {
+            "Write every explanation, suggestion and commit message in English. \
+             Keep the protocol keywords requested in the instructions (CRITICO, SEGURO, \
+             LEER:, JSON keys and code blocks) exactly as written."
+        }
+    }
+}
+
+/// `true` si la respuesta del usuario es afirmativa en cualquier idioma (s/sí/y/yes)
+pub fn es_afirmativo(respuesta: &str) -> bool {
+    matches!(
+        respuesta.trim().to_lowercase().as_str(),
+        "s" | "si" | "sí" | "y" | "yes"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn variables(texto: &str) -> BTreeSet<String> {
+        texto
+            .split('{')
+            .skip(1)
+            .filter_map(|resto| resto.split_once('}').map(|(nombre, _)| nombre.to_string()))
+            .filter(|nombre| nombre.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogos_tienen_las_mismas_claves() {
+        let es = cargar(CATALOGO_ES);
+        let en = cargar(CATALOGO_EN);
+        assert!(!es.is_empty());
+
+        let faltan_en: Vec<&String> = es.keys().filter(|k| !en.contains_key(*k)).collect();
+        let faltan_es: Vec<&String> = en.keys().filter(|k| !es.contains_key(*k)).collect();
+        assert!(
+            faltan_en.is_empty(),
+            "claves sin traducir en en.toml: {:?}",
+            faltan_en
+        );
+        assert!(
+            faltan_es.is_empty(),
+            "claves sin traducir en es.toml: {:?}",
+            faltan_es
+        );
+
+        for (clave, texto) in &es {
+            assert_eq!(
+                variables(texto),
+                variables(&en[clave]),
+                "variables distintas en '{}'",
+                clave
+            );
+        }
+    }
+
+    /// Claves literales que un archivo fuente pasa a `t` y `tf`
+    ///
+    /// Se revisan todos los strings del primer argumento, así que también
+    /// cuentan las claves elegidas con `if`/`match` dentro de la llamada.
+    fn claves_usadas(codigo: &str) -> Vec<String> {
+        let mut claves = Vec::new();
+        for (i, _) in codigo.match_indices('(') {
+            let antes = &codigo[..i];
+            let nombre = if antes.ends_with("tf") { "tf" } else { "t" };
+            if !antes.ends_with(nombre)
+                || antes[..antes.len() - nombre.len()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '.')
+            {
+                continue;
+            }
+            // Recorre el primer argumento hasta la coma o el paréntesis de cierre
+            let mut profundidad = 0;
+            let mut literal: Option<String> = None;
+            for c in codigo[i + 1..].chars() {
+                match (&mut literal, c) {
+                    (Some(texto), '"') => {
+                        let es_clave = texto.contains('.')
+                            && texto
+                                .chars()
+                                .all(|c| c.is_ascii_lowercase() || c == '_' || c == '.');
+                        if es_clave {
+                            claves.push(texto.clone());
+                        }
+                        literal = None;
+                    }
+                    (Some(texto), c) => texto.push(c),
+                    (None, '"') => literal = Some(String::new()),
+                    (None, '(' | '[' | '{') => profundidad += 1,
+                    (None, ')' | ']' | '}') if profundidad == 0 => break,
+                    (None, ')' | ']' | '}') => profundidad -= 1,
+                    (None, ',') if profundidad == 0 => break,
+                    _ => {}
+                }
+            }
+        }
+        claves
+    }
+
+    #[test]
+    fn test_claves_usadas_extrae_argumentos_condicionales() {
+        let codigo = r#"t(if ok { "tests.pasaron" } else { "tests.fallaron" }); tf("pr.sin_config", &[("x", "y.z")]);"#;
+        assert_eq!(
+            claves_usadas(codigo),
+            vec!["tests.pasaron", "tests.fallaron", "pr.sin_config"]
+        );
+    }
+
+    #[test]
+    fn test_claves_usadas_existen_en_los_catalogos() {
+        let es = cargar(CATALOGO_ES);
+        let en = cargar(CATALOGO_EN);
+        let mut pendientes = vec![std::path::PathBuf::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src"
+        ))];
+        let mut revisadas = 0;
+        let mut faltan = Vec::new();
+        while let Some(dir) = pendientes.pop() {
+            for entrada in std::fs::read_dir(&dir).unwrap().flatten() {
+                let ruta = entrada.path();
+                if ruta.is_dir() {
+                    pendientes.push(ruta);
+                } else if ruta.extension().is_some_and(|e| e == "rs") {
+                    let codigo = std::fs::read_to_string(&ruta).unwrap();
+                    for clave in claves_usadas(&codigo) {
+                        revisadas += 1;
+                        if !es.contains_key(&clave) || !en.contains_key(&clave) {
+                            faltan.push(format!("{}: {}", ruta.display(), clave));
+                        }
+                    }
+                }
+            }
+        }
+        assert!(revisadas > 100);
+        assert!(faltan.is_empty(), "claves sin catálogo: {:#?}", faltan);
+    }
+
+    #[test]
+    fn test_idioma_desde_codigo() {
+        assert_eq!(Idioma::desde_codigo("en_US.UTF-8"), Some(Idioma::En));
+        assert_eq!(Idioma::desde_codigo("es"), Some(Idioma::Es));
+        assert_eq!(Idioma::desde_codigo("C.UTF-8"), None);
+        assert!(es_afirmativo("Y") && es_afirmativo("sí") && !es_afirmativo("n"));
+    }
+}
@@ -5,28 +5,77 @@
 
 use colored::*;
 use config::SentinelConfig;
+use i18n::{es_afirmativo, t, tf};
 use notify::{Event, EventKind, RecursiveMode, Watcher};
 use stats::SentinelStats;
 use std::collections::HashMap;
 use std::io::{self, Write};
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex, mpsc};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock, mpsc};
 use std::thread;
 use std::time::Instant;
 
 // Módulos
 mod ai;
+mod audit;
+mod changelog;
+mod cli;
+mod comments;
 mod config;
+mod coverage;
+mod detector;
+mod diff;
 mod docs;
 mod files;
+mod fix;
 mod git;
+mod graph;
+mod i18n;
+mod pr;
+mod report;
+mod rules;
+mod secrets;
 mod stats;
 mod tests;
 mod ui;
+mod workspace;
 
 // --- MAIN ---
 
 fn main() {
+    // Hasta leer la configuración, el idioma se toma del entorno (LANG)
+    i18n::establecer(None);
+
+    // Subcomandos puntuales (audit, ...); sin argumentos se inicia el monitor
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match cli::parsear(&args) {
+        Ok(cli::Comando::Monitor) => {}
+        Ok(cli::Comando::Ayuda) => {
+            cli::mostrar_uso();
+            return;
+        }
+        Ok(cli::Comando::Audit { proyecto, db }) => {
+            std::process::exit(audit::ejecutar(&proyecto, db.as_deref()));
+        }
+        Ok(cli::Comando::DocsBuild { proyecto, mermaid }) => {
+            std::process::exit(docs::construir(&proyecto, mermaid));
+        }
+        Ok(cli::Comando::Report { proyecto, opciones }) => {
+            std::process::exit(report::ejecutar(&proyecto, &opciones));
+        }
+        Ok(cli::Comando::Changelog { proyecto, opciones }) => {
+            std::process::exit(changelog::ejecutar(&proyecto, &opciones));
+        }
+        Ok(cli::Comando::Review { proyecto, opciones }) => {
+            std::process::exit(pr::ejecutar(&proyecto, &opciones));
+        }
+        Err(e) => {
+            eprintln!("{} {}", "❌".red(), e);
+            cli::mostrar_uso();
+            std::process::exit(2);
+        }
+    }
+
     // Mostrar banner al inicio
     ui::mostrar_banner();
 
@@ -35,12 +84,20 @@ fn main() {
         std::process::exit(1);
     }
 
-    let config = Arc::new(ui::inicializar_sentinel(&project_path));
+    // La configuración vive detrás de un RwLock para poder recargarla en caliente
+    let mut config_inicial = ui::inicializar_sentinel(&project_path);
+    ui::sincronizar_workspace(&project_path, &mut config_inicial);
+    i18n::establecer(config_inicial.language.as_deref());
+    let config = Arc::new(RwLock::new(config_inicial));
     let stats = Arc::new(Mutex::new(SentinelStats::cargar(&project_path)));
 
     let esta_pausado = Arc::new(Mutex::new(false));
     let pausa_loop = Arc::clone(&esta_pausado);
     let (tx, rx) = mpsc::channel::<PathBuf>();
+    // Tests creados, editados o borrados: no se revisan, pero actualizan el grafo
+    let (tx_grafo, rx_grafo) = mpsc::channel::<PathBuf>();
+    // Directorios de paquetes agregados al recargar la configuración
+    let (tx_vigilar, rx_vigilar) = mpsc::channel::<Vec<PathBuf>>();
     let (stdin_tx, stdin_rx) = mpsc::channel::<String>();
     let stdin_rx = Arc::new(Mutex::new(stdin_rx));
     let esperando_input = Arc::new(Mutex::new(false));
@@ -65,93 +122,140 @@ fn main() {
                     println!(
                         " ⌨️ SENTINEL: {}",
                         if *p {
-                            "PAUSADO".yellow()
+                            t("main.pausado").yellow()
                         } else {
-                            "ACTIVO".green()
+                            t("main.activo").green()
                         }
                     );
                 } else if cmd == "r" {
-                    git::generar_reporte_diario(
+                    let cfg = config_hilo.read().unwrap().clone();
+                    if let Err(e) = report::generar_reporte(
                         &project_path_hilo,
-                        &config_hilo,
+                        &cfg,
                         Arc::clone(&stats_hilo),
-                    );
+                        &report::OpcionesReporte::default(),
+                    ) {
+                        println!("{}", tf("reporte.error", &[("error", &e.to_string())]));
+                    }
                 } else if cmd == "m" {
                     let s = stats_hilo.lock().unwrap();
+                    println!("\n{}", t("main.dashboard_titulo").bright_green().bold());
+                    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                     println!(
-                        "\n{}",
-                        "📊 DASHBOARD DE RENDIMIENTO SENTINEL".bright_green().bold()
+                        "{}",
+                        tf(
+                            "main.dashboard_bugs",
+                            &[("n", &s.bugs_criticos_evitados.to_string().red().to_string())]
+                        )
+                    );
+                    println!(
+                        "{}",
+                        tf(
+                            "main.dashboard_timeouts",
+                            &[(
+                                "n",
+                                &s.tests_tiempo_agotado.to_string().yellow().to_string()
+                            )]
+                        )
                     );
-                    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                     println!(
-                        "🚫 Bugs Evitados:  {}",
-                        s.bugs_criticos_evitados.to_string().red()
+                        "{}",
+                        tf(
+                            "main.dashboard_costo",
+                            &[("costo", &format!("{:.4}", s.total_cost_usd))]
+                        )
                     );
-                    println!("💰 Costo Acumulado: ${:.4}", s.total_cost_usd);
-                    println!("🎟️ Tokens Usados:   {}", s.total_tokens_used);
                     println!(
-                        "⏳ Tiempo Ahorrado: {}h",
-                        (s.tiempo_estimado_ahorrado_mins as f32 / 60.0)
+                        "{}",
+                        tf(
+                            "main.dashboard_tokens",
+                            &[("n", &s.total_tokens_used.to_string())]
+                        )
+                    );
+                    println!(
+                        "{}",
+                        tf(
+                            "main.dashboard_tiempo",
+                            &[(
+                                "horas",
+                                &(s.tiempo_estimado_ahorrado_mins as f32 / 60.0).to_string()
+                            )]
+                        )
                     );
                     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
                 } else if cmd == "l" {
-                    print!(
-                        "⚠️  ¿Limpiar todo el caché? Esto eliminará las respuestas guardadas (s/n): "
-                    );
+                    print!("{}", t("main.confirmar_limpiar_cache"));
                     io::stdout().flush().unwrap();
                     let mut confirm = String::new();
-                    if io::stdin().read_line(&mut confirm).is_ok()
-                        && confirm.trim().to_lowercase() == "s"
-                    {
+                    if io::stdin().read_line(&mut confirm).is_ok() && es_afirmativo(&confirm) {
                         if let Err(e) = ai::limpiar_cache(&project_path_hilo) {
-                            println!("   ❌ Error al limpiar caché: {}", e);
+                            println!(
+                                "{}",
+                                tf("main.error_limpiar_cache", &[("error", &e.to_string())])
+                            );
                         }
                     } else {
-                        println!("   ⏭️  Limpieza de caché cancelada.");
+                        println!("{}", t("main.limpieza_cancelada"));
                     }
                 } else if cmd == "h" || cmd == "help" {
-                    ui::mostrar_ayuda(Some(&config_hilo));
+                    ui::mostrar_ayuda(Some(&config_hilo.read().unwrap()));
                 } else if cmd == "t" {
                     // Ver sugerencias de testing complementarias
-                    let cfg = &config_hilo;
+                    let cfg = &config_hilo.read().unwrap().clone();
                     if let Some(testing_fw) = &cfg.testing_framework {
-                        if cfg.testing_status.as_ref().map_or(false, |s| s == "valid") {
-                            match ai::obtener_sugerencias_complementarias(&project_path_hilo, cfg, testing_fw) {
+                        if cfg.testing_status.as_deref() == Some("valid") {
+                            match ai::obtener_sugerencias_complementarias(
+                                &project_path_hilo,
+                                cfg,
+                                testing_fw,
+                            ) {
                                 Ok(sugerencias) => {
                                     if !sugerencias.is_empty() {
-                                        println!("\n   {}", "🧪 FRAMEWORKS COMPLEMENTARIOS SUGERIDOS:".bold().yellow());
+                                        println!(
+                                            "\n   {}",
+                                            t("main.frameworks_complementarios").bold().yellow()
+                                        );
                                         for (i, sug) in sugerencias.iter().enumerate() {
                                             let priority_icon = match sug.priority {
                                                 1 => "🔥",
                                                 2 => "⭐",
                                                 _ => "💡",
                                             };
-                                            println!("\n   {} {}. {}", priority_icon, i + 1, sug.framework.bold());
+                                            println!(
+                                                "\n   {} {}. {}",
+                                                priority_icon,
+                                                i + 1,
+                                                sug.framework.bold()
+                                            );
                                             println!("      📝 {}", sug.reason);
                                             println!("      💻 {}", sug.install_command.cyan());
                                         }
                                         println!();
                                     } else {
-                                        println!("   ✅ Tu stack de testing está completo. No hay sugerencias adicionales.");
+                                        println!("{}", t("main.testing_completo"));
                                     }
                                 }
                                 Err(e) => {
-                                    println!("   ⚠️  Error al obtener sugerencias: {}", e.to_string().yellow());
+                                    println!(
+                                        "{}",
+                                        tf(
+                                            "main.error_sugerencias",
+                                            &[("error", &e.to_string().yellow().to_string())]
+                                        )
+                                    );
                                 }
                             }
                         } else {
-                            println!("   ℹ️  El comando [t] solo está disponible cuando el testing está configurado correctamente.");
+                            println!("{}", t("main.comando_t_no_disponible"));
                         }
                     } else {
-                        println!("   ℹ️  No hay testing configurado. Ejecuta 'sentinel init' para detectar frameworks de testing.");
+                        println!("{}", t("main.sin_testing"));
                     }
                 } else if cmd == "x" {
-                    print!("⚠️  ¿Reiniciar configuración? (s/n): ");
+                    print!("{}", t("main.confirmar_reiniciar"));
                     io::stdout().flush().unwrap();
                     let mut confirm = String::new();
-                    if io::stdin().read_line(&mut confirm).is_ok()
-                        && confirm.trim().to_lowercase() == "s"
-                    {
+                    if io::stdin().read_line(&mut confirm).is_ok() && es_afirmativo(&confirm) {
                         let _ = SentinelConfig::eliminar(&project_path_hilo);
                         std::process::exit(0);
                     }
@@ -162,21 +266,65 @@ fn main() {
 
     // Watcher
     let config_watcher = Arc::clone(&config);
+    let project_path_watcher = project_path.clone();
     let mut watcher = notify::recommended_watcher(move |res: Result<Event, notify::Error>| {
-        if let Ok(event) = res {
-            if let EventKind::Modify(_) = event.kind {
-                for path in event.paths {
-                    if !config_watcher.debe_ignorar(&path) {
-                        let _ = tx.send(path);
-                    }
+        let Ok(event) = res else { return };
+        for path in event.paths {
+            let nombre = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or_default();
+            if files::es_archivo_test(nombre) {
+                if matches!(
+                    event.kind,
+                    EventKind::Modify(_) | EventKind::Create(_) | EventKind::Remove(_)
+                ) {
+                    let _ = tx_grafo.send(path);
                 }
+                continue;
+            }
+            if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                continue;
+            }
+            if nombre == ".sentinelrc.toml" {
+                let nuevos = recargar_config(&project_path_watcher, &config_watcher);
+                if !nuevos.is_empty() {
+                    let _ = tx_vigilar.send(nuevos);
+                }
+            } else if path.parent() == Some(project_path_watcher.as_path()) {
+                // El resto de la raíz (jest.config.ts, index.ts...) no se revisa
+                continue;
+            } else if matches!(event.kind, EventKind::Modify(_))
+                && !workspace::debe_ignorar(
+                    &config_watcher.read().unwrap(),
+                    &project_path_watcher,
+                    &path,
+                )
+            {
+                // Una ejecución de tests en curso queda obsoleta
+                tests::notificar_cambio();
+                let _ = tx.send(path);
             }
         }
     })
     .unwrap();
+    // src/ de la raíz y el directorio de cada paquete del workspace
+    for dir in workspace::directorios_a_vigilar(&config.read().unwrap(), &project_path) {
+        watcher.watch(&dir, RecursiveMode::Recursive).unwrap();
+    }
+    // Raíz sin recursión: solo para detectar cambios en .sentinelrc.toml
     watcher
-        .watch(&project_path.join("src"), RecursiveMode::Recursive)
+        .watch(&project_path, RecursiveMode::NonRecursive)
         .unwrap();
+    // El callback del watcher no puede agregar directorios (bloquearía su propio
+    // hilo): los paquetes nuevos se vigilan desde aquí, dueño del watcher
+    thread::spawn(move || {
+        for dirs in rx_vigilar {
+            for dir in dirs {
+                let _ = watcher.watch(&dir, RecursiveMode::Recursive);
+            }
+        }
+    });
 
     let leer_respuesta = move || -> Option<String> {
         *esperando_input.lock().unwrap() = true;
@@ -191,12 +339,18 @@ fn main() {
 
     println!(
         "\n{} {}",
-        format!("🛡️ Sentinel v{} activo en:", config::SENTINEL_VERSION).green().bold(),
+        tf("main.activo_en", &[("version", config::SENTINEL_VERSION)])
+            .green()
+            .bold(),
         project_path.display()
     );
 
     // Mostrar ayuda de comandos al inicio
-    ui::mostrar_ayuda(Some(&config));
+    ui::mostrar_ayuda(Some(&config.read().unwrap()));
+
+    // Grafo de imports para encontrar los tests que dependen de cada archivo
+    let mut grafo = graph::GrafoDependencias::cargar(&project_path);
+    let _ = grafo.guardar(&project_path);
 
     let mut ultimo_cambio: HashMap<PathBuf, Instant> = HashMap::new();
     while let Ok(changed_path) = rx.recv() {
@@ -208,13 +362,20 @@ fn main() {
         }
 
         let ahora = Instant::now();
-        if let Some(ultimo) = ultimo_cambio.get(&changed_path) {
-            if ahora.duration_since(*ultimo) < std::time::Duration::from_secs(10) {
-                continue;
-            }
+        if let Some(ultimo) = ultimo_cambio.get(&changed_path)
+            && ahora.duration_since(*ultimo) < std::time::Duration::from_secs(10)
+        {
+            continue;
         }
         ultimo_cambio.insert(changed_path.clone(), ahora);
 
+        // Snapshot de la configuración vigente para todo el procesamiento de este cambio.
+        // En monorepos se usa la configuración y la raíz del paquete que contiene el archivo.
+        let contexto =
+            workspace::contexto_para(&config.read().unwrap(), &project_path, &changed_path);
+        let config = contexto.config;
+        let package_root = contexto.root;
+
         let file_name = changed_path
             .file_name()
             .unwrap()
@@ -223,34 +384,84 @@ fn main() {
             .to_string();
 
         // Intentar detectar si este archivo es un hijo de un servicio/módulo padre
-        let base_name = match files::detectar_archivo_padre(&changed_path, &project_path, &config.parent_patterns) {
+        let base_name = match files::detectar_archivo_padre(
+            &changed_path,
+            &package_root,
+            &config.parent_patterns,
+            &config.file_extensions,
+        ) {
             Some(padre) => {
                 println!(
-                    "   ℹ️  Archivo hijo detectado, usando tests del módulo: {}",
-                    padre.yellow()
+                    "{}",
+                    tf(
+                        "main.archivo_hijo",
+                        &[("modulo", &padre.yellow().to_string())]
+                    )
                 );
                 padre
             }
             None => {
                 // Usar el nombre del archivo actual
-                file_name.split('.').next().unwrap().to_string()
+                files::nombre_base(&changed_path, &config.parent_patterns)
             }
         };
 
-        // Buscar archivo de test usando los patrones del framework
-        let test_rel_path = files::buscar_archivo_test(&base_name, &project_path, &config.test_patterns);
+        // Buscar archivo de test usando los patrones del framework (del módulo y del archivo)
+        let tests_por_nombre = files::buscar_tests_por_nombre(
+            &base_name,
+            &changed_path,
+            &package_root,
+            &config.parent_patterns,
+            &config.test_patterns,
+        );
+
+        // Sumar los tests que dependen del archivo según el grafo de imports
+        // (relativos a la raíz del paquete, desde donde corre el runner)
+        for test in rx_grafo.try_iter() {
+            grafo.actualizar_archivo(&project_path, &test);
+        }
+        grafo.actualizar_archivo(&project_path, &changed_path);
+        let _ = grafo.guardar(&project_path);
+        let mut test_paths = tests_por_nombre;
+        for test in grafo.tests_afectados(&project_path, &changed_path) {
+            if let Ok(rel) = project_path.join(&test).strip_prefix(&package_root) {
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                if !test_paths.contains(&rel) {
+                    test_paths.push(rel);
+                }
+            }
+        }
 
         // Si no existen tests, preguntar al usuario si quiere revisión del código
-        if test_rel_path.is_none() {
-            println!("\n🔔 CAMBIO EN: {}", file_name.cyan().bold());
+        // Test recién generado con IA que ya pasó (no hace falta repetir la ejecución)
+        let mut test_generado = false;
+        if test_paths.is_empty() {
             println!(
-                "{}",
-                "⚠️  No se encontraron tests para este archivo.".yellow()
+                "\n{}",
+                tf(
+                    "main.cambio_en",
+                    &[("archivo", &file_name.cyan().bold().to_string())]
+                )
             );
-            print!("🔍 ¿Deseas que revise el código de todas formas? (s/n) [30s timeout]: ");
+            println!("{}", t("main.sin_tests").yellow());
+            print!("{}", t("main.preguntar_sin_tests"));
             io::stdout().flush().unwrap();
 
             match leer_respuesta() {
+                Some(respuesta) if respuesta == "g" => {
+                    if let Some(ruta) = generar_test_inicial(
+                        &changed_path,
+                        &base_name,
+                        &package_root,
+                        &project_path,
+                        &config,
+                        Arc::clone(&stats),
+                        &leer_respuesta,
+                    ) {
+                        test_paths = vec![ruta];
+                        test_generado = true;
+                    }
+                }
                 Some(respuesta) if respuesta == "s" => {
                     // Usuario quiere revisión sin tests
                     if let Ok(codigo) = std::fs::read_to_string(&changed_path) {
@@ -263,77 +474,340 @@ fn main() {
                             &changed_path,
                         ) {
                             Ok(true) => {
-                                println!("   ✅ Código revisado. Sin tests, no se realizará commit automático.");
+                                println!("{}", t("main.revisado_sin_tests"));
                             }
                             Ok(false) => {
-                                println!("   ⚠️  Se encontraron problemas. Revisa las sugerencias.");
+                                println!("{}", t("main.problemas_encontrados"));
                             }
                             Err(e) => {
-                                println!("   ❌ Error al analizar: {}", e);
+                                println!(
+                                    "{}",
+                                    tf("main.error_analizar", &[("error", &e.to_string())])
+                                );
                             }
                         }
                     }
                 }
                 _ => {
                     // Timeout o respuesta negativa
-                    println!("   ⏭️  Revisión omitida. Continuando monitoreo...");
+                    println!("{}", t("main.revision_omitida"));
                 }
             }
-            continue;
+            if !test_generado {
+                continue;
+            }
         }
 
         // Si hay tests disponibles, proceder con el flujo completo
-        if let Some(test_path) = test_rel_path {
-            println!("\n🔔 CAMBIO EN: {}", file_name.cyan().bold());
+        if !test_paths.is_empty() {
+            if !test_generado {
+                println!(
+                    "\n{}",
+                    tf(
+                        "main.cambio_en",
+                        &[("archivo", &file_name.cyan().bold().to_string())]
+                    )
+                );
+            }
+            if test_paths.len() > 1 {
+                println!(
+                    "{}",
+                    tf(
+                        "main.tests_dependientes",
+                        &[("n", &test_paths.len().to_string().yellow().to_string())]
+                    )
+                );
+            }
+            if let Some(paquete) = &contexto.paquete {
+                println!(
+                    "{}",
+                    tf(
+                        "main.paquete",
+                        &[("paquete", &paquete.yellow().to_string())]
+                    )
+                );
+            }
 
-            if let Ok(codigo) = std::fs::read_to_string(&changed_path) {
-                match ai::analizar_arquitectura(
+            if let Ok(codigo) = std::fs::read_to_string(&changed_path)
+                && let Ok(true) = ai::analizar_arquitectura(
                     &codigo,
                     &file_name,
                     Arc::clone(&stats),
                     &config,
                     &project_path,
                     &changed_path,
-                ) {
-                    Ok(true) => {
-                        if tests::ejecutar_tests(&test_path, &project_path).is_ok() {
-                            let _ = docs::actualizar_documentacion(
-                                &codigo,
+                )
+            {
+                let resultado = if test_generado {
+                    tests::ResultadoTests::Pasaron
+                } else {
+                    tests::ejecutar_tests(&test_paths, &package_root, &config)
+                };
+                match resultado {
+                    tests::ResultadoTests::TiempoAgotado => {
+                        let mut s = stats.lock().unwrap();
+                        s.tests_tiempo_agotado += 1;
+                        s.guardar(&project_path);
+                        continue;
+                    }
+                    tests::ResultadoTests::Cancelado => {
+                        // Permitir que el cambio más reciente del mismo archivo se procese
+                        ultimo_cambio.remove(&changed_path);
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                if resultado == tests::ResultadoTests::Pasaron {
+                    if coverage::verificar_cobertura(
+                        &changed_path,
+                        &package_root,
+                        &project_path,
+                        &config,
+                    ) {
+                        let mut codigo = codigo;
+                        if config.inline_doc_comments {
+                            match comments::proponer_comentarios(
                                 &changed_path,
                                 &config,
                                 Arc::clone(&stats),
                                 &project_path,
-                            );
-                            let msg = git::generar_mensaje_commit(
+                                &leer_respuesta,
+                            ) {
+                                Ok(true) => {
+                                    codigo =
+                                        std::fs::read_to_string(&changed_path).unwrap_or(codigo);
+                                    // El evento de nuestra propia escritura no es un cambio nuevo
+                                    ultimo_cambio.insert(changed_path.clone(), Instant::now());
+                                }
+                                Ok(false) => {}
+                                Err(e) => println!(
+                                    "{}",
+                                    tf("comentarios.error", &[("error", &e.to_string())])
+                                ),
+                            }
+                        }
+                        // Después de los comentarios, para que el hash guardado sea el del archivo final
+                        let _ = docs::actualizar_documentacion(
+                            &codigo,
+                            &changed_path,
+                            &config,
+                            Arc::clone(&stats),
+                            &project_path,
+                        );
+                        let msg = git::generar_mensaje_commit(
+                            &codigo,
+                            &file_name,
+                            &config,
+                            Arc::clone(&stats),
+                            &project_path,
+                        );
+                        println!(
+                            "\n{}",
+                            tf(
+                                "main.mensaje_commit",
+                                &[("mensaje", &msg.bright_cyan().bold().to_string())]
+                            )
+                        );
+                        print!("{}", t("main.preguntar_commit"));
+                        io::stdout().flush().unwrap();
+                        if let Some(r) = leer_respuesta() {
+                            git::preguntar_commit(&project_path, &msg, &r, &config);
+                        }
+                    }
+                } else {
+                    print!("\n{}", t("main.preguntar_ayuda_test"));
+                    io::stdout().flush().unwrap();
+                    match leer_respuesta().as_deref() {
+                        Some("s") => {
+                            let _ = tests::pedir_ayuda_test(
                                 &codigo,
-                                &file_name,
+                                &test_paths,
                                 &config,
                                 Arc::clone(&stats),
                                 &project_path,
+                                &package_root,
                             );
-                            println!("\n🚀 Mensaje: {}", msg.bright_cyan().bold());
-                            print!("📝 ¿Commit? (s/n): ");
-                            io::stdout().flush().unwrap();
-                            if let Some(r) = leer_respuesta() {
-                                git::preguntar_commit(&project_path, &msg, &r);
-                            }
-                        } else {
-                            print!("\n🔍 ¿Ayuda con test? (s/n): ");
-                            io::stdout().flush().unwrap();
-                            if leer_respuesta().as_deref() == Some("s") {
-                                let _ = tests::pedir_ayuda_test(
-                                    &codigo,
-                                    &test_path,
-                                    &config,
-                                    Arc::clone(&stats),
-                                    &project_path,
+                        }
+                        Some("f") => {
+                            if let Err(e) = fix::corregir_tests(
+                                &changed_path,
+                                &test_paths,
+                                &package_root,
+                                &project_path,
+                                &config,
+                                Arc::clone(&stats),
+                                &leer_respuesta,
+                            ) {
+                                println!(
+                                    "{}",
+                                    tf("main.error_correccion", &[("error", &e.to_string())])
                                 );
                             }
                         }
+                        _ => {}
                     }
-                    _ => {}
                 }
             }
         }
     }
 }
+
+/// Genera con IA un test inicial para un archivo sin tests
+///
+/// El test se ubica según el primer patrón de `test_patterns`, se muestra como
+/// diff para su aprobación y se ejecuta de inmediato.
+///
+/// # Retorna
+/// La ruta del test (relativa a `package_root`) si se creó y pasa.
+fn generar_test_inicial(
+    changed_path: &Path,
+    base_name: &str,
+    package_root: &Path,
+    project_path: &Path,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    leer_respuesta: &dyn Fn() -> Option<String>,
+) -> Option<String> {
+    let Some(ruta) = files::ruta_test_sugerida(
+        base_name,
+        Some(changed_path),
+        package_root,
+        &config.test_patterns,
+    ) else {
+        println!("{}", t("main.sin_patron_test"));
+        return None;
+    };
+    let destino = package_root.join(&ruta);
+    if destino.exists() {
+        println!(
+            "{}",
+            tf(
+                "main.test_ya_existe",
+                &[("ruta", &ruta.yellow().to_string())]
+            )
+        );
+        return None;
+    }
+
+    let codigo = std::fs::read_to_string(changed_path).ok()?;
+    let source_rel = changed_path
+        .strip_prefix(package_root)
+        .unwrap_or(changed_path)
+        .to_string_lossy()
+        .to_string();
+
+    println!("{}", t("main.generando_test").magenta());
+    let contenido = match ai::generar_test_inicial(
+        &codigo,
+        &source_rel,
+        &ruta,
+        config,
+        Arc::clone(&stats),
+        project_path,
+    ) {
+        Ok(c) => c,
+        Err(e) => {
+            println!(
+                "{}",
+                tf("main.error_generar_test", &[("error", &e.to_string())])
+            );
+            return None;
+        }
+    };
+
+    println!();
+    diff::mostrar_diff(&diff::diff_unificado("", &contenido, &ruta));
+    print!(
+        "\n{}",
+        tf(
+            "main.preguntar_crear_test",
+            &[("ruta", &ruta.cyan().to_string())]
+        )
+    );
+    io::stdout().flush().unwrap();
+    if !leer_respuesta().is_some_and(|r| es_afirmativo(&r)) {
+        println!("{}", t("main.test_descartado"));
+        return None;
+    }
+
+    if let Some(dir) = destino.parent()
+        && let Err(e) = std::fs::create_dir_all(dir)
+    {
+        println!(
+            "{}",
+            tf(
+                "main.error_crear",
+                &[
+                    ("ruta", &dir.display().to_string()),
+                    ("error", &e.to_string())
+                ]
+            )
+        );
+        return None;
+    }
+    if let Err(e) = std::fs::write(&destino, format!("{}\n", contenido.trim_end())) {
+        println!(
+            "{}",
+            tf(
+                "main.error_escribir",
+                &[("ruta", &ruta), ("error", &e.to_string())]
+            )
+        );
+        return None;
+    }
+
+    match tests::ejecutar_tests(std::slice::from_ref(&ruta), package_root, config) {
+        tests::ResultadoTests::Pasaron => Some(ruta),
+        resultado => {
+            if resultado == tests::ResultadoTests::TiempoAgotado {
+                let mut s = stats.lock().unwrap();
+                s.tests_tiempo_agotado += 1;
+                s.guardar(project_path);
+            }
+            println!(
+                "{}",
+                tf(
+                    "main.test_generado_fallo",
+                    &[("ruta", &ruta.yellow().to_string())]
+                )
+            );
+            None
+        }
+    }
+}
+
+/// Recarga `.sentinelrc.toml` en caliente y reemplaza la configuración compartida
+///
+/// Si la nueva configuración no es válida, se mantiene la anterior y se informa
+/// el error. Solo imprime un resumen cuando hay cambios reales (los editores suelen
+/// emitir varios eventos por guardado).
+///
+/// # Retorna
+/// Los directorios de paquetes que hay que empezar a vigilar.
+fn recargar_config(project_path: &Path, config: &RwLock<SentinelConfig>) -> Vec<PathBuf> {
+    match SentinelConfig::recargar(project_path) {
+        Ok(nueva) => {
+            let mut actual = config.write().unwrap();
+            let vigilados = workspace::directorios_a_vigilar(&actual, project_path);
+            let Some(cambios) = actual.aplicar_recarga(nueva) else {
+                return vec![];
+            };
+            i18n::establecer(actual.language.as_deref());
+            println!("\n{}", t("main.config_recargada").bright_cyan().bold());
+            for cambio in cambios {
+                println!("   • {}", cambio);
+            }
+            // Paquetes agregados o quitados a mano en el archivo
+            ui::sincronizar_workspace(project_path, &mut actual);
+            workspace::directorios_a_vigilar(&actual, project_path)
+                .into_iter()
+                .filter(|d| !vigilados.contains(d))
+                .collect()
+        }
+        Err(e) => {
+            println!("\n{} {}", t("main.config_invalida").yellow(), e);
+            vec![]
+        }
+    }
+}
@@ -0,0 +1,701 @@
+//! # Revisión de Pull Requests
+//!
+//! `sentinel review --base main` revisa todos los cambios de la rama actual
+//! respecto a una rama base (`git diff base...HEAD`), en lugar de un archivo
+//! al guardarlo. Por cada archivo vigilado que cambió:
+//!
+//! 1. Corre las reglas de seguridad locales sobre la versión nueva y conserva
+//!    los hallazgos de las líneas modificadas.
+//! 2. Envía sus hunks, con las reglas de arquitectura vigentes, a la IA, que
+//!    responde con hallazgos estructurados (línea, gravedad, regla).
+//!
+//! El resultado es un resumen ejecutivo en Markdown (veredicto, métricas,
+//! checklist de calidad y sugerencias clasificadas) listo para pegar en la
+//! descripción del PR. Cada archivo revisado queda registrado en
+//! `.sentinel/reviews.jsonl` para `sentinel report`.
+
+use crate::ai::client::{TaskType, consultar_ia_con_sistema};
+use crate::ai::injection::{INSTRUCCIONES_SISTEMA, delimitar};
+use crate::ai::prompts;
+use crate::audit;
+use crate::config::SentinelConfig;
+use crate::files;
+use crate::i18n::{self, t, tf};
+use crate::report;
+use crate::rules::{self, security};
+use crate::secrets;
+use crate::stats::SentinelStats;
+use crate::workspace;
+use colored::*;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// Resumen escrito cuando no se indica `--output` (relativo a la raíz)
+const SALIDA_POR_DEFECTO: &str = ".sentinel/pr-review.md";
+
+/// Tamaño máximo de los hunks enviados en una consulta; un archivo más grande
+/// se revisa en varias
+const MAX_DIFF_CONSULTA: usize = 12_000;
+
+/// Opciones de `sentinel review`
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpcionesRevision {
+    /// Ref contra la que se compara (ej: `main`)
+    pub base: String,
+    /// Ref revisada; sin definir, `HEAD`
+    pub head: Option<String>,
+    /// Archivo del resumen; sin definir, `.sentinel/pr-review.md`
+    pub salida: Option<PathBuf>,
+}
+
+/// Clasificación de una sugerencia
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Gravedad {
+    /// Debe corregirse antes de mergear
+    Critica,
+    /// Mejora sugerida, no bloqueante
+    Opcional,
+    /// Buena práctica u optimización
+    Informativa,
+}
+
+/// Sugerencia sobre un archivo del PR
+#[derive(Debug, Clone, PartialEq)]
+struct Observacion {
+    archivo: String,
+    linea: Option<usize>,
+    gravedad: Gravedad,
+    regla: Option<String>,
+    mensaje: String,
+}
+
+/// Archivo modificado en el PR (`git diff --numstat` + `--name-status`)
+#[derive(Debug, PartialEq)]
+struct ArchivoCambiado {
+    ruta: String,
+    /// `A`, `M`, `D`...
+    estado: char,
+    inserciones: u32,
+    borrados: u32,
+}
+
+/// Respuesta de la IA para un grupo de hunks
+#[derive(Debug, Deserialize)]
+struct RespuestaRevision {
+    veredicto: String,
+    #[serde(default)]
+    hallazgos: Vec<HallazgoIa>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HallazgoIa {
+    linea: Option<usize>,
+    severidad: String,
+    regla: Option<String>,
+    mensaje: String,
+}
+
+/// Estado de un punto del checklist
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Estado {
+    Ok,
+    Advertencia,
+    Falla,
+}
+
+impl Estado {
+    fn icono(&self) -> &'static str {
+        match self {
+            Estado::Ok => "✅",
+            Estado::Advertencia => "⚠️",
+            Estado::Falla => "❌",
+        }
+    }
+}
+
+/// Ejecuta git en el proyecto; `None` si falla
+fn git(project_path: &Path, args: &[&str]) -> Option<String> {
+    let salida = Command::new("git")
+        .args(args)
+        .current_dir(project_path)
+        .output()
+        .ok()?;
+    salida
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&salida.stdout).to_string())
+}
+
+/// Combina `git diff --name-status` y `--numstat` (sin detección de renombres)
+fn interpretar_cambios(estados: &str, numstat: &str) -> Vec<ArchivoCambiado> {
+    let lineas: BTreeMap<&str, (u32, u32)> = numstat
+        .lines()
+        .filter_map(|l| {
+            let mut campos = l.splitn(3, '\t');
+            // Los binarios tienen "-" en lugar de números
+            let inserciones = campos.next()?.parse().unwrap_or(0);
+            let borrados = campos.next()?.parse().unwrap_or(0);
+            Some((campos.next()?, (inserciones, borrados)))
+        })
+        .collect();
+    estados
+        .lines()
+        .filter_map(|l| {
+            let (estado, ruta) = l.split_once('\t')?;
+            let (inserciones, borrados) = lineas.get(ruta).copied().unwrap_or_default();
+            Some(ArchivoCambiado {
+                ruta: ruta.to_string(),
+                estado: estado.chars().next()?,
+                inserciones,
+                borrados,
+            })
+        })
+        .collect()
+}
+
+/// Separa el diff de un archivo en hunks y los agrupa hasta `maximo` bytes
+///
+/// Cada grupo lleva el encabezado del diff (`diff --git`, `---`, `+++`) para
+/// que la IA sepa a qué archivo pertenece. Un hunk más grande que `maximo`
+/// forma un grupo propio.
+fn agrupar_hunks(diff: &str, maximo: usize) -> Vec<String> {
+    let (encabezado, resto) = match diff.find("\n@@") {
+        Some(i) => diff.split_at(i + 1),
+        None => return vec![],
+    };
+    let mut hunks: Vec<String> = Vec::new();
+    for linea in resto.lines() {
+        if linea.starts_with("@@") || hunks.is_empty() {
+            hunks.push(String::new());
+        }
+        let hunk = hunks.last_mut().unwrap();
+        hunk.push_str(linea);
+        hunk.push('\n');
+    }
+
+    let mut grupos: Vec<String> = Vec::new();
+    for hunk in hunks {
+        match grupos.last_mut() {
+            Some(grupo) if grupo.len() + hunk.len() <= maximo => grupo.push_str(&hunk),
+            _ => grupos.push(format!("{}{}", encabezado, hunk)),
+        }
+    }
+    grupos
+}
+
+/// Extrae la respuesta JSON de la IA; `None` si no tiene el formato pedido
+fn interpretar_respuesta(respuesta: &str, archivo: &str) -> Option<(bool, Vec<Observacion>)> {
+    let inicio = respuesta.find('{')?;
+    let fin = respuesta.rfind('}')?;
+    let revision: RespuestaRevision = serde_json::from_str(respuesta.get(inicio..=fin)?).ok()?;
+    let veredicto = revision.veredicto.trim().to_uppercase();
+    let critico = match veredicto.as_str() {
+        "CRITICO" | "CRÍTICO" => true,
+        "SEGURO" => false,
+        _ => return None,
+    };
+    let observaciones = revision
+        .hallazgos
+        .into_iter()
+        .filter(|h| !h.mensaje.trim().is_empty())
+        .map(|h| Observacion {
+            archivo: archivo.to_string(),
+            linea: h.linea,
+            gravedad: match h.severidad.trim().to_lowercase().as_str() {
+                "critica" | "crítica" | "critico" | "crítico" => Gravedad::Critica,
+                "opcional" => Gravedad::Opcional,
+                _ => Gravedad::Informativa,
+            },
+            regla: h.regla.filter(|r| !r.trim().is_empty()),
+            mensaje: h.mensaje.trim().replace('\n', " "),
+        })
+        .collect();
+    Some((critico, observaciones))
+}
+
+/// Revisa con IA los hunks de un archivo
+///
+/// # Retorna
+/// `None` si alguna consulta falló o no tuvo el formato pedido.
+fn revisar_hunks(
+    archivo: &str,
+    diff: &str,
+    hallazgos_locales: &str,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    project_path: &Path,
+) -> Option<(bool, Vec<Observacion>)> {
+    let sistema = INSTRUCCIONES_SISTEMA;
+    let reglas = rules::formatear_para_prompt(&config.reglas_efectivas());
+    let mut critico = false;
+    let mut observaciones = Vec::new();
+    for grupo in agrupar_hunks(diff, MAX_DIFF_CONSULTA) {
+        let prompt = prompts::renderizar(
+            "pr-review",
+            project_path,
+            &[
+                ("framework", config.framework.as_str()),
+                ("language", &config.code_language),
+                ("file_name", archivo),
+                ("rules", &reglas),
+                ("findings", hallazgos_locales),
+                ("diff", &delimitar("DIFF", &grupo)),
+            ],
+        );
+        let respuesta = consultar_ia_con_sistema(
+            sistema,
+            prompt,
+            TaskType::Deep,
+            config,
+            Arc::clone(&stats),
+            project_path,
+        )
+        .ok()?;
+        let (grupo_critico, grupo_observaciones) = interpretar_respuesta(&respuesta, archivo)?;
+        critico |= grupo_critico;
+        observaciones.extend(grupo_observaciones);
+    }
+    Some((critico, observaciones))
+}
+
+/// Resumen ejecutivo en Markdown
+#[allow(clippy::too_many_arguments)]
+fn componer_resumen(
+    base: &str,
+    head: &str,
+    cambios: &[ArchivoCambiado],
+    revisables: &BTreeSet<String>,
+    no_revisados: &[String],
+    observaciones: &[Observacion],
+    checklist: &[(Estado, String)],
+    criticos: &BTreeSet<String>,
+) -> String {
+    let bloqueado = checklist.iter().any(|(e, _)| *e == Estado::Falla);
+    let advertencias = checklist.iter().any(|(e, _)| *e == Estado::Advertencia)
+        || observaciones
+            .iter()
+            .any(|o| o.gravedad == Gravedad::Opcional);
+    let veredicto = if bloqueado {
+        t("pr.veredicto_bloqueado")
+    } else if advertencias {
+        t("pr.veredicto_advertencias")
+    } else {
+        t("pr.veredicto_aprobado")
+    };
+
+    let inserciones: u32 = cambios.iter().map(|c| c.inserciones).sum();
+    let borrados: u32 = cambios.iter().map(|c| c.borrados).sum();
+    let mut md = format!(
+        "## {}\n\n**{}**\n\n{}\n\n",
+        tf("pr.titulo", &[("head", head), ("base", base)]),
+        veredicto,
+        tf(
+            "pr.metricas",
+            &[
+                ("inserciones", &inserciones.to_string()),
+                ("borrados", &borrados.to_string()),
+                ("archivos", &cambios.len().to_string()),
+                (
+                    "revisados",
+                    &(revisables.len() - no_revisados.len()).to_string()
+                ),
+            ]
+        )
+    );
+
+    md.push_str(&format!("### {}\n\n", t("pr.checklist_titulo")));
+    for (estado, texto) in checklist {
+        md.push_str(&format!("- {} {}\n", estado.icono(), texto));
+    }
+
+    for (gravedad, clave) in [
+        (Gravedad::Critica, "pr.criticas_titulo"),
+        (Gravedad::Opcional, "pr.opcionales_titulo"),
+        (Gravedad::Informativa, "pr.informativas_titulo"),
+    ] {
+        let de_gravedad: Vec<&Observacion> = observaciones
+            .iter()
+            .filter(|o| o.gravedad == gravedad)
+            .collect();
+        if de_gravedad.is_empty() {
+            continue;
+        }
+        md.push_str(&format!("\n### {}\n\n", t(clave)));
+        for o in de_gravedad {
+            let ubicacion = match o.linea {
+                Some(linea) => format!("{}:{}", o.archivo, linea),
+                None => o.archivo.clone(),
+            };
+            let regla = o
+                .regla
+                .as_ref()
+                .map(|r| format!("[{}] ", r))
+                .unwrap_or_default();
+            md.push_str(&format!("- `{}` {}{}\n", ubicacion, regla, o.mensaje));
+        }
+    }
+
+    md.push_str(&format!(
+        "\n### {}\n\n| {} |\n|---|:---:|---:|---:|:---:|\n",
+        t("pr.archivos_titulo"),
+        t("pr.columnas_archivos")
+    ));
+    for c in cambios {
+        let revision = if criticos.contains(&c.ruta) {
+            "❌"
+        } else if no_revisados.contains(&c.ruta) {
+            "⚠️"
+        } else if revisables.contains(&c.ruta) {
+            "✅"
+        } else {
+            "—"
+        };
+        md.push_str(&format!(
+            "| `{}` | {} | +{} | -{} | {} |\n",
+            c.ruta, c.estado, c.inserciones, c.borrados, revision
+        ));
+    }
+    md
+}
+
+/// `sentinel review`: revisión de los cambios de la rama respecto a `base`
+///
+/// # Retorna
+/// Código de salida: 0 si no hay hallazgos críticos ni secretos, 1 si los hay
+/// (el PR no debería mergearse) y 2 si no se pudo hacer la revisión.
+pub fn ejecutar(project_path: &Path, opciones: &OpcionesRevision) -> i32 {
+    let Some(config) = SentinelConfig::load(project_path) else {
+        eprintln!("{}", t("pr.sin_config").red().bold());
+        return 2;
+    };
+    i18n::establecer(config.language.as_deref());
+    let stats = Arc::new(Mutex::new(SentinelStats::cargar(project_path)));
+
+    let head = opciones.head.clone().unwrap_or_else(|| "HEAD".to_string());
+    let rango = format!("{}...{}", opciones.base, head);
+    let nombre_head = git(project_path, &["rev-parse", "--abbrev-ref", &head])
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| head.clone());
+
+    let diff_args = |extra: &[&str]| -> Option<String> {
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff", "--no-renames"];
+        args.extend_from_slice(extra);
+        args.push(&rango);
+        git(project_path, &args)
+    };
+    let (Some(estados), Some(numstat), Some(diff_u0)) = (
+        diff_args(&["--name-status"]),
+        diff_args(&["--numstat"]),
+        diff_args(&["-U0"]),
+    ) else {
+        eprintln!("{}", tf("pr.error_git", &[("rango", &rango)]).red());
+        return 2;
+    };
+    let cambios = interpretar_cambios(&estados, &numstat);
+    if cambios.is_empty() {
+        println!("{}", tf("pr.sin_cambios", &[("rango", &rango)]).yellow());
+        return 0;
+    }
+
+    // Líneas agregadas por archivo (para filtrar hallazgos locales y buscar secretos)
+    let agregadas = secrets::lineas_agregadas(&diff_u0);
+    let mut lineas_modificadas: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+    for (archivo, linea, _) in &agregadas {
+        lineas_modificadas
+            .entry(archivo)
+            .or_default()
+            .insert(*linea);
+    }
+
+    let revisables: Vec<&ArchivoCambiado> = cambios
+        .iter()
+        .filter(|c| {
+            // Cada paquete del workspace vigila sus propias extensiones
+            let contexto =
+                workspace::contexto_para(&config, project_path, &project_path.join(&c.ruta));
+            c.estado != 'D' && !contexto.config.debe_ignorar(Path::new(&c.ruta))
+        })
+        .collect();
+    println!(
+        "{}",
+        tf(
+            "pr.revisando",
+            &[("n", &revisables.len().to_string()), ("rango", &rango)]
+        )
+        .bright_cyan()
+        .bold()
+    );
+
+    let mut observaciones: Vec<Observacion> = Vec::new();
+    let mut criticos: BTreeSet<String> = BTreeSet::new();
+    let mut no_revisados: Vec<String> = Vec::new();
+    for cambio in &revisables {
+        println!("   🔍 {}", cambio.ruta);
+        let ruta_completa = project_path.join(&cambio.ruta);
+        let contexto = workspace::contexto_para(&config, project_path, &ruta_completa);
+
+        // Reglas locales sobre la versión revisada, solo en líneas modificadas
+        let contenido = git(
+            project_path,
+            &["show", &format!("{}:{}", head, cambio.ruta)],
+        )
+        .unwrap_or_default();
+        let modificadas = lineas_modificadas.get(cambio.ruta.as_str());
+        let locales: Vec<_> = security::analizar(&contenido, &ruta_completa, &contexto.config)
+            .into_iter()
+            .filter(|h| modificadas.is_some_and(|m| m.contains(&h.linea)))
+            .collect();
+        let critico_local = locales
+            .iter()
+            .any(|h| h.severidad == security::Severidad::Critica);
+        for h in &locales {
+            observaciones.push(Observacion {
+                archivo: cambio.ruta.clone(),
+                linea: Some(h.linea),
+                gravedad: match h.severidad {
+                    security::Severidad::Critica => Gravedad::Critica,
+                    security::Severidad::Advertencia => Gravedad::Opcional,
+                },
+                regla: Some(h.regla.to_string()),
+                mensaje: h.descripcion.to_string(),
+            });
+        }
+        let seccion_locales = if locales.is_empty() {
+            String::new()
+        } else {
+            format!(
+                "HALLAZGOS DEL ANÁLISIS ESTÁTICO LOCAL (deterministas, confírmalos):\n{}\n\n",
+                security::formatear_hallazgos(&locales)
+            )
+        };
+
+        let diff_archivo = git(
+            project_path,
+            &[
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                "--no-renames",
+                "-U5",
+                &rango,
+                "--",
+                &cambio.ruta,
+            ],
+        )
+        .unwrap_or_default();
+        let critico = match revisar_hunks(
+            &cambio.ruta,
+            &diff_archivo,
+            &seccion_locales,
+            &contexto.config,
+            Arc::clone(&stats),
+            project_path,
+        ) {
+            Some((critico_ia, de_ia)) => {
+                observaciones.extend(de_ia);
+                critico_ia || critico_local
+            }
+            None => {
+                println!(
+                    "{}",
+                    tf("pr.error_archivo", &[("archivo", &cambio.ruta)]).yellow()
+                );
+                no_revisados.push(cambio.ruta.clone());
+                critico_local
+            }
+        };
+        if critico {
+            criticos.insert(cambio.ruta.clone());
+        }
+        report::registrar_revision(
+            project_path,
+            &ruta_completa,
+            critico,
+            locales.len(),
+            report::Origen::Pr,
+        );
+    }
+    {
+        let mut s = stats.lock().unwrap();
+        s.total_analisis += (revisables.len() - no_revisados.len()) as u32;
+        s.guardar(project_path);
+    }
+
+    // Checklist de calidad
+    let nombre = |ruta: &str| ruta.rsplit('/').next().unwrap_or(ruta).to_string();
+    let secretos: Vec<String> = agregadas
+        .iter()
+        .filter(|(archivo, _, texto)| {
+            !secrets::detectar_secretos_en_archivo(archivo, texto, &config.secret_allowlist)
+                .is_empty()
+        })
+        .map(|(archivo, linea, _)| format!("{}:{}", archivo, linea))
+        .collect();
+    let hay_fuentes = !revisables.is_empty();
+    let hay_tests = cambios
+        .iter()
+        .any(|c| c.estado != 'D' && files::es_archivo_test(&nombre(&c.ruta)));
+    let hay_docs = cambios.iter().any(|c| c.ruta.ends_with(".md"));
+    let lockfiles: Vec<String> = cambios
+        .iter()
+        .filter(|c| audit::es_lockfile(&nombre(&c.ruta)))
+        .map(|c| c.ruta.clone())
+        .collect();
+    let n_criticas = observaciones
+        .iter()
+        .filter(|o| o.gravedad == Gravedad::Critica)
+        .count();
+
+    let estado = |ok: bool, si_no: Estado| if ok { Estado::Ok } else { si_no };
+    let mut checklist = vec![
+        (
+            estado(criticos.is_empty() && n_criticas == 0, Estado::Falla),
+            if criticos.is_empty() && n_criticas == 0 {
+                t("pr.check_sin_criticos")
+            } else {
+                tf(
+                    "pr.check_criticos",
+                    &[("n", &n_criticas.max(criticos.len()).to_string())],
+                )
+            },
+        ),
+        (
+            estado(secretos.is_empty(), Estado::Falla),
+            if secretos.is_empty() {
+                t("pr.check_sin_secretos")
+            } else {
+                tf(
+                    "pr.check_secretos",
+                    &[("ubicaciones", &secretos.join(", "))],
+                )
+            },
+        ),
+    ];
+    if hay_fuentes {
+        checklist.push((
+            estado(hay_tests, Estado::Advertencia),
+            t(if hay_tests {
+                "pr.check_tests"
+            } else {
+                "pr.check_sin_tests"
+            }),
+        ));
+        checklist.push((
+            estado(hay_docs, Estado::Advertencia),
+            t(if hay_docs {
+                "pr.check_docs"
+            } else {
+                "pr.check_sin_docs"
+            }),
+        ));
+    }
+    checklist.push((
+        estado(lockfiles.is_empty(), Estado::Advertencia),
+        if lockfiles.is_empty() {
+            t("pr.check_sin_dependencias")
+        } else {
+            tf(
+                "pr.check_dependencias",
+                &[("archivos", &lockfiles.join(", "))],
+            )
+        },
+    ));
+    checklist.push((
+        estado(no_revisados.is_empty(), Estado::Advertencia),
+        if no_revisados.is_empty() {
+            t("pr.check_revisados")
+        } else {
+            tf(
+                "pr.check_no_revisados",
+                &[("archivos", &no_revisados.join(", "))],
+            )
+        },
+    ));
+    let bloqueado = checklist.iter().any(|(e, _)| *e == Estado::Falla);
+
+    let resumen = componer_resumen(
+        &opciones.base,
+        &nombre_head,
+        &cambios,
+        &revisables.iter().map(|c| c.ruta.clone()).collect(),
+        &no_revisados,
+        &observaciones,
+        &checklist,
+        &criticos,
+    );
+    let ruta = opciones
+        .salida
+        .clone()
+        .unwrap_or_else(|| project_path.join(SALIDA_POR_DEFECTO));
+    if let Some(dir) = ruta.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let Err(e) = fs::write(&ruta, &resumen) {
+        eprintln!(
+            "{}",
+            tf("pr.error_escritura", &[("error", &e.to_string())]).red()
+        );
+        return 2;
+    }
+
+    println!("\n{}", resumen);
+    println!(
+        "{}",
+        tf("pr.escrito", &[("ruta", &ruta.display().to_string())]).green()
+    );
+    if bloqueado { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agrupar_hunks_con_encabezado() {
+        let diff = "diff --git a/a.ts b/a.ts\n--- a/a.ts\n+++ b/a.ts\n@@ -1,2 +1,2 @@\n-a\n+b\n@@ -10 +10 @@\n-c\n+d\n";
+        let grupos = agrupar_hunks(diff, 1_000);
+        assert_eq!(grupos.len(), 1);
+        assert!(grupos[0].starts_with("diff --git") && grupos[0].contains("+d"));
+
+        let grupos = agrupar_hunks(diff, 10);
+        assert_eq!(grupos.len(), 2);
+        assert!(grupos[1].starts_with("diff --git") && grupos[1].contains("@@ -10 +10 @@"));
+        assert!(!grupos[1].contains("+b"));
+        assert!(agrupar_hunks("Binary files differ\n", 10).is_empty());
+    }
+
+    #[test]
+    fn test_interpretar_respuesta_y_cambios() {
+        let respuesta = r#"```json
+{"veredicto": "CRITICO", "hallazgos": [
+  {"linea": 12, "severidad": "critica", "regla": "nestjs-01", "mensaje": "SQL concatenado"},
+  {"linea": null, "severidad": "opcional", "regla": "", "mensaje": "Extraer a un servicio"}
+]}
+```"#;
+        let (critico, observaciones) = interpretar_respuesta(respuesta, "src/a.ts").unwrap();
+        assert!(critico);
+        assert_eq!(observaciones[0].gravedad, Gravedad::Critica);
+        assert_eq!(observaciones[1].regla, None);
+        assert!(interpretar_respuesta("SEGURO, todo bien", "a").is_none());
+
+        let cambios = interpretar_cambios(
+            "M\tsrc/a.ts\nA\tlogo.png\nD\told.ts\n",
+            "3\t1\tsrc/a.ts\n-\t-\tlogo.png\n0\t20\told.ts\n",
+        );
+        assert_eq!(
+            cambios[0],
+            ArchivoCambiado {
+                ruta: "src/a.ts".to_string(),
+                estado: 'M',
+                inserciones: 3,
+                borrados: 1,
+            }
+        );
+        assert_eq!((cambios[1].inserciones, cambios[2].estado), (0, 'D'));
+    }
+}
@@ -0,0 +1,663 @@
+//! # Reportes de actividad
+//!
+//! `sentinel report` (y el comando `r` del monitor) resume los commits de un
+//! periodo —un día, una semana, un sprint— con el diffstat de cada uno y las
+//! revisiones que Sentinel hizo en ese tiempo. Cada revisión del monitor se
+//! registra en `.sentinel/reviews.jsonl`; el reporte se escribe en
+//! `reports_dir` (por defecto `docs/reports`) con las fechas del periodo en el
+//! nombre del archivo.
+//!
+//! Los límites `--since`/`--until` aceptan cualquier fecha que entienda git
+//! ("2026-10-01", "1 week ago", "yesterday"...): se convierten a segundos con
+//! `git rev-parse` para filtrar también el registro de revisiones.
+
+use crate::ai;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::config::SentinelConfig;
+use crate::docs::fecha_iso8601;
+use crate::i18n::{self, t, tf};
+use crate::stats::SentinelStats;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Registro de revisiones del monitor (relativo a la raíz)
+const RUTA_REVISIONES: &str = ".sentinel/reviews.jsonl";
+
+/// Directorio de reportes cuando `reports_dir` no está definido
+const DIRECTORIO_POR_DEFECTO: &str = "docs/reports";
+
+/// Inicio del periodo por defecto: hoy a las 00:00 (reporte diario)
+const DESDE_POR_DEFECTO: &str = "00:00:00";
+
+/// Commits que se envían a la IA (el reporte los lista todos)
+const MAX_COMMITS_PROMPT: usize = 300;
+
+/// Filtros del reporte; los que no se indican no restringen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OpcionesReporte {
+    pub since: Option<String>,
+    pub until: Option<String>,
+    pub author: Option<String>,
+    pub branch: Option<String>,
+}
+
+/// Flujo que hizo una revisión
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Origen {
+    /// El monitor, al guardar un archivo (las entradas sin origen son de este tipo)
+    #[default]
+    Monitor,
+    /// `sentinel review` sobre el diff de una rama
+    Pr,
+}
+
+/// Resultado de una revisión de arquitectura
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Revision {
+    /// Segundos desde 1970-01-01 (UTC)
+    pub fecha: u64,
+    /// Archivo relativo a la raíz del proyecto
+    pub archivo: String,
+    pub critico: bool,
+    /// Hallazgos del análisis de seguridad local
+    #[serde(default)]
+    pub hallazgos_locales: usize,
+    /// Autor según git (`Nombre <email>`), para filtrar con `--author`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autor: Option<String>,
+    /// Rama activa al revisar, para filtrar con `--branch`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rama: Option<String>,
+    #[serde(default)]
+    pub origen: Origen,
+}
+
+/// Commit del periodo con su diffstat
+#[derive(Debug, PartialEq)]
+struct Commit {
+    hash: String,
+    autor: String,
+    fecha: String,
+    asunto: String,
+    archivos: u32,
+    inserciones: u32,
+    borrados: u32,
+}
+
+/// Salida de un comando git sin el salto final; `None` si falla
+fn git(project_path: &Path, args: &[&str]) -> Option<String> {
+    let salida = Command::new("git")
+        .args(args)
+        .current_dir(project_path)
+        .output()
+        .ok()?;
+    let texto = String::from_utf8_lossy(&salida.stdout).trim().to_string();
+    (salida.status.success() && !texto.is_empty()).then_some(texto)
+}
+
+fn ahora() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Agrega una revisión a `.sentinel/reviews.jsonl`
+///
+/// Los errores de escritura se ignoran: el registro no debe interrumpir el monitor.
+pub fn registrar_revision(
+    project_path: &Path,
+    file_path: &Path,
+    critico: bool,
+    hallazgos_locales: usize,
+    origen: Origen,
+) {
+    let revision = Revision {
+        fecha: ahora(),
+        archivo: file_path
+            .strip_prefix(project_path)
+            .unwrap_or(file_path)
+            .to_string_lossy()
+            .replace('\\', "/"),
+        critico,
+        hallazgos_locales,
+        // `GIT_AUTHOR_IDENT` es `Nombre <email> segundos zona`
+        autor: git(project_path, &["var", "GIT_AUTHOR_IDENT"])
+            .and_then(|i| i.split_once('>').map(|(autor, _)| format!("{}>", autor))),
+        rama: git(project_path, &["symbolic-ref", "--short", "HEAD"]),
+        origen,
+    };
+    let ruta = project_path.join(RUTA_REVISIONES);
+    if let Some(dir) = ruta.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    if let (Ok(linea), Ok(mut archivo)) = (
+        serde_json::to_string(&revision),
+        OpenOptions::new().create(true).append(true).open(&ruta),
+    ) {
+        let _ = writeln!(archivo, "{}", linea);
+    }
+}
+
+/// Revisiones registradas dentro de `[desde, hasta]` (líneas inválidas se ignoran)
+///
+/// Con `--author` o `--branch` solo se cuentan las revisiones con ese autor o
+/// rama; las registradas sin esos datos quedan fuera.
+fn cargar_revisiones(
+    project_path: &Path,
+    desde: u64,
+    hasta: u64,
+    opciones: &OpcionesReporte,
+) -> Vec<Revision> {
+    fs::read_to_string(project_path.join(RUTA_REVISIONES))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| serde_json::from_str::<Revision>(l).ok())
+        .filter(|r| (desde..=hasta).contains(&r.fecha))
+        .filter(|r| {
+            opciones.author.as_ref().is_none_or(|a| {
+                r.autor
+                    .as_ref()
+                    .is_some_and(|autor| autor.contains(a.as_str()))
+            })
+        })
+        .filter(|r| opciones.branch.is_none() || r.rama == opciones.branch)
+        .collect()
+}
+
+/// Desfase de la zona horaria local en segundos (`date +%z`); 0 (UTC) si no se puede obtener
+fn desfase_local() -> i64 {
+    Command::new("date")
+        .arg("+%z")
+        .output()
+        .ok()
+        .and_then(|o| interpretar_desfase(String::from_utf8_lossy(&o.stdout).trim()))
+        .unwrap_or(0)
+}
+
+/// `"-0300"` → `-10800`
+fn interpretar_desfase(texto: &str) -> Option<i64> {
+    let signo = match texto.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let horas: i64 = texto.get(1..3)?.parse().ok()?;
+    let minutos: i64 = texto.get(3..5)?.parse().ok()?;
+    Some(signo * (horas * 3_600 + minutos * 60))
+}
+
+/// Fecha `AAAA-MM-DD` de un instante en la zona local
+///
+/// git interpreta `--since`/`--until` en hora local, así que el periodo se
+/// nombra con fechas locales y no UTC.
+fn fecha_local(segundos: u64, desfase: i64) -> String {
+    fecha_iso8601(segundos.saturating_add_signed(desfase))[..10].to_string()
+}
+
+/// Convierte una fecha de git (`--since`/`--until`) a segundos con `git rev-parse`
+fn limite_periodo(project_path: &Path, flag: &str, valor: &str) -> Option<u64> {
+    let salida = Command::new("git")
+        .args(["rev-parse", &format!("{}={}", flag, valor)])
+        .current_dir(project_path)
+        .output()
+        .ok()?;
+    // `--since` imprime `--max-age=N` y `--until`, `--min-age=N`
+    String::from_utf8_lossy(&salida.stdout)
+        .trim()
+        .rsplit_once('=')
+        .and_then(|(_, n)| n.parse().ok())
+}
+
+/// Commits del periodo con `git log --shortstat` (sin merges)
+fn leer_commits(
+    project_path: &Path,
+    desde: &str,
+    opciones: &OpcionesReporte,
+) -> anyhow::Result<Vec<Commit>> {
+    let mut args = vec![
+        "log".to_string(),
+        "--no-merges".to_string(),
+        "--date=short".to_string(),
+        "--pretty=format:%x1e%h%x1f%an%x1f%ad%x1f%s".to_string(),
+        "--shortstat".to_string(),
+        format!("--since={}", desde),
+    ];
+    if let Some(hasta) = &opciones.until {
+        args.push(format!("--until={}", hasta));
+    }
+    if let Some(autor) = &opciones.author {
+        args.push(format!("--author={}", autor));
+    }
+    if let Some(rama) = &opciones.branch {
+        args.push(rama.clone());
+        args.push("--".to_string());
+    }
+
+    // Diffstat en inglés aunque git esté traducido
+    let salida = Command::new("git")
+        .args(&args)
+        .env("LC_ALL", "C")
+        .current_dir(project_path)
+        .output()?;
+    if !salida.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&salida.stderr).trim());
+    }
+    Ok(interpretar_log(&String::from_utf8_lossy(&salida.stdout)))
+}
+
+/// Interpreta la salida de `git log` con el formato de `leer_commits`
+fn interpretar_log(salida: &str) -> Vec<Commit> {
+    salida
+        .split('\x1e')
+        .filter_map(|bloque| {
+            let mut lineas = bloque.lines();
+            let campos: Vec<&str> = lineas.next()?.splitn(4, '\x1f').collect();
+            let [hash, autor, fecha, asunto] = campos[..] else {
+                return None;
+            };
+            let (archivos, inserciones, borrados) = lineas
+                .find(|l| l.contains("changed"))
+                .map(interpretar_shortstat)
+                .unwrap_or_default();
+            Some(Commit {
+                hash: hash.to_string(),
+                autor: autor.to_string(),
+                fecha: fecha.to_string(),
+                asunto: asunto.to_string(),
+                archivos,
+                inserciones,
+                borrados,
+            })
+        })
+        .collect()
+}
+
+/// `" 3 files changed, 10 insertions(+), 2 deletions(-)"` → `(3, 10, 2)`
+fn interpretar_shortstat(linea: &str) -> (u32, u32, u32) {
+    let mut resultado = (0, 0, 0);
+    for parte in linea.split(',') {
+        let parte = parte.trim();
+        let numero = parte
+            .split_whitespace()
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or(0);
+        if parte.contains("changed") {
+            resultado.0 = numero;
+        } else if parte.contains("insertion") {
+            resultado.1 = numero;
+        } else if parte.contains("deletion") {
+            resultado.2 = numero;
+        }
+    }
+    resultado
+}
+
+/// Nombre del archivo: fechas del periodo y, si hay, autor y rama
+fn nombre_archivo(desde: &str, hasta: &str, opciones: &OpcionesReporte) -> String {
+    let mut partes = vec![if desde == hasta {
+        desde.to_string()
+    } else {
+        format!("{}_{}", desde, hasta)
+    }];
+    for filtro in [&opciones.author, &opciones.branch].into_iter().flatten() {
+        let slug: String = filtro
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        let slug = slug.trim_matches('-');
+        if !slug.is_empty() {
+            partes.push(slug.to_string());
+        }
+    }
+    format!("{}.md", partes.join("_"))
+}
+
+/// Línea de commits para el prompt (con diffstat)
+fn formatear_commits(commits: &[Commit]) -> String {
+    commits
+        .iter()
+        .take(MAX_COMMITS_PROMPT)
+        .map(|c| {
+            format!(
+                "{} {} ({}): {} [{} archivos, +{} -{}]",
+                c.fecha, c.hash, c.autor, c.asunto, c.archivos, c.inserciones, c.borrados
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Resumen de las revisiones para el prompt y el reporte
+fn resumir_revisiones(revisiones: &[Revision]) -> String {
+    if revisiones.is_empty() {
+        return t("reporte.sin_revisiones");
+    }
+    let criticas = revisiones.iter().filter(|r| r.critico).count();
+    let hallazgos: usize = revisiones.iter().map(|r| r.hallazgos_locales).sum();
+    let de_pr = revisiones.iter().filter(|r| r.origen == Origen::Pr).count();
+    // Un archivo puede revisarse en el monitor y en un PR: se cuentan por separado
+    let mut por_archivo: BTreeMap<&str, (usize, usize, usize)> = BTreeMap::new();
+    for r in revisiones {
+        let entrada = por_archivo.entry(&r.archivo).or_default();
+        match r.origen {
+            Origen::Monitor => entrada.0 += 1,
+            Origen::Pr => entrada.1 += 1,
+        }
+        entrada.2 += usize::from(r.critico);
+    }
+
+    let mut texto = tf(
+        "reporte.resumen_revisiones",
+        &[
+            ("total", &revisiones.len().to_string()),
+            ("monitor", &(revisiones.len() - de_pr).to_string()),
+            ("pr", &de_pr.to_string()),
+            ("aprobadas", &(revisiones.len() - criticas).to_string()),
+            ("criticas", &criticas.to_string()),
+            ("hallazgos", &hallazgos.to_string()),
+        ],
+    );
+    texto.push_str(&format!(
+        "\n\n| {} |\n|---|---:|---:|---:|\n",
+        t("reporte.columnas_revisiones")
+    ));
+    for (archivo, (monitor, pr, criticas)) in por_archivo {
+        texto.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            archivo, monitor, pr, criticas
+        ));
+    }
+    texto
+}
+
+/// Markdown del reporte: resumen de la IA (si hay) y los datos del periodo
+fn componer_reporte(
+    periodo: &str,
+    opciones: &OpcionesReporte,
+    resumen: Option<&str>,
+    commits: &[Commit],
+    revisiones: &[Revision],
+) -> String {
+    let mut md = format!("# {}\n\n", tf("reporte.titulo", &[("periodo", periodo)]));
+    let filtros: Vec<String> = [
+        ("reporte.filtro_autor", &opciones.author),
+        ("reporte.filtro_rama", &opciones.branch),
+    ]
+    .into_iter()
+    .filter_map(|(clave, valor)| valor.as_ref().map(|v| tf(clave, &[("valor", v)])))
+    .collect();
+    if !filtros.is_empty() {
+        md.push_str(&format!("{}\n\n", filtros.join(" · ")));
+    }
+    if let Some(resumen) = resumen {
+        md.push_str(resumen.trim());
+        md.push_str("\n\n");
+    }
+
+    let (archivos, inserciones, borrados) = commits.iter().fold((0, 0, 0), |acc, c| {
+        (
+            acc.0 + c.archivos,
+            acc.1 + c.inserciones,
+            acc.2 + c.borrados,
+        )
+    });
+    md.push_str(&format!(
+        "## {}\n\n{}\n\n",
+        tf(
+            "reporte.commits_titulo",
+            &[("n", &commits.len().to_string())]
+        ),
+        tf(
+            "reporte.totales",
+            &[
+                ("archivos", &archivos.to_string()),
+                ("inserciones", &inserciones.to_string()),
+                ("borrados", &borrados.to_string()),
+            ]
+        )
+    ));
+    if !commits.is_empty() {
+        md.push_str(&format!(
+            "| {} |\n|---|---|---|---|---:|---:|---:|\n",
+            t("reporte.columnas_commits")
+        ));
+        for c in commits {
+            md.push_str(&format!(
+                "| {} | `{}` | {} | {} | {} | +{} | -{} |\n",
+                c.fecha,
+                c.hash,
+                c.autor.replace('|', "\\|"),
+                c.asunto.replace('|', "\\|"),
+                c.archivos,
+                c.inserciones,
+                c.borrados
+            ));
+        }
+        md.push('\n');
+    }
+
+    md.push_str(&format!(
+        "## {}\n\n{}\n",
+        t("reporte.revisiones_titulo"),
+        resumir_revisiones(revisiones)
+    ));
+    md
+}
+
+/// Genera el reporte del periodo y lo escribe en `reports_dir`
+///
+/// # Retorna
+/// `Ok(Some(true))` si el reporte incluye el resumen de la IA, `Ok(Some(false))`
+/// si la IA falló y solo tiene los datos, y `Ok(None)` si en el periodo no hubo
+/// commits ni revisiones.
+pub fn generar_reporte(
+    project_path: &Path,
+    config: &SentinelConfig,
+    stats: Arc<Mutex<SentinelStats>>,
+    opciones: &OpcionesReporte,
+) -> anyhow::Result<Option<bool>> {
+    println!("\n📊 {}...", t("reporte.generando").magenta().bold());
+
+    let desde_git = opciones.since.as_deref().unwrap_or(DESDE_POR_DEFECTO);
+    let commits = leer_commits(project_path, desde_git, opciones)?;
+    let desde = limite_periodo(project_path, "--since", desde_git).unwrap_or(0);
+    let hasta = opciones
+        .until
+        .as_deref()
+        .and_then(|u| limite_periodo(project_path, "--until", u))
+        .unwrap_or_else(ahora);
+    let revisiones = cargar_revisiones(project_path, desde, hasta, opciones);
+    if commits.is_empty() && revisiones.is_empty() {
+        println!("{}", t("reporte.sin_actividad").yellow());
+        return Ok(None);
+    }
+
+    let desfase = desfase_local();
+    let fecha_desde = fecha_local(desde, desfase);
+    let fecha_hasta = fecha_local(hasta, desfase);
+    let periodo = if fecha_desde == fecha_hasta {
+        fecha_desde.clone()
+    } else {
+        format!("{} → {}", fecha_desde, fecha_hasta)
+    };
+
+    let prompt = prompts::renderizar(
+        "daily-report",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("period", &periodo),
+            (
+                "commits",
+                &delimitar("COMMITS", &formatear_commits(&commits)),
+            ),
+            ("reviews", &resumir_revisiones(&revisiones)),
+        ],
+    );
+    let resumen =
+        match ai::consultar_ia_dinamico(prompt, ai::TaskType::Deep, config, stats, project_path) {
+            Ok(resumen) => {
+                println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                println!("{}", t("reporte.titulo_consola").cyan().bold());
+                println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n");
+                println!("{}", resumen);
+                println!("\n━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+                Some(resumen)
+            }
+            Err(e) => {
+                println!(
+                    "{}",
+                    tf("reporte.error_ia", &[("error", &e.to_string())]).red()
+                );
+                None
+            }
+        };
+
+    let dir = project_path.join(
+        config
+            .reports_dir
+            .as_deref()
+            .unwrap_or(DIRECTORIO_POR_DEFECTO),
+    );
+    fs::create_dir_all(&dir)?;
+    let ruta = dir.join(nombre_archivo(&fecha_desde, &fecha_hasta, opciones));
+    fs::write(
+        &ruta,
+        componer_reporte(
+            &periodo,
+            opciones,
+            resumen.as_deref(),
+            &commits,
+            &revisiones,
+        ),
+    )?;
+    println!(
+        "{}",
+        tf(
+            "reporte.escrito",
+            &[(
+                "ruta",
+                &ruta
+                    .strip_prefix(project_path)
+                    .unwrap_or(&ruta)
+                    .display()
+                    .to_string()
+            )]
+        )
+        .green()
+    );
+    Ok(Some(resumen.is_some()))
+}
+
+/// `sentinel report`: reporte del periodo indicado
+///
+/// # Retorna
+/// Código de salida: 0 si se escribió el reporte (o no hubo actividad), 1 si
+/// la IA falló y el reporte solo tiene los datos, 2 si no se pudo generar.
+pub fn ejecutar(project_path: &Path, opciones: &OpcionesReporte) -> i32 {
+    let Some(config) = SentinelConfig::load(project_path) else {
+        eprintln!("{}", t("reporte.sin_config").red().bold());
+        return 2;
+    };
+    i18n::establecer(config.language.as_deref());
+    let stats = Arc::new(Mutex::new(SentinelStats::cargar(project_path)));
+
+    match generar_reporte(project_path, &config, stats, opciones) {
+        Ok(Some(false)) => 1,
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!(
+                "{}",
+                tf("reporte.error", &[("error", &e.to_string())]).red()
+            );
+            2
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpretar_log_con_diffstat() {
+        let salida = "\x1eabc1234\x1fAna\x1f2026-10-18\x1ffeat(auth): login | jwt\n 3 files changed, 10 insertions(+), 2 deletions(-)\n\n\x1edef5678\x1fLuis\x1f2026-10-17\x1fdocs: readme\n 1 file changed, 1 deletion(-)\n\x1e0a0a0a0\x1fAna\x1f2026-10-17\x1fchore: vacío\n";
+        let commits = interpretar_log(salida);
+        assert_eq!(commits.len(), 3);
+        assert_eq!(commits[0].asunto, "feat(auth): login | jwt");
+        assert_eq!(
+            (
+                commits[0].archivos,
+                commits[0].inserciones,
+                commits[0].borrados
+            ),
+            (3, 10, 2)
+        );
+        assert_eq!(
+            (
+                commits[1].archivos,
+                commits[1].inserciones,
+                commits[1].borrados
+            ),
+            (1, 0, 1)
+        );
+        assert_eq!(commits[2].archivos, 0);
+    }
+
+    #[test]
+    fn test_nombre_archivo_y_revisiones_del_periodo() {
+        let opciones = OpcionesReporte {
+            author: Some("Ana López".to_string()),
+            branch: Some("feature/login".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            nombre_archivo("2026-10-01", "2026-10-14", &opciones),
+            "2026-10-01_2026-10-14_ana-lópez_feature-login.md"
+        );
+        assert_eq!(
+            nombre_archivo("2026-10-18", "2026-10-18", &OpcionesReporte::default()),
+            "2026-10-18.md"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let archivo = dir.path().join("src/a.ts");
+        registrar_revision(dir.path(), &archivo, true, 2, Origen::Monitor);
+        registrar_revision(dir.path(), &archivo, false, 0, Origen::Pr);
+        let todas = OpcionesReporte::default();
+        let revisiones = cargar_revisiones(dir.path(), 0, u64::MAX, &todas);
+        assert_eq!(revisiones.len(), 2);
+        assert_eq!(revisiones[0].archivo, "src/a.ts");
+        assert!(cargar_revisiones(dir.path(), 0, 1, &todas).is_empty());
+        assert_eq!(revisiones[1].origen, Origen::Pr);
+        let resumen = resumir_revisiones(&revisiones);
+        assert!(resumen.contains("| `src/a.ts` | 1 | 1 | 1 |"));
+
+        // Sin rama (fuera de un repositorio) y de otro autor: los filtros las excluyen
+        assert!(cargar_revisiones(dir.path(), 0, u64::MAX, &opciones).is_empty());
+    }
+
+    #[test]
+    fn test_fechas_del_periodo_en_hora_local() {
+        assert_eq!(interpretar_desfase("-0300"), Some(-10_800));
+        assert_eq!(interpretar_desfase("+0530"), Some(19_800));
+        assert_eq!(interpretar_desfase("UTC"), None);
+
+        // 2026-10-19T02:00:00Z son las 23:00 del 18 en UTC-3
+        let segundos = 1_792_375_200;
+        assert_eq!(fecha_local(segundos, 0), "2026-10-19");
+        assert_eq!(fecha_local(segundos, -10_800), "2026-10-18");
+    }
+}
@@ -0,0 +1,188 @@
+//! # Paquetes de reglas de arquitectura integrados
+//!
+//! Sentinel incluye paquetes de reglas curados y versionados por framework
+//! (NestJS, Express, React, Next.js, Angular, Vue, Django, FastAPI, Laravel,
+//! Spring Boot, Go y Axum/Actix). Cada paquete es un archivo TOML en
+//! `src/rules/packs/` embebido en el binario con `include_str!`.
+//!
+//! El paquete se selecciona por el nombre del framework detectado
+//! (`FrameworkDetection::framework`) o por cualquiera de sus alias. Cada
+//! proyecto puede desactivar reglas por `id` (`disabled_rules`) y agregar
+//! reglas propias (`custom_rules`) en `.sentinelrc.toml`.
+
+use crate::config::ArchitectureRule;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+pub mod security;
+
+/// Paquete de reglas de un framework
+#[derive(Deserialize, Debug, Clone)]
+pub struct RulePack {
+    /// Nombre del framework al que aplica
+    pub framework: String,
+    /// Versión del paquete (se incrementa al modificar sus reglas)
+    pub version: String,
+    /// Nombres alternativos con los que se selecciona (minúsculas)
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    pub rules: Vec<ArchitectureRule>,
+}
+
+/// Contenido TOML de los paquetes embebidos en el binario
+const PACKS: &[&str] = &[
+    include_str!("packs/nestjs.toml"),
+    include_str!("packs/express.toml"),
+    include_str!("packs/react.toml"),
+    include_str!("packs/nextjs.toml"),
+    include_str!("packs/angular.toml"),
+    include_str!("packs/vue.toml"),
+    include_str!("packs/django.toml"),
+    include_str!("packs/fastapi.toml"),
+    include_str!("packs/laravel.toml"),
+    include_str!("packs/spring-boot.toml"),
+    include_str!("packs/go.toml"),
+    include_str!("packs/rust-web.toml"),
+];
+
+/// Retorna todos los paquetes de reglas integrados (se interpretan una sola vez)
+pub fn paquetes() -> &'static [RulePack] {
+    static PAQUETES: OnceLock<Vec<RulePack>> = OnceLock::new();
+    PAQUETES.get_or_init(|| {
+        PACKS
+            .iter()
+            .map(|contenido| {
+                toml::from_str(contenido).expect("paquete de reglas integrado con formato inválido")
+            })
+            .collect()
+    })
+}
+
+/// Busca el paquete de reglas para un framework (por nombre o alias, sin distinguir mayúsculas)
+///
+/// # Ejemplos
+/// ```ignore
+/// assert_eq!(pack_para("NestJS").unwrap().framework, "NestJS");
+/// assert_eq!(pack_para("Gin").unwrap().framework, "Go");
+/// assert!(pack_para("Generic").is_none());
+/// ```
+pub fn pack_para(framework: &str) -> Option<&'static RulePack> {
+    let buscado = framework.trim().to_lowercase();
+    paquetes()
+        .iter()
+        .find(|pack| pack.framework.to_lowercase() == buscado || pack.aliases.contains(&buscado))
+}
+
+/// Combina las reglas base con las personalizaciones del proyecto
+///
+/// Descarta las reglas cuyo `id` esté en `desactivadas` y agrega al final las
+/// reglas propias. Una regla propia con el mismo `id` que una del paquete la
+/// reemplaza en su posición original.
+pub fn aplicar_personalizacion(
+    base: Vec<ArchitectureRule>,
+    desactivadas: &[String],
+    propias: &[ArchitectureRule],
+) -> Vec<ArchitectureRule> {
+    let mut reglas: Vec<ArchitectureRule> = base
+        .into_iter()
+        .map(|regla| {
+            propias
+                .iter()
+                .find(|p| p.id == regla.id)
+                .cloned()
+                .unwrap_or(regla)
+        })
+        .collect();
+
+    for propia in propias {
+        if !reglas.iter().any(|r| r.id == propia.id) {
+            reglas.push(propia.clone());
+        }
+    }
+
+    reglas.retain(|r| !desactivadas.contains(&r.id));
+    reglas
+}
+
+/// Formatea las reglas como lista numerada para los prompts de la IA
+///
+/// Cada regla incluye su `id` (para que la IA lo cite en sus hallazgos) y,
+/// si existe, el primer ejemplo.
+pub fn formatear_para_prompt(reglas: &[ArchitectureRule]) -> String {
+    reglas
+        .iter()
+        .enumerate()
+        .map(|(i, regla)| {
+            let mut linea = format!("{}. [{}] {}", i + 1, regla.id, regla.description);
+            if let Some(ejemplo) = regla.examples.first() {
+                linea.push_str(&format!("\n   Ejemplo: {}", ejemplo));
+            }
+            linea
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn regla(id: &str, description: &str) -> ArchitectureRule {
+        ArchitectureRule {
+            id: id.to_string(),
+            description: description.to_string(),
+            examples: vec![],
+        }
+    }
+
+    #[test]
+    fn test_paquetes_integrados_validos() {
+        let packs = paquetes();
+        assert_eq!(packs.len(), PACKS.len());
+
+        let mut ids = HashSet::new();
+        for pack in packs {
+            assert!(!pack.version.is_empty(), "{} sin versión", pack.framework);
+            assert!(!pack.rules.is_empty(), "{} sin reglas", pack.framework);
+            for regla in &pack.rules {
+                assert!(ids.insert(regla.id.clone()), "id duplicado: {}", regla.id);
+                assert!(!regla.description.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_pack_para_nombre_y_alias() {
+        assert_eq!(pack_para("NestJS").unwrap().framework, "NestJS");
+        assert_eq!(pack_para("next.js").unwrap().framework, "Next.js");
+        assert_eq!(pack_para("Gin").unwrap().framework, "Go");
+        assert_eq!(pack_para("Actix").unwrap().framework, "Axum/Actix");
+        assert!(pack_para("Generic").is_none());
+    }
+
+    #[test]
+    fn test_aplicar_personalizacion() {
+        let base = vec![regla("a", "A"), regla("b", "B"), regla("c", "C")];
+        let propias = vec![regla("b", "B propia"), regla("team/x", "X")];
+        let desactivadas = vec!["c".to_string()];
+
+        let reglas = aplicar_personalizacion(base, &desactivadas, &propias);
+
+        let ids: Vec<&str> = reglas.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["a", "b", "team/x"]);
+        assert_eq!(reglas[1].description, "B propia");
+    }
+
+    #[test]
+    fn test_formatear_para_prompt() {
+        let mut r = regla("go/context-first", "Context primero");
+        r.examples = vec!["func Get(ctx context.Context)".to_string()];
+
+        let texto = formatear_para_prompt(&[r]);
+        assert_eq!(
+            texto,
+            "1. [go/context-first] Context primero\n   Ejemplo: func Get(ctx context.Context)"
+        );
+    }
+}
@@ -11,6 +11,8 @@ pub struct SentinelStats {
     pub tiempo_estimado_ahorrado_mins: u32,
     pub total_cost_usd: f64,
     pub total_tokens_used: u64,
+    #[serde(default)]
+    pub tests_tiempo_agotado: u32,
 }
 
 impl SentinelStats {
@@ -1,101 +1,577 @@
 //! Módulo de ejecución de tests
 //!
-//! Se encarga de correr los tests con Jest y reportar resultados.
+//! Se encarga de correr los tests con el runner del proyecto (o del paquete
+//! del workspace) y reportar resultados.
 
 use crate::ai;
+use crate::ai::injection::delimitar;
+use crate::ai::prompts;
+use crate::coverage;
+use crate::i18n::{t, tf};
 use colored::*;
+use std::collections::BTreeSet;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use crate::config::SentinelConfig;
 use crate::stats::SentinelStats;
 
-/// Ejecuta los tests de un archivo específico usando Jest.
+/// Construye el comando para ejecutar un conjunto de archivos de test según el runner configurado
 ///
-/// La salida de Jest se muestra en tiempo real en la consola.
-pub fn ejecutar_tests(test_path: &str, project_path: &Path) -> Result<(), String> {
-    println!("🧪 Ejecutando tests: {}", test_path.cyan());
-    println!(); // Línea en blanco para separar
-
-    // Usar .status() para que la salida se muestre en tiempo real
-    let status = Command::new("npx")
-        .args(["jest", test_path, "--passWithNoTests", "--colors"])
-        .current_dir(project_path)
-        .status()
-        .map_err(|e| format!("Error al ejecutar Jest: {}", e))?;
+/// Si `test_command` (de la raíz o del paquete del archivo) fue personalizado,
+/// se usa ese comando con las rutas de los tests al final. Si no, se usa
+/// `testing_framework` (Jest, Vitest, Mocha, Pytest, Go test, Cargo test,
+/// PHPUnit, Pest, JUnit) y el gestor de paquetes para elegir el ejecutable.
+/// Si no hay runner conocido se usa Jest, el comportamiento histórico.
+///
+/// # Retorna
+/// Programa y argumentos listos para `Command::new(..).args(..)`.
+pub fn comando_tests(
+    config: &SentinelConfig,
+    test_paths: &[String],
+    colores: bool,
+) -> (String, Vec<String>) {
+    if let Some(comando) = comando_personalizado(config, test_paths) {
+        return comando;
+    }
+    let runner = config
+        .testing_framework
+        .as_deref()
+        .unwrap_or("Jest")
+        .to_lowercase();
+    let tests = test_paths.to_vec();
+    let stem = |test: &String| {
+        Path::new(test)
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string()
+    };
 
-    println!(); // Línea en blanco después de la salida de Jest
+    // Prefijo para ejecutar binarios locales de node_modules
+    let exec_node = |bin: &str| -> (String, Vec<String>) {
+        match config.manager.as_str() {
+            "pnpm" => (
+                "pnpm".to_string(),
+                vec!["exec".to_string(), bin.to_string()],
+            ),
+            "yarn" => ("yarn".to_string(), vec![bin.to_string()]),
+            "bun" => ("bunx".to_string(), vec![bin.to_string()]),
+            _ => ("npx".to_string(), vec![bin.to_string()]),
+        }
+    };
 
-    if status.success() {
-        println!("{}", "   ✅ Tests pasados con éxito".green());
-        Ok(())
+    let (programa, mut args) = if runner.contains("vitest") {
+        let (p, mut a) = exec_node("vitest");
+        a.push("run".to_string());
+        a.extend(tests);
+        (p, a)
+    } else if runner.contains("mocha") {
+        let (p, mut a) = exec_node("mocha");
+        a.extend(tests);
+        if colores {
+            a.push("--color".to_string());
+        }
+        (p, a)
+    } else if runner.contains("pytest") {
+        let programa = if config.manager == "poetry" {
+            "poetry"
+        } else {
+            "python"
+        };
+        let mut a = if programa == "poetry" {
+            vec!["run".to_string(), "pytest".to_string()]
+        } else {
+            vec!["-m".to_string(), "pytest".to_string()]
+        };
+        a.extend(tests);
+        (programa.to_string(), a)
+    } else if runner == "go" || runner.starts_with("go ") {
+        // go test trabaja por paquete: se ejecuta el directorio de cada archivo
+        let paquetes: BTreeSet<String> = tests
+            .iter()
+            .map(|test| {
+                let dir = Path::new(test)
+                    .parent()
+                    .map(|d| d.to_string_lossy().to_string())
+                    .filter(|d| !d.is_empty())
+                    .unwrap_or_else(|| ".".to_string());
+                format!("./{}", dir.trim_start_matches("./"))
+            })
+            .collect();
+        let mut a = vec!["test".to_string()];
+        a.extend(paquetes);
+        ("go".to_string(), a)
+    } else if runner.contains("cargo") {
+        // Los tests de integración (tests/*.rs) se filtran por nombre de archivo;
+        // si hay tests unitarios (src/) se ejecuta la suite completa
+        let mut a = vec!["test".to_string()];
+        if !tests.is_empty() && tests.iter().all(|t| t.starts_with("tests/")) {
+            for test in &tests {
+                a.extend(["--test".to_string(), stem(test)]);
+            }
+        }
+        ("cargo".to_string(), a)
+    } else if runner.contains("pest") {
+        ("vendor/bin/pest".to_string(), tests)
+    } else if runner.contains("phpunit") {
+        ("vendor/bin/phpunit".to_string(), tests)
+    } else if runner.contains("junit") {
+        let clases: Vec<String> = tests.iter().map(stem).collect();
+        (
+            "mvn".to_string(),
+            vec!["test".to_string(), format!("-Dtest={}", clases.join(","))],
+        )
     } else {
-        println!("{}", "   ❌ Tests fallaron".red());
-        Err("Tests fallidos. Revisa la salida anterior.".to_string())
+        let (p, mut a) = exec_node("jest");
+        a.extend(tests);
+        a.extend([
+            "--passWithNoTests".to_string(),
+            if colores { "--colors" } else { "--no-colors" }.to_string(),
+        ]);
+        (p, a)
+    };
+
+    if runner.contains("pytest") && colores {
+        args.push("--color=yes".to_string());
     }
+    (programa, args)
 }
 
-/// Captura el error de un test específico ejecutando Jest nuevamente.
-pub fn capturar_error_test(test_path: &str, project_path: &Path) -> String {
-    let output = Command::new("npx")
-        .args(["jest", test_path, "--passWithNoTests", "--no-colors"])
-        .current_dir(project_path)
-        .output();
+/// `test_command` definido por el usuario, si difiere del generado para el gestor
+///
+/// Las rutas de los tests se agregan al final; `npm run` necesita `--` para
+/// pasarlas al script.
+fn comando_personalizado(
+    config: &SentinelConfig,
+    test_paths: &[String],
+) -> Option<(String, Vec<String>)> {
+    let comando = config.test_command.trim();
+    if comando.is_empty() || comando == SentinelConfig::comando_test_por_defecto(&config.manager) {
+        return None;
+    }
+    let mut partes = comando.split_whitespace().map(str::to_string);
+    let programa = partes.next()?;
+    let mut args: Vec<String> = partes.collect();
+    if !test_paths.is_empty() {
+        if programa == "npm"
+            && args.first().is_some_and(|a| a == "run")
+            && !args.iter().any(|a| a == "--")
+        {
+            args.push("--".to_string());
+        }
+        args.extend(test_paths.iter().cloned());
+    }
+    Some((programa, args))
+}
+
+/// Resultado de una ejecución de tests
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResultadoTests {
+    Pasaron,
+    Fallaron,
+    /// Se superó `test_timeout_secs` y se terminó el grupo de procesos
+    TiempoAgotado,
+    /// Un cambio más reciente dejó obsoleta la ejecución
+    Cancelado,
+}
+
+/// Contador de cambios detectados por el watcher
+///
+/// Una ejecución de tests en primer plano se cancela si el contador cambia
+/// mientras corre: sus resultados ya no corresponden al código en disco.
+static GENERACION_CAMBIOS: AtomicU64 = AtomicU64::new(0);
+
+/// Intervalo de sondeo del proceso del runner
+const INTERVALO_SONDEO: Duration = Duration::from_millis(100);
+
+/// Registra un cambio de archivo (llamado desde el watcher)
+pub fn notificar_cambio() {
+    GENERACION_CAMBIOS.fetch_add(1, Ordering::SeqCst);
+}
 
-    match output {
-        Ok(out) => {
-            let stderr = String::from_utf8_lossy(&out.stderr).to_string();
-            let stdout = String::from_utf8_lossy(&out.stdout).to_string();
+/// Termina el proceso del runner y todos sus descendientes
+///
+/// El runner se lanza en su propio grupo de procesos, así que se mata el
+/// grupo completo (workers de Jest, servidores abiertos por los tests, etc.).
+fn terminar_grupo(hijo: &mut Child) {
+    #[cfg(unix)]
+    let _ = Command::new("kill")
+        .args(["-KILL", "--", &format!("-{}", hijo.id())])
+        .stderr(Stdio::null())
+        .status();
+    #[cfg(windows)]
+    let _ = Command::new("taskkill")
+        .args(["/F", "/T", "/PID", &hijo.id().to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    let _ = hijo.kill();
+    let _ = hijo.wait();
+}
+
+/// Lee una tubería del runner en un hilo para que no se bloquee al llenarse
+fn leer_en_hilo<R: Read + Send + 'static>(tuberia: Option<R>) -> Option<JoinHandle<String>> {
+    tuberia.map(|mut t| {
+        thread::spawn(move || {
+            let mut texto = String::new();
+            let _ = t.read_to_string(&mut texto);
+            texto
+        })
+    })
+}
 
-            // Combinar stdout y stderr para obtener todo el contexto del error
-            if !stderr.is_empty() {
-                format!("{}\n{}", stdout, stderr)
+/// Lanza el runner y espera su fin respetando el timeout y, si se pide, la cancelación
+///
+/// # Retorna
+/// El resultado y la salida capturada (stdout y stderr combinados; vacía si
+/// el comando hereda la consola).
+fn correr_runner(
+    comando: &mut Command,
+    config: &SentinelConfig,
+    cancelable: bool,
+) -> std::io::Result<(ResultadoTests, String)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        comando.process_group(0);
+    }
+
+    let generacion = GENERACION_CAMBIOS.load(Ordering::SeqCst);
+    let limite = (config.test_timeout_secs > 0)
+        .then(|| Instant::now() + Duration::from_secs(config.test_timeout_secs));
+    let mut hijo = comando.spawn()?;
+    let stdout = leer_en_hilo(hijo.stdout.take());
+    let stderr = leer_en_hilo(hijo.stderr.take());
+
+    let resultado = loop {
+        if let Some(status) = hijo.try_wait()? {
+            break if status.success() {
+                ResultadoTests::Pasaron
             } else {
-                stdout
-            }
+                ResultadoTests::Fallaron
+            };
+        }
+        if limite.is_some_and(|l| Instant::now() >= l) {
+            terminar_grupo(&mut hijo);
+            break ResultadoTests::TiempoAgotado;
+        }
+        if cancelable && GENERACION_CAMBIOS.load(Ordering::SeqCst) != generacion {
+            terminar_grupo(&mut hijo);
+            break ResultadoTests::Cancelado;
+        }
+        thread::sleep(INTERVALO_SONDEO);
+    };
+
+    let unir =
+        |lector: Option<JoinHandle<String>>| lector.and_then(|l| l.join().ok()).unwrap_or_default();
+    let (stdout, stderr) = (unir(stdout), unir(stderr));
+
+    // Combinar stdout y stderr para obtener todo el contexto del error
+    let salida = if !stderr.is_empty() {
+        format!("{}\n{}", stdout, stderr)
+    } else {
+        stdout
+    };
+    Ok((resultado, salida))
+}
+
+/// Ejecuta un conjunto de archivos de test con el runner configurado.
+///
+/// La salida del runner se muestra en tiempo real en la consola. Con cobertura
+/// activa se piden además los reportes que luego lee `coverage::verificar_cobertura`.
+/// La ejecución se corta al superar `test_timeout_secs` o si se detecta un
+/// cambio más reciente en el proyecto.
+pub fn ejecutar_tests(
+    test_paths: &[String],
+    project_path: &Path,
+    config: &SentinelConfig,
+) -> ResultadoTests {
+    println!(
+        "{}",
+        tf(
+            "tests.ejecutando",
+            &[("tests", &test_paths.join(", ").cyan().to_string())]
+        )
+    );
+    println!(); // Línea en blanco para separar
+
+    let (programa, mut args) = comando_tests(config, test_paths, true);
+    if config.cobertura_activa() {
+        args.extend(coverage::argumentos_runner(config));
+    }
+
+    // La salida se hereda para que se muestre en tiempo real
+    let mut comando = Command::new(&programa);
+    comando.args(&args).current_dir(project_path);
+    let resultado = match correr_runner(&mut comando, config, true) {
+        Ok((resultado, _)) => resultado,
+        Err(e) => {
+            println!(
+                "{}",
+                tf(
+                    "tests.error_ejecutar",
+                    &[("programa", &programa), ("error", &e.to_string())]
+                )
+            );
+            return ResultadoTests::Fallaron;
         }
-        Err(e) => format!("Error al capturar salida de Jest: {}", e),
+    };
+
+    println!(); // Línea en blanco después de la salida del runner
+
+    match resultado {
+        ResultadoTests::Pasaron => println!("{}", t("tests.pasaron").green()),
+        ResultadoTests::Fallaron => println!("{}", t("tests.fallaron").red()),
+        ResultadoTests::TiempoAgotado => println!(
+            "{}",
+            tf(
+                "tests.tiempo_agotado",
+                &[("segundos", &config.test_timeout_secs.to_string())]
+            )
+            .yellow()
+        ),
+        ResultadoTests::Cancelado => println!("{}", t("tests.cancelados").dimmed()),
     }
+    resultado
+}
+
+/// Captura el error de los tests ejecutando el runner nuevamente.
+pub fn capturar_error_test(
+    test_paths: &[String],
+    project_path: &Path,
+    config: &SentinelConfig,
+) -> String {
+    ejecutar_tests_capturando(test_paths, project_path, config).1
+}
+
+/// Ejecuta los tests sin mostrar la salida en consola.
+///
+/// Respeta `test_timeout_secs`; un tiempo agotado cuenta como fallo y se
+/// indica al final de la salida.
+///
+/// # Retorna
+/// `(pasaron, salida)` con stdout y stderr combinados.
+pub fn ejecutar_tests_capturando(
+    test_paths: &[String],
+    project_path: &Path,
+    config: &SentinelConfig,
+) -> (bool, String) {
+    let (programa, args) = comando_tests(config, test_paths, false);
+    let mut comando = Command::new(&programa);
+    comando
+        .args(&args)
+        .current_dir(project_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let (resultado, mut salida) = match correr_runner(&mut comando, config, false) {
+        Ok(r) => r,
+        Err(e) => {
+            return (
+                false,
+                tf(
+                    "tests.error_capturar",
+                    &[("programa", &programa), ("error", &e.to_string())],
+                ),
+            );
+        }
+    };
+    if resultado == ResultadoTests::TiempoAgotado {
+        salida.push_str(&format!(
+            "\n{}",
+            tf(
+                "tests.salida_tiempo_agotado",
+                &[("segundos", &config.test_timeout_secs.to_string())]
+            )
+        ));
+    }
+    (resultado == ResultadoTests::Pasaron, salida)
 }
 
 /// Pide ayuda a la IA cuando un test falla.
+///
+/// `test_root` es el directorio desde el que se ejecuta el runner (la raíz del
+/// paquete en monorepos); `project_path` se usa para caché y estadísticas.
 pub fn pedir_ayuda_test(
     codigo: &str,
-    test_path: &str,
+    test_paths: &[String],
     config: &SentinelConfig,
     stats: Arc<Mutex<SentinelStats>>,
     project_path: &Path,
+    test_root: &Path,
 ) -> anyhow::Result<()> {
-    println!(
-        "{}",
-        "🔍 Analizando el error con IA...".magenta()
-    );
+    println!("{}", t("tests.analizando_error").magenta());
+
+    // Capturar el error ejecutando el runner nuevamente
+    let error_jest = capturar_error_test(test_paths, test_root, config);
 
-    // Capturar el error ejecutando Jest nuevamente
-    let error_jest = capturar_error_test(test_path, project_path);
-
-    let prompt = format!(
-        "Eres un experto en NestJS que da soluciones directas y accionables.\n\n\
-        ERROR DEL TEST:\n{}\n\n\
-        CÓDIGO:\n{}\n\n\
-        INSTRUCCIONES:\n\
-        1. Identifica el problema en UNA oración\n\
-        2. Da la solución en formato de pasos numerados (máximo 3 pasos)\n\
-        3. Incluye SOLO el código que debe cambiar (no repitas todo el archivo)\n\
-        4. Sé ultra-conciso: máximo 150 palabras\n\n\
-        Formato esperado:\n\
-        🔴 PROBLEMA: [una línea]\n\
-        ✅ SOLUCIÓN:\n\
-        1. [paso específico]\n\
-        2. [paso específico]\n\
-        ```typescript\n[código a cambiar]\n```",
-        error_jest, codigo
+    let prompt = prompts::renderizar(
+        "test-diagnosis",
+        project_path,
+        &[
+            ("framework", config.framework.as_str()),
+            ("language", &config.code_language),
+            ("test_output", &delimitar("ERROR", &error_jest)),
+            ("code", &delimitar("CODIGO", codigo)),
+        ],
     );
 
     let respuesta =
         ai::consultar_ia_dinamico(prompt, ai::TaskType::Deep, config, stats, project_path)?;
 
-    println!("\n💡 SOLUCIÓN SUGERIDA:\n{}", respuesta.yellow());
+    println!("\n{}\n{}", t("tests.solucion_sugerida"), respuesta.yellow());
     Ok(())
 }
+
+#[cfg(test)]
+mod test_comandos {
+    use super::*;
+
+    fn config_con(manager: &str, runner: Option<&str>) -> SentinelConfig {
+        let mut config = SentinelConfig::default(
+            "demo".to_string(),
+            manager.to_string(),
+            "Generic".to_string(),
+            vec![],
+            vec![],
+            "typescript".to_string(),
+            vec![],
+            vec![],
+        );
+        config.testing_framework = runner.map(|r| r.to_string());
+        config
+    }
+
+    fn rutas(lista: &[&str]) -> Vec<String> {
+        lista.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_comando_tests_por_runner() {
+        let (p, a) = comando_tests(&config_con("npm", None), &rutas(&["src/a.spec.ts"]), false);
+        assert_eq!(p, "npx");
+        assert_eq!(
+            a,
+            vec!["jest", "src/a.spec.ts", "--passWithNoTests", "--no-colors"]
+        );
+
+        let (p, a) = comando_tests(
+            &config_con("pnpm", Some("Vitest")),
+            &rutas(&["src/a.test.ts"]),
+            false,
+        );
+        assert_eq!(p, "pnpm");
+        assert_eq!(a, vec!["exec", "vitest", "run", "src/a.test.ts"]);
+
+        let (p, a) = comando_tests(
+            &config_con("go", Some("Go test")),
+            &rutas(&["internal/user/user_test.go"]),
+            false,
+        );
+        assert_eq!(p, "go");
+        assert_eq!(a, vec!["test", "./internal/user"]);
+
+        let (p, a) = comando_tests(
+            &config_con("cargo", Some("Cargo test")),
+            &rutas(&["tests/api.rs"]),
+            false,
+        );
+        assert_eq!(p, "cargo");
+        assert_eq!(a, vec!["test", "--test", "api"]);
+
+        let (p, a) = comando_tests(
+            &config_con("pip", Some("Pytest")),
+            &rutas(&["tests/test_user.py"]),
+            false,
+        );
+        assert_eq!(p, "python");
+        assert_eq!(a, vec!["-m", "pytest", "tests/test_user.py"]);
+    }
+
+    #[test]
+    fn test_comando_tests_varios_archivos() {
+        let tests = rutas(&["src/a.spec.ts", "test/app.e2e-spec.ts"]);
+        let (_, a) = comando_tests(&config_con("yarn", Some("Jest")), &tests, true);
+        assert_eq!(
+            a,
+            vec![
+                "jest",
+                "src/a.spec.ts",
+                "test/app.e2e-spec.ts",
+                "--passWithNoTests",
+                "--colors"
+            ]
+        );
+
+        let tests = rutas(&["pkg/a/a_test.go", "pkg/a/b_test.go", "cmd/c_test.go"]);
+        let (_, a) = comando_tests(&config_con("go", Some("Go test")), &tests, false);
+        assert_eq!(a, vec!["test", "./cmd", "./pkg/a"]);
+
+        // Un test unitario en src/ obliga a correr toda la suite de cargo
+        let tests = rutas(&["tests/api.rs", "src/config.rs"]);
+        let (_, a) = comando_tests(&config_con("cargo", Some("Cargo test")), &tests, false);
+        assert_eq!(a, vec!["test"]);
+    }
+
+    #[test]
+    fn test_comando_tests_personalizado() {
+        let mut config = config_con("npm", Some("Jest"));
+        config.test_command = "npm run test:unit".to_string();
+        let (p, a) = comando_tests(&config, &rutas(&["src/a.spec.ts"]), false);
+        assert_eq!(p, "npm");
+        assert_eq!(a, vec!["run", "test:unit", "--", "src/a.spec.ts"]);
+
+        config.manager = "pnpm".to_string();
+        config.test_command = "pnpm --filter api test".to_string();
+        let (p, a) = comando_tests(&config, &rutas(&["src/a.spec.ts"]), false);
+        assert_eq!(p, "pnpm");
+        assert_eq!(a, vec!["--filter", "api", "test", "src/a.spec.ts"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout_termina_el_grupo() {
+        let mut config = config_con("npm", None);
+        config.test_timeout_secs = 1;
+        let inicio = Instant::now();
+        let mut comando = Command::new("sh");
+        comando
+            .args(["-c", "sleep 30 & echo iniciado; wait"])
+            .stdout(Stdio::piped());
+
+        let (resultado, salida) = correr_runner(&mut comando, &config, false).unwrap();
+        assert_eq!(resultado, ResultadoTests::TiempoAgotado);
+        // El `sleep` hijo también muere: si no, la tubería seguiría abierta 30s
+        assert!(inicio.elapsed() < Duration::from_secs(10));
+        assert_eq!(salida.trim(), "iniciado");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cambio_nuevo_cancela_el_grupo() {
+        let config = config_con("npm", None);
+        let inicio = Instant::now();
+        let mut comando = Command::new("sh");
+        comando
+            .args(["-c", "sleep 30 & echo iniciado; wait"])
+            .stdout(Stdio::piped());
+
+        let notificador = thread::spawn(|| {
+            thread::sleep(Duration::from_millis(300));
+            notificar_cambio();
+        });
+        let (resultado, salida) = correr_runner(&mut comando, &config, true).unwrap();
+        notificador.join().unwrap();
+
+        assert_eq!(resultado, ResultadoTests::Cancelado);
+        // La tubería solo se cierra si también murió el `sleep` del grupo
+        assert!(inicio.elapsed() < Duration::from_secs(10));
+        assert_eq!(salida.trim(), "iniciado");
+    }
+}
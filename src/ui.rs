@@ -4,6 +4,10 @@
 
 use crate::ai;
 use crate::config::{AIConfig, AIProvider, SentinelConfig};
+use crate::detector::{self, ResultadoDeteccion};
+use crate::i18n::{es_afirmativo, t, tf};
+use crate::rules;
+use crate::workspace;
 use colored::*;
 use dialoguer::theme::ColorfulTheme;
 use dialoguer::{Confirm, Input, Select};
@@ -49,7 +53,7 @@ pub fn mostrar_banner() {
     );
     println!(
         "{}",
-        "                 Vigilando tu código 24/7 ⚡".bright_yellow()
+        format!("                 {}", t("ui.lema")).bright_yellow()
     );
     println!();
 }
@@ -67,13 +71,18 @@ pub fn mostrar_banner() {
 ///
 /// Si el usuario ingresa un número inválido, por defecto selecciona el proyecto 1.
 pub fn seleccionar_proyecto() -> PathBuf {
-    println!("{}", "\n📂 Proyectos detectados:".bright_cyan().bold());
+    println!(
+        "{}",
+        format!("\n{}", t("ui.proyectos_detectados"))
+            .bright_cyan()
+            .bold()
+    );
 
     let entries = match fs::read_dir("../") {
         Ok(e) => e,
         Err(e) => {
-            eprintln!("{}", "❌ Error al leer el directorio padre.".red().bold());
-            eprintln!("   Error: {}", e);
+            eprintln!("{}", t("ui.error_directorio_padre").red().bold());
+            eprintln!("{}", tf("ui.detalle_error", &[("error", &e.to_string())]));
             std::process::exit(1);
         }
     };
@@ -85,12 +94,7 @@ pub fn seleccionar_proyecto() -> PathBuf {
         .collect();
 
     if proyectos.is_empty() {
-        eprintln!(
-            "{}",
-            "❌ No se encontraron proyectos en el directorio padre."
-                .red()
-                .bold()
-        );
+        eprintln!("{}", t("ui.sin_proyectos").red().bold());
         std::process::exit(1);
     }
 
@@ -98,11 +102,12 @@ pub fn seleccionar_proyecto() -> PathBuf {
         let nombre = p
             .file_name()
             .and_then(|n| n.to_str())
-            .unwrap_or("<nombre inválido>");
+            .map(String::from)
+            .unwrap_or_else(|| t("ui.nombre_invalido"));
         println!("{}. {}", i + 1, nombre);
     }
 
-    print!("\n👉 Selecciona número: ");
+    print!("\n{}", t("ui.selecciona_numero"));
     io::stdout().flush().unwrap();
     let mut input = String::new();
     io::stdin().read_line(&mut input).unwrap();
@@ -111,8 +116,11 @@ pub fn seleccionar_proyecto() -> PathBuf {
         Ok(n) if n > 0 && n <= proyectos.len() => n - 1,
         _ => {
             eprintln!(
-                "❌ Selección inválida. Usa un número entre 1 y {}",
-                proyectos.len()
+                "{}",
+                tf(
+                    "ui.seleccion_invalida",
+                    &[("max", &proyectos.len().to_string())]
+                )
             );
             std::process::exit(1);
         }
@@ -127,39 +135,26 @@ pub fn mostrar_ayuda(config: Option<&SentinelConfig>) {
         "\n{}",
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
     );
-    println!("{}", "⌨️  COMANDOS DISPONIBLES".bright_cyan().bold());
+    println!("{}", t("ui.comandos_disponibles").bright_cyan().bold());
     println!(
         "{}",
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".bright_cyan()
     );
-    println!("{}", "  p       Pausar/Reanudar monitoreo".dimmed());
-    println!(
-        "{}",
-        "  r       Generar reporte diario de productividad".dimmed()
-    );
-    println!(
-        "{}",
-        "  m       Ver dashboard de métricas (bugs, costos, tokens)".dimmed()
-    );
-    println!("{}", "  l       Limpiar caché de respuestas de IA".dimmed());
+    println!("{}", t("ui.ayuda_p").dimmed());
+    println!("{}", t("ui.ayuda_r").dimmed());
+    println!("{}", t("ui.ayuda_m").dimmed());
+    println!("{}", t("ui.ayuda_l").dimmed());
 
     // Mostrar comando T solo si hay testing configurado
-    if let Some(cfg) = config {
-        if cfg.testing_framework.is_some()
-            && cfg.testing_status.as_ref().map_or(false, |s| s == "valid")
-        {
-            println!(
-                "{}",
-                "  t       Ver sugerencias de testing complementarias".dimmed()
-            );
-        }
+    if let Some(cfg) = config
+        && cfg.testing_framework.is_some()
+        && cfg.testing_status.as_deref() == Some("valid")
+    {
+        println!("{}", t("ui.ayuda_t").dimmed());
     }
 
-    println!(
-        "{}",
-        "  x       Reiniciar configuración desde cero".dimmed()
-    );
-    println!("{}", "  h/help  Mostrar esta ayuda".dimmed());
+    println!("{}", t("ui.ayuda_x").dimmed());
+    println!("{}", t("ui.ayuda_h").dimmed());
     println!(
         "{}",
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━\n".bright_cyan()
@@ -177,16 +172,13 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
 
     let mut existia_config = false;
     let mut config = if let Some(cfg) = SentinelConfig::load(project_path) {
-        println!("{}", "🔄 Configuración existente encontrada".yellow());
-        println!("   💾 Preservando API keys y configuraciones personalizadas...");
+        println!("{}", t("ui.config_existente").yellow());
+        println!("{}", t("ui.preservando_config"));
         existia_config = true;
         cfg
     } else {
         // Nueva configuración - pedir API keys
-        println!(
-            "{}",
-            "🚀 Configurando nuevo proyecto en Sentinel...".bright_cyan()
-        );
+        println!("{}", t("ui.configurando_proyecto").bright_cyan());
 
         let mut config = SentinelConfig::default(
             nombre.clone(),
@@ -199,13 +191,13 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
             vec![],
         );
 
-        println!(
-            "\n{}",
-            "🤖 Configuración de Modelos AI".bright_magenta().bold()
-        );
+        println!("\n{}", t("ui.config_modelos").bright_magenta().bold());
 
         config.ai_configs = ask_ai_configs().unwrap_or_else(|e| {
-            eprintln!("⚠️  Error al configurar modelos: {}. Usando default.", e);
+            eprintln!(
+                "{}",
+                tf("ui.error_config_modelos", &[("error", &e.to_string())])
+            );
             vec![AIConfig {
                 name: "Claude Default".to_string(),
                 provider: AIProvider::Claude,
@@ -223,30 +215,72 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
     let framework_actual = config.framework.clone();
     let tiene_config_existente = existia_config;
 
-    // Detectar framework con IA (silenciosamente)
-    let deteccion = match ai::detectar_framework_con_ia(project_path, &config) {
-        Ok(d) => d,
-        Err(e) => {
+    // 1. Detección heurística local (sin costo de IA)
+    let (deteccion_local, candidatos) = match detector::detectar_framework_local(project_path) {
+        ResultadoDeteccion::Detectado(d) => (Some(d), vec![]),
+        ResultadoDeteccion::Ambiguo(c) => (None, c),
+        ResultadoDeteccion::Desconocido => (None, vec![]),
+    };
+
+    // 2. Solo se consulta a la IA si la heurística no fue concluyente o si se pidió
+    let deteccion = match deteccion_local {
+        Some(d) if !config.force_ai_detection => {
+            println!(
+                "{}",
+                tf(
+                    "ui.framework_local",
+                    &[("framework", &d.framework.green().to_string())]
+                )
+            );
+            d
+        }
+        _ if tiene_config_existente && !config.force_ai_detection => {
+            // Proyecto ambiguo o desconocido con configuración previa: no gastar IA en cada arranque
             println!(
-                "   ⚠️  Error al detectar framework: {}",
-                e.to_string().yellow()
+                "{}",
+                tf(
+                    "ui.deteccion_no_concluyente",
+                    &[("framework", &config.framework.green().to_string())]
+                )
             );
-            if tiene_config_existente {
-                println!("   ℹ️  Manteniendo configuración actual");
-                return config;
+            return config;
+        }
+        _ => {
+            if !candidatos.is_empty() {
+                let nombres = candidatos
+                    .iter()
+                    .map(|c| c.framework.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                println!(
+                    "{}",
+                    tf(
+                        "ui.proyecto_ambiguo",
+                        &[("candidatos", &nombres.yellow().to_string())]
+                    )
+                );
             }
-            crate::config::FrameworkDetection {
-                framework: "Generic".to_string(),
-                rules: vec![
-                    "Clean Code principles".to_string(),
-                    "SOLID design patterns".to_string(),
-                    "Code maintainability".to_string(),
-                    "Comprehensive testing".to_string(),
-                ],
-                extensions: vec!["js".to_string(), "ts".to_string()],
-                code_language: "typescript".to_string(),
-                parent_patterns: vec![],
-                test_patterns: vec!["{name}.test.ts".to_string(), "{name}.spec.ts".to_string()],
+            let nombres: Vec<String> = candidatos.iter().map(|c| c.framework.clone()).collect();
+            match ai::detectar_framework_con_ia(project_path, &config, &nombres) {
+                Ok(d) => d,
+                Err(e) => {
+                    println!(
+                        "{}",
+                        tf(
+                            "ui.error_detectar_framework",
+                            &[("error", &e.to_string().yellow().to_string())]
+                        )
+                    );
+                    if tiene_config_existente {
+                        println!("{}", t("ui.manteniendo_config"));
+                        return config;
+                    }
+                    // Preferir el primer candidato local antes que el perfil genérico
+                    candidatos
+                        .into_iter()
+                        .next()
+                        .unwrap_or_else(detector::perfil_generico)
+                }
             }
         }
     };
@@ -254,9 +288,13 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
     // Comparar con framework actual
     if tiene_config_existente && deteccion.framework == framework_actual {
         println!(
-            "   ✓ Framework: {} (sin cambios)",
-            deteccion.framework.green()
+            "{}",
+            tf(
+                "ui.framework_sin_cambios",
+                &[("framework", &deteccion.framework.green().to_string())]
+            )
         );
+        mostrar_paquete_reglas(&config);
 
         // Detectar frameworks de testing si no está ya configurado
         if config.testing_framework.is_none() || config.testing_status.is_none() {
@@ -272,10 +310,13 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
                 }
                 Err(e) => {
                     println!(
-                        "   ⚠️  Error al detectar testing framework: {}",
-                        e.to_string().yellow()
+                        "{}",
+                        tf(
+                            "ui.error_detectar_testing",
+                            &[("error", &e.to_string().yellow().to_string())]
+                        )
                     );
-                    println!("   ℹ️  Continuando sin detección de testing");
+                    println!("{}", t("ui.sin_deteccion_testing"));
                 }
             }
         } else {
@@ -295,29 +336,55 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
     }
 
     // Hay cambios o es primera vez - mostrar y confirmar
-    println!("\n{}", "📋 Framework Detectado:".bright_yellow().bold());
-    println!("   Framework: {}", deteccion.framework.bright_green());
-    println!("   Lenguaje: {}", deteccion.code_language.bright_green());
+    println!("\n{}", t("ui.framework_detectado").bright_yellow().bold());
     println!(
-        "   Extensiones: {}",
-        deteccion.extensions.join(", ").bright_green()
+        "{}",
+        tf(
+            "ui.framework",
+            &[("framework", &deteccion.framework.bright_green().to_string())]
+        )
+    );
+    println!(
+        "{}",
+        tf(
+            "ui.lenguaje",
+            &[(
+                "lenguaje",
+                &deteccion.code_language.bright_green().to_string()
+            )]
+        )
+    );
+    println!(
+        "{}",
+        tf(
+            "ui.extensiones",
+            &[(
+                "extensiones",
+                &deteccion.extensions.join(", ").bright_green().to_string()
+            )]
+        )
     );
 
     if tiene_config_existente {
         println!(
-            "\n   ⚠️  Cambio detectado: {} → {}",
-            framework_actual.yellow(),
-            deteccion.framework.green()
+            "\n{}",
+            tf(
+                "ui.cambio_detectado",
+                &[
+                    ("antes", &framework_actual.yellow().to_string()),
+                    ("despues", &deteccion.framework.green().to_string())
+                ]
+            )
         );
     }
 
-    print!("\n👉 ¿Es correcto? (s/n): ");
+    print!("\n{}", t("ui.es_correcto"));
     io::stdout().flush().unwrap();
     let mut confirmacion = String::new();
     io::stdin().read_line(&mut confirmacion).unwrap();
 
-    if confirmacion.trim().to_lowercase() != "s" {
-        println!("   ℹ️  Manteniendo configuración actual");
+    if !es_afirmativo(&confirmacion) {
+        println!("{}", t("ui.manteniendo_config"));
         return config;
     }
 
@@ -341,38 +408,127 @@ pub fn inicializar_sentinel(project_path: &Path) -> SentinelConfig {
         }
         Err(e) => {
             println!(
-                "   ⚠️  Error al detectar testing framework: {}",
-                e.to_string().yellow()
+                "{}",
+                tf(
+                    "ui.error_detectar_testing",
+                    &[("error", &e.to_string().yellow().to_string())]
+                )
             );
-            println!("   ℹ️  Continuando sin detección de testing");
+            println!("{}", t("ui.sin_deteccion_testing"));
         }
     }
 
     match config.save(project_path) {
         Ok(_) => println!(
-            "   💾 Configuración guardada en: {}",
-            project_path
-                .join(".sentinelrc.toml")
-                .display()
-                .to_string()
-                .cyan()
+            "{}",
+            tf(
+                "ui.config_guardada",
+                &[(
+                    "ruta",
+                    &project_path
+                        .join(".sentinelrc.toml")
+                        .display()
+                        .to_string()
+                        .cyan()
+                        .to_string()
+                )]
+            )
+        ),
+        Err(e) => eprintln!(
+            "{}",
+            tf("ui.error_guardar_config", &[("error", &e.to_string())])
         ),
-        Err(e) => eprintln!("   ❌ Error al guardar la configuración: {}", e),
     }
-    println!("{}", "✅ Configuración actualizada.".green());
+    mostrar_paquete_reglas(&config);
+    println!("{}", t("ui.config_actualizada").green());
     config
 }
 
+/// Descubre los paquetes del workspace y los sincroniza con `.sentinelrc.toml`
+///
+/// Los paquetes ya configurados conservan sus valores; los nuevos se detectan
+/// localmente (sin IA) y se guardan para que el usuario pueda ajustarlos.
+pub fn sincronizar_workspace(project_path: &Path, config: &mut SentinelConfig) {
+    let detectados = workspace::detectar_paquetes(project_path, config);
+    let paquetes = workspace::fusionar_paquetes(&config.packages, detectados);
+
+    if paquetes != config.packages {
+        config.packages = paquetes;
+        if let Err(e) = config.save(project_path) {
+            eprintln!(
+                "{}",
+                tf("ui.error_guardar_config", &[("error", &e.to_string())])
+            );
+        }
+    }
+
+    if config.packages.is_empty() {
+        return;
+    }
+
+    println!(
+        "\n{}",
+        tf(
+            "ui.workspace_paquetes",
+            &[("n", &config.packages.len().to_string())]
+        )
+        .bright_cyan()
+        .bold()
+    );
+    for paquete in &config.packages {
+        println!(
+            "   • {} ({}) → {} [{}]",
+            paquete.name.green(),
+            paquete.path.dimmed(),
+            paquete.framework,
+            paquete.test_command.cyan()
+        );
+    }
+}
+
+/// Informa qué paquete de reglas integrado se aplicará al análisis
+fn mostrar_paquete_reglas(config: &SentinelConfig) {
+    let total = config.reglas_efectivas().len();
+    match rules::pack_para(&config.framework) {
+        Some(pack) => println!(
+            "{}",
+            tf(
+                "ui.reglas_paquete",
+                &[
+                    ("framework", &pack.framework.green().to_string()),
+                    ("version", &pack.version),
+                    ("activas", &total.to_string()),
+                    ("desactivadas", &config.disabled_rules.len().to_string()),
+                    ("propias", &config.custom_rules.len().to_string())
+                ]
+            )
+        ),
+        None => println!(
+            "{}",
+            tf(
+                "ui.reglas_sin_paquete",
+                &[
+                    ("total", &total.to_string()),
+                    ("framework", &config.framework)
+                ]
+            )
+        ),
+    }
+}
+
 pub fn ask_ai_configs() -> anyhow::Result<Vec<AIConfig>> {
     let mut configs = Vec::new();
 
     loop {
-        println!("\n🤖 CONFIGURACIÓN DE LA IA (#{})", configs.len() + 1);
+        println!(
+            "\n{}",
+            tf("ui.config_ia", &[("n", &(configs.len() + 1).to_string())])
+        );
         println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
         // Pedir un nombre para esta configuración
         let name: String = Input::with_theme(&ColorfulTheme::default())
-            .with_prompt("Nombre para esta configuración (ej: Claude Pro, Ollama Local)")
+            .with_prompt(t("ui.nombre_config"))
             .interact_text()?;
 
         let providers = vec![
@@ -385,7 +541,7 @@ pub fn ask_ai_configs() -> anyhow::Result<Vec<AIConfig>> {
             "DeepSeek",
         ];
         let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Selecciona un proveedor de IA")
+            .with_prompt(t("ui.selecciona_proveedor"))
             .items(&providers)
             .default(0)
             .interact()?;
@@ -420,34 +576,34 @@ pub fn ask_ai_configs() -> anyhow::Result<Vec<AIConfig>> {
 
         // Solo pedimos la URL si es Ollama o si el usuario quiere cambiarla
         let api_url: String = Input::with_theme(&ColorfulTheme::default())
-            .with_prompt(format!("URL de la API para {}", provider.as_str()))
+            .with_prompt(tf("ui.url_api", &[("proveedor", provider.as_str())]))
             .default(env_url.unwrap_or(default_url))
             .interact_text()?;
 
         let api_key: String = if provider == crate::config::AIProvider::Ollama {
             Input::with_theme(&ColorfulTheme::default())
-                .with_prompt("API Key (opcional para Ollama)")
+                .with_prompt(t("ui.api_key_ollama"))
                 .allow_empty(true)
-                .default(env_key.unwrap_or_else(|| String::new()))
+                .default(env_key.unwrap_or_default())
                 .interact_text()?
         } else {
             Input::with_theme(&ColorfulTheme::default())
-                .with_prompt(format!("API Key para {}", provider.as_str()))
-                .default(env_key.unwrap_or_else(|| String::new()))
+                .with_prompt(tf("ui.api_key", &[("proveedor", provider.as_str())]))
+                .default(env_key.unwrap_or_default())
                 .interact_text()?
         };
 
         // Obtener modelos dinámicamente
         println!(
-            "🔍 Conectando con {} para obtener modelos...",
-            provider.as_str()
+            "{}",
+            tf("ui.obteniendo_modelos", &[("proveedor", provider.as_str())])
         );
         let model: String =
             match crate::ai::obtener_modelos_disponibles(&provider, &api_url, &api_key) {
                 Ok(mut models) if !models.is_empty() => {
                     models.sort();
                     let selection = Select::with_theme(&ColorfulTheme::default())
-                        .with_prompt("Selecciona el modelo")
+                        .with_prompt(t("ui.selecciona_modelo"))
                         .items(&models)
                         .default(0)
                         .interact()?;
@@ -455,19 +611,17 @@ pub fn ask_ai_configs() -> anyhow::Result<Vec<AIConfig>> {
                 }
                 Err(e) => {
                     println!(
-                        "⚠️  No se pudieron obtener los modelos automáticamente: {}",
-                        e
+                        "{}",
+                        tf("ui.error_obtener_modelos", &[("error", &e.to_string())])
                     );
                     Input::with_theme(&ColorfulTheme::default())
-                    .with_prompt(
-                        "Ingresa el nombre del modelo manualmente (ej: claude-3-5-sonnet-20241022)",
-                    )
-                    .interact_text()?
+                        .with_prompt(t("ui.modelo_manual_ejemplo"))
+                        .interact_text()?
                 }
                 _ => {
-                    println!("⚠️  La lista de modelos está vacía.");
+                    println!("{}", t("ui.lista_modelos_vacia"));
                     Input::with_theme(&ColorfulTheme::default())
-                        .with_prompt("Ingresa el nombre del modelo manualmente")
+                        .with_prompt(t("ui.modelo_manual"))
                         .interact_text()?
                 }
             };
@@ -480,10 +634,10 @@ pub fn ask_ai_configs() -> anyhow::Result<Vec<AIConfig>> {
             model,
         });
 
-        println!("✅ Configuración añadida.");
+        println!("{}", t("ui.config_anadida"));
 
         let add_another = Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("¿Deseas agregar otro modelo de IA?")
+            .with_prompt(t("ui.agregar_otro_modelo"))
             .default(false)
             .interact()?;
 
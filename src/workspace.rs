@@ -0,0 +1,667 @@
+//! # Soporte de monorepos y workspaces
+//!
+//! Descubre los paquetes de un workspace (npm/pnpm/yarn workspaces, Cargo
+//! workspaces, Go workspaces y layouts Nx/Turborepo), detecta el framework y
+//! el runner de tests de cada uno, y resuelve a qué paquete pertenece cada
+//! archivo modificado para usar sus reglas, patrones y comando de tests.
+
+use crate::config::{PackageConfig, SentinelConfig};
+use crate::detector::{self, ResultadoDeteccion};
+use crate::files;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directorios que nunca contienen paquetes del workspace
+const DIRECTORIOS_EXCLUIDOS: &[&str] = &["node_modules", ".git", "target", "dist", "vendor"];
+
+/// Manifiestos que identifican un directorio como paquete
+const MANIFIESTOS: &[&str] = &[
+    "package.json",
+    "Cargo.toml",
+    "go.mod",
+    "pyproject.toml",
+    "composer.json",
+    "project.json",
+];
+
+/// Contexto de un archivo modificado dentro del proyecto
+pub struct ContextoArchivo {
+    /// Configuración efectiva (con los valores del paquete si aplica)
+    pub config: SentinelConfig,
+    /// Raíz del paquete (o del proyecto si no pertenece a ningún paquete)
+    pub root: PathBuf,
+    /// Nombre del paquete, si el archivo pertenece a uno
+    pub paquete: Option<String>,
+}
+
+/// Descubre los directorios de paquetes declarados por el workspace
+///
+/// Combina todas las fuentes encontradas en la raíz: `workspaces` de
+/// `package.json`, `pnpm-workspace.yaml`, `[workspace].members` de
+/// `Cargo.toml` y `go.work`. Si no hay declaración explícita pero existe
+/// `nx.json` o `turbo.json`, se usan las convenciones `apps/*`, `packages/*`
+/// y `libs/*`.
+///
+/// # Retorna
+/// Rutas absolutas de los paquetes (ordenadas, sin duplicados ni la raíz).
+pub fn descubrir_paquetes(project_path: &Path) -> Vec<PathBuf> {
+    let mut patrones: Vec<String> = Vec::new();
+
+    patrones.extend(patrones_npm(project_path));
+    patrones.extend(patrones_pnpm(project_path));
+    patrones.extend(patrones_cargo(project_path));
+    patrones.extend(patrones_go_work(project_path));
+
+    if patrones.is_empty()
+        && (project_path.join("nx.json").exists() || project_path.join("turbo.json").exists())
+    {
+        patrones = vec![
+            "apps/*".to_string(),
+            "packages/*".to_string(),
+            "libs/*".to_string(),
+        ];
+    }
+
+    // Los patrones `!ruta` excluyen paquetes (npm, pnpm y yarn los aceptan)
+    let (negados, incluidos): (Vec<&String>, Vec<&String>) =
+        patrones.iter().partition(|p| p.starts_with('!'));
+    let excluidos: Vec<PathBuf> = negados
+        .iter()
+        .flat_map(|p| expandir_patron(project_path, &p[1..]))
+        .collect();
+
+    let mut paquetes: Vec<PathBuf> = incluidos
+        .iter()
+        .flat_map(|p| expandir_patron(project_path, p))
+        .filter(|dir| *dir != project_path && es_paquete(dir) && !excluidos.contains(dir))
+        .collect();
+
+    paquetes.sort();
+    paquetes.dedup();
+    paquetes
+}
+
+/// Descubre los paquetes y detecta framework, gestor y runner de cada uno
+///
+/// Los paquetes sin framework reconocible heredan el de la raíz (`config`).
+pub fn detectar_paquetes(project_path: &Path, config: &SentinelConfig) -> Vec<PackageConfig> {
+    let manager_raiz = SentinelConfig::detectar_gestor(project_path);
+
+    descubrir_paquetes(project_path)
+        .into_iter()
+        .map(|dir| {
+            let path = ruta_relativa(project_path, &dir);
+            let name = nombre_paquete(&dir).unwrap_or_else(|| path.clone());
+
+            let deteccion = match detector::detectar_framework_local(&dir) {
+                ResultadoDeteccion::Detectado(d) => Some(d),
+                ResultadoDeteccion::Ambiguo(candidatos) => candidatos.into_iter().next(),
+                ResultadoDeteccion::Desconocido => None,
+            };
+
+            // En workspaces JS el lockfile vive en la raíz
+            let manager = match SentinelConfig::detectar_gestor(&dir) {
+                m if dir.join("package.json").exists() && !tiene_lockfile(&dir) => {
+                    if ["pnpm", "yarn", "bun", "npm"].contains(&manager_raiz.as_str()) {
+                        manager_raiz.clone()
+                    } else {
+                        m
+                    }
+                }
+                m => m,
+            };
+
+            let (framework, code_language, rules, parent_patterns, test_patterns) = match deteccion
+            {
+                Some(d) => (
+                    d.framework,
+                    d.code_language,
+                    d.rules,
+                    d.parent_patterns,
+                    d.test_patterns,
+                ),
+                None => (
+                    config.framework.clone(),
+                    config.code_language.clone(),
+                    vec![],
+                    vec![],
+                    vec![],
+                ),
+            };
+
+            PackageConfig {
+                name,
+                path,
+                framework,
+                code_language,
+                test_command: SentinelConfig::comando_test_por_defecto(&manager),
+                manager,
+                testing_framework: detector::detectar_test_runner(&dir),
+                architecture_rules: rules,
+                parent_patterns,
+                test_patterns,
+            }
+        })
+        .collect()
+}
+
+/// Fusiona los paquetes recién detectados con los guardados en la configuración
+///
+/// Los paquetes que ya existían conservan su entrada (y las personalizaciones
+/// que el usuario haya hecho); se agregan los nuevos y se eliminan los que ya
+/// no forman parte del workspace.
+pub fn fusionar_paquetes(
+    existentes: &[PackageConfig],
+    detectados: Vec<PackageConfig>,
+) -> Vec<PackageConfig> {
+    detectados
+        .into_iter()
+        .map(|nuevo| {
+            existentes
+                .iter()
+                .find(|p| p.path == nuevo.path)
+                .cloned()
+                .unwrap_or(nuevo)
+        })
+        .collect()
+}
+
+/// Busca el paquete que contiene un archivo (el de ruta más específica)
+pub fn paquete_de<'a>(
+    config: &'a SentinelConfig,
+    project_path: &Path,
+    file_path: &Path,
+) -> Option<&'a PackageConfig> {
+    config
+        .packages
+        .iter()
+        .filter(|p| file_path.starts_with(project_path.join(&p.path)))
+        .max_by_key(|p| p.path.len())
+}
+
+/// Resuelve la configuración y la raíz a usar para un archivo modificado
+pub fn contexto_para(
+    config: &SentinelConfig,
+    project_path: &Path,
+    file_path: &Path,
+) -> ContextoArchivo {
+    match paquete_de(config, project_path, file_path) {
+        Some(paquete) => ContextoArchivo {
+            config: config.para_paquete(paquete),
+            root: project_path.join(&paquete.path),
+            paquete: Some(paquete.name.clone()),
+        },
+        None => ContextoArchivo {
+            config: config.clone(),
+            root: project_path.to_path_buf(),
+            paquete: None,
+        },
+    }
+}
+
+/// Filtro del watcher: cada archivo se evalúa con la configuración de su paquete
+///
+/// Así un servicio Go dentro de un workspace TypeScript revisa sus `.go`
+/// aunque la raíz solo vigile `ts`.
+pub fn debe_ignorar(config: &SentinelConfig, project_path: &Path, file_path: &Path) -> bool {
+    match paquete_de(config, project_path, file_path) {
+        Some(paquete) => config.para_paquete(paquete).debe_ignorar(file_path),
+        None => config.debe_ignorar(file_path),
+    }
+}
+
+/// Directorios a vigilar: `src/` de la raíz y cada paquete del workspace
+pub fn directorios_a_vigilar(config: &SentinelConfig, project_path: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if project_path.join("src").is_dir() || config.packages.is_empty() {
+        dirs.push(project_path.join("src"));
+    }
+    for paquete in &config.packages {
+        let dir = project_path.join(&paquete.path);
+        if dir.is_dir() && !dirs.iter().any(|d| dir.starts_with(d)) {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+fn patrones_npm(project_path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(project_path.join("package.json")) else {
+        return vec![];
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return vec![];
+    };
+
+    // "workspaces": ["packages/*"] o "workspaces": { "packages": [...] } (yarn)
+    let lista = match &json["workspaces"] {
+        serde_json::Value::Array(items) => items.clone(),
+        serde_json::Value::Object(obj) => obj
+            .get("packages")
+            .and_then(|p| p.as_array())
+            .cloned()
+            .unwrap_or_default(),
+        _ => vec![],
+    };
+
+    lista
+        .iter()
+        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+        .collect()
+}
+
+/// Lee la lista `packages:` de `pnpm-workspace.yaml` (solo el subconjunto de YAML que usa pnpm)
+fn patrones_pnpm(project_path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(project_path.join("pnpm-workspace.yaml")) else {
+        return vec![];
+    };
+
+    let mut patrones = Vec::new();
+    let mut en_packages = false;
+    for linea in content.lines() {
+        let sin_comentario = linea.split('#').next().unwrap_or("");
+        if sin_comentario.trim().is_empty() {
+            continue;
+        }
+        if !linea.starts_with(' ') && !linea.starts_with('-') {
+            en_packages = sin_comentario.trim() == "packages:";
+            continue;
+        }
+        if en_packages && let Some(item) = sin_comentario.trim().strip_prefix('-') {
+            patrones.push(item.trim().trim_matches(['\'', '"']).to_string());
+        }
+    }
+    patrones
+}
+
+fn patrones_cargo(project_path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(project_path.join("Cargo.toml")) else {
+        return vec![];
+    };
+    let Ok(manifest) = toml::from_str::<toml::Value>(&content) else {
+        return vec![];
+    };
+
+    let leer = |clave: &str| -> Vec<String> {
+        manifest
+            .get("workspace")
+            .and_then(|w| w.get(clave))
+            .and_then(|m| m.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let excluidos = leer("exclude");
+    leer("members")
+        .into_iter()
+        .filter(|m| !excluidos.contains(m))
+        .collect()
+}
+
+/// Lee las directivas `use` de `go.work` (forma simple y en bloque)
+fn patrones_go_work(project_path: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(project_path.join("go.work")) else {
+        return vec![];
+    };
+
+    let mut patrones = Vec::new();
+    let mut en_bloque = false;
+    for linea in content.lines() {
+        let linea = linea.split("//").next().unwrap_or("").trim();
+        if en_bloque {
+            if linea == ")" {
+                en_bloque = false;
+            } else if !linea.is_empty() {
+                patrones.push(linea.to_string());
+            }
+        } else if linea == "use (" {
+            en_bloque = true;
+        } else if let Some(ruta) = linea.strip_prefix("use ") {
+            patrones.push(ruta.trim().to_string());
+        }
+    }
+    patrones
+}
+
+/// Expande un patrón de workspace (`packages/*`, `apps/**`, `crates/core`) a directorios
+fn expandir_patron(project_path: &Path, patron: &str) -> Vec<PathBuf> {
+    let patron = patron.trim().trim_start_matches("./").trim_end_matches('/');
+    let segmentos: Vec<&str> = patron.split('/').filter(|s| !s.is_empty()).collect();
+
+    let mut actuales = vec![project_path.to_path_buf()];
+    for segmento in segmentos {
+        let mut siguientes = Vec::new();
+        for dir in &actuales {
+            if segmento == "**" {
+                recolectar_subdirectorios(dir, 4, &mut siguientes);
+            } else if segmento.contains('*') {
+                siguientes.extend(
+                    subdirectorios(dir)
+                        .into_iter()
+                        .filter(|d| nombre_coincide(d, segmento)),
+                );
+            } else {
+                let candidato = dir.join(segmento);
+                if candidato.is_dir() {
+                    siguientes.push(candidato);
+                }
+            }
+        }
+        actuales = siguientes;
+    }
+    actuales
+}
+
+fn subdirectorios(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| !n.starts_with('.') && !DIRECTORIOS_EXCLUIDOS.contains(&n))
+        })
+        .collect()
+}
+
+fn recolectar_subdirectorios(dir: &Path, profundidad: usize, salida: &mut Vec<PathBuf>) {
+    salida.push(dir.to_path_buf());
+    if profundidad == 0 {
+        return;
+    }
+    for sub in subdirectorios(dir) {
+        recolectar_subdirectorios(&sub, profundidad - 1, salida);
+    }
+}
+
+fn nombre_coincide(dir: &Path, segmento: &str) -> bool {
+    dir.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|nombre| files::coincide_comodin(nombre, segmento))
+}
+
+fn es_paquete(dir: &Path) -> bool {
+    MANIFIESTOS.iter().any(|m| dir.join(m).exists())
+}
+
+fn tiene_lockfile(dir: &Path) -> bool {
+    [
+        "pnpm-lock.yaml",
+        "yarn.lock",
+        "package-lock.json",
+        "bun.lockb",
+        "bun.lock",
+    ]
+    .iter()
+    .any(|f| dir.join(f).exists())
+}
+
+/// Nombre declarado en el manifiesto del paquete (`name` de package.json o Cargo.toml, módulo de go.mod)
+fn nombre_paquete(dir: &Path) -> Option<String> {
+    if let Ok(content) = fs::read_to_string(dir.join("package.json"))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(&content)
+        && let Some(nombre) = json["name"].as_str()
+    {
+        return Some(nombre.to_string());
+    }
+    if let Ok(content) = fs::read_to_string(dir.join("Cargo.toml"))
+        && let Ok(manifest) = toml::from_str::<toml::Value>(&content)
+        && let Some(nombre) = manifest
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+    {
+        return Some(nombre.to_string());
+    }
+    if let Ok(content) = fs::read_to_string(dir.join("go.mod")) {
+        return content
+            .lines()
+            .find_map(|l| l.strip_prefix("module "))
+            .map(|m| m.trim().to_string());
+    }
+    None
+}
+
+fn ruta_relativa(project_path: &Path, dir: &Path) -> String {
+    dir.strip_prefix(project_path)
+        .unwrap_or(dir)
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn crear_paquete(root: &Path, ruta: &str, package_json: &str) {
+        let dir = root.join(ruta);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("package.json"), package_json).unwrap();
+    }
+
+    fn config_raiz() -> SentinelConfig {
+        SentinelConfig::default(
+            "mono".to_string(),
+            "pnpm".to_string(),
+            "Node.js".to_string(),
+            vec![],
+            vec!["ts".to_string()],
+            "typescript".to_string(),
+            vec![],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_descubre_npm_workspaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("package.json"),
+            r#"{"private": true, "workspaces": ["packages/*", "apps/web"]}"#,
+        )
+        .unwrap();
+        crear_paquete(root, "packages/ui", r#"{"name": "@acme/ui"}"#);
+        crear_paquete(root, "packages/api", r#"{"name": "@acme/api"}"#);
+        crear_paquete(root, "apps/web", r#"{"name": "web"}"#);
+        // Carpeta sin manifiesto: no es un paquete
+        fs::create_dir_all(root.join("packages/docs")).unwrap();
+
+        let paquetes: Vec<String> = descubrir_paquetes(root)
+            .iter()
+            .map(|p| ruta_relativa(root, p))
+            .collect();
+
+        assert_eq!(paquetes, vec!["apps/web", "packages/api", "packages/ui"]);
+    }
+
+    #[test]
+    fn test_descubre_pnpm_workspace_yaml() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("pnpm-workspace.yaml"),
+            "packages:\n  - 'apps/*'\n  # comentario\n  - \"!apps/legacy\"\n",
+        )
+        .unwrap();
+        crear_paquete(root, "apps/admin", r#"{"name": "admin"}"#);
+        crear_paquete(root, "apps/legacy", r#"{"name": "legacy"}"#);
+
+        let paquetes = descubrir_paquetes(root);
+        assert_eq!(paquetes, vec![root.join("apps/admin")]);
+    }
+
+    #[test]
+    fn test_descubre_cargo_y_go_workspaces() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/*\"]\nexclude = []\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("crates/core")).unwrap();
+        fs::write(
+            root.join("crates/core/Cargo.toml"),
+            "[package]\nname = \"core\"\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("go.work"),
+            "go 1.22\n\nuse (\n\t./services/auth\n)\n",
+        )
+        .unwrap();
+        fs::create_dir_all(root.join("services/auth")).unwrap();
+        fs::write(
+            root.join("services/auth/go.mod"),
+            "module example.com/auth\n",
+        )
+        .unwrap();
+
+        let paquetes = descubrir_paquetes(root);
+        assert_eq!(
+            paquetes,
+            vec![root.join("crates/core"), root.join("services/auth")]
+        );
+        assert_eq!(
+            nombre_paquete(&root.join("services/auth")),
+            Some("example.com/auth".to_string())
+        );
+    }
+
+    #[test]
+    fn test_detectar_paquetes_por_framework() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join("package.json"), r#"{"workspaces": ["apps/*"]}"#).unwrap();
+        fs::write(root.join("pnpm-lock.yaml"), "").unwrap();
+        crear_paquete(
+            root,
+            "apps/api",
+            r#"{"name": "api", "dependencies": {"@nestjs/core": "10"}, "devDependencies": {"jest": "29"}}"#,
+        );
+        crear_paquete(
+            root,
+            "apps/web",
+            r#"{"name": "web", "dependencies": {"next": "14"}, "devDependencies": {"vitest": "1"}}"#,
+        );
+
+        let paquetes = detectar_paquetes(root, &config_raiz());
+
+        assert_eq!(paquetes.len(), 2);
+        assert_eq!(paquetes[0].framework, "NestJS");
+        assert_eq!(paquetes[0].testing_framework.as_deref(), Some("Jest"));
+        assert_eq!(paquetes[0].manager, "pnpm");
+        assert_eq!(paquetes[1].framework, "Next.js");
+        assert_eq!(paquetes[1].testing_framework.as_deref(), Some("Vitest"));
+    }
+
+    #[test]
+    fn test_contexto_para_archivo_usa_paquete_mas_especifico() {
+        let root = Path::new("/repo");
+        let mut config = config_raiz();
+        let paquete = |path: &str, framework: &str| PackageConfig {
+            name: path.to_string(),
+            path: path.to_string(),
+            framework: framework.to_string(),
+            code_language: "typescript".to_string(),
+            manager: "pnpm".to_string(),
+            test_command: "pnpm run test".to_string(),
+            testing_framework: Some("Jest".to_string()),
+            architecture_rules: vec![],
+            parent_patterns: vec![],
+            test_patterns: vec!["src/{name}.spec.ts".to_string()],
+        };
+        config.packages = vec![paquete("apps", "Express"), paquete("apps/api", "NestJS")];
+
+        let contexto = contexto_para(&config, root, Path::new("/repo/apps/api/src/user.ts"));
+        assert_eq!(contexto.paquete.as_deref(), Some("apps/api"));
+        assert_eq!(contexto.root, PathBuf::from("/repo/apps/api"));
+        assert_eq!(contexto.config.framework, "NestJS");
+        assert_eq!(contexto.config.test_patterns, vec!["src/{name}.spec.ts"]);
+
+        let raiz = contexto_para(&config, root, Path::new("/repo/src/main.ts"));
+        assert!(raiz.paquete.is_none());
+        assert_eq!(raiz.config.framework, "Node.js");
+
+        // Un servicio Go en un workspace TypeScript vigila también sus .go
+        config.packages.push(paquete("services/billing", "Go"));
+        let go = Path::new("/repo/services/billing/main.go");
+        assert!(config.debe_ignorar(go));
+        assert!(!contexto_para(&config, root, go).config.debe_ignorar(go));
+    }
+
+    #[test]
+    fn test_watcher_acepta_go_en_workspace_typescript() {
+        let root = Path::new("/repo");
+        let mut config = config_raiz();
+        config.packages = vec![PackageConfig {
+            name: "billing".to_string(),
+            path: "services/billing".to_string(),
+            framework: "Go".to_string(),
+            code_language: "go".to_string(),
+            manager: "go".to_string(),
+            test_command: "go test ./...".to_string(),
+            testing_framework: Some("Go test".to_string()),
+            architecture_rules: vec![],
+            parent_patterns: vec![],
+            test_patterns: vec!["{name}_test.go".to_string()],
+        }];
+
+        assert!(!debe_ignorar(
+            &config,
+            root,
+            Path::new("/repo/services/billing/main.go")
+        ));
+        assert!(debe_ignorar(
+            &config,
+            root,
+            Path::new("/repo/services/billing/main_test.go")
+        ));
+        // Fuera del paquete sigue mandando la raíz
+        assert!(debe_ignorar(&config, root, Path::new("/repo/src/tool.go")));
+        assert!(!debe_ignorar(&config, root, Path::new("/repo/src/main.ts")));
+    }
+
+    #[test]
+    fn test_fusionar_paquetes_conserva_personalizaciones() {
+        let mut existente = detectar_paquetes(Path::new("/no-existe"), &config_raiz());
+        assert!(existente.is_empty());
+
+        let base = PackageConfig {
+            name: "api".to_string(),
+            path: "apps/api".to_string(),
+            framework: "NestJS".to_string(),
+            code_language: "typescript".to_string(),
+            manager: "npm".to_string(),
+            test_command: "npm run test:unit".to_string(),
+            testing_framework: None,
+            architecture_rules: vec![],
+            parent_patterns: vec![],
+            test_patterns: vec![],
+        };
+        existente.push(base.clone());
+
+        let mut detectado = base.clone();
+        detectado.test_command = "npm run test".to_string();
+        let mut nuevo = base.clone();
+        nuevo.path = "apps/web".to_string();
+
+        let fusionados = fusionar_paquetes(&existente, vec![detectado, nuevo]);
+        assert_eq!(fusionados.len(), 2);
+        assert_eq!(fusionados[0].test_command, "npm run test:unit");
+        assert_eq!(fusionados[1].path, "apps/web");
+    }
+}